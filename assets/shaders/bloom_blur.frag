@@ -0,0 +1,22 @@
+#version 460
+
+in vec2 v_uv;
+
+out vec4 color;
+
+uniform sampler2D u_color;
+uniform vec2 u_texel_size;
+// (1, 0) for the horizontal pass and (0, 1) for the vertical one
+uniform vec2 u_direction;
+
+const float WEIGHTS[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec2 offset = u_direction * u_texel_size;
+    vec3 sum = texture(u_color, v_uv).rgb * WEIGHTS[0];
+    for (int i = 1; i < 5; i++) {
+        sum += texture(u_color, v_uv + offset * i).rgb * WEIGHTS[i];
+        sum += texture(u_color, v_uv - offset * i).rgb * WEIGHTS[i];
+    }
+    color = vec4(sum, 1.0);
+}
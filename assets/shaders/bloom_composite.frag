@@ -0,0 +1,14 @@
+#version 460
+
+in vec2 v_uv;
+
+out vec4 color;
+
+uniform sampler2D u_color;
+uniform sampler2D u_bloom;
+uniform float u_intensity;
+
+void main() {
+    vec4 scene = texture(u_color, v_uv);
+    color = vec4(scene.rgb + texture(u_bloom, v_uv).rgb * u_intensity, scene.a);
+}
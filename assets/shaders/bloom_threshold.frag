@@ -0,0 +1,21 @@
+#version 460
+
+in vec2 v_uv;
+
+out vec4 color;
+
+uniform sampler2D u_color;
+uniform vec2 u_texel_size;
+uniform float u_threshold;
+
+// Downsamples to half resolution keeping only the pixels brighter than the threshold
+void main() {
+    vec3 sum = texture(u_color, v_uv + vec2(-0.5, -0.5) * u_texel_size).rgb
+        + texture(u_color, v_uv + vec2(0.5, -0.5) * u_texel_size).rgb
+        + texture(u_color, v_uv + vec2(-0.5, 0.5) * u_texel_size).rgb
+        + texture(u_color, v_uv + vec2(0.5, 0.5) * u_texel_size).rgb;
+    vec3 average = sum * 0.25;
+    float brightness = max(average.r, max(average.g, average.b));
+    float contribution = max(brightness - u_threshold, 0.0) / max(brightness, 0.0001);
+    color = vec4(average * contribution, 1.0);
+}
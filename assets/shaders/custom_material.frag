@@ -0,0 +1,29 @@
+#version 460
+#include "shaders/lights.glsl"
+
+in vec3 v_position;
+in vec3 v_normal;
+in vec2 v_tex_coord;
+
+out vec4 color;
+
+uniform material {
+    vec4 u_color;
+    // width of the stripes in texture coordinates
+    float u_stripe_width;
+};
+
+void main() {
+    vec3 normal = normalize(v_normal);
+    vec3 light = u_ambient.rgb;
+    for (uint i = 0; i < u_light_count; i++) {
+        Light l = u_lights[i];
+        vec3 light_dir = int(l.position.w) == DIRECTIONAL_LIGHT
+            ? normalize(-l.direction.xyz)
+            : normalize(l.position.xyz - v_position);
+        light += max(dot(normal, light_dir), 0.0) * l.color.rgb;
+    }
+
+    float stripe = step(0.5, fract(v_tex_coord.x / u_stripe_width));
+    color = vec4(mix(u_color.rgb, vec3(1.0), stripe) * light, u_color.a);
+}
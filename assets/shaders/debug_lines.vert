@@ -0,0 +1,13 @@
+#version 460
+
+uniform mat4 u_pv_matrix;
+
+in vec3 position;
+in vec4 color;
+
+out vec4 v_color;
+
+void main() {
+    gl_Position = u_pv_matrix * vec4(position, 1.0);
+    v_color = color;
+}
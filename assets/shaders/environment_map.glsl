@@ -0,0 +1,15 @@
+// The `u_environment_map` sampler bound by the MaterialPlugin when the view has a Skybox,
+// included after "shaders/lights.glsl"
+#ifdef ENVIRONMENT_MAP
+uniform samplerCube u_environment_map;
+#endif
+
+// The color reflected by a mirror at `position`, black without a skybox
+vec3 environment_reflection(vec3 position, vec3 normal) {
+#ifdef ENVIRONMENT_MAP
+    vec3 view_dir = normalize(position - u_camera_pos.xyz);
+    return texture(u_environment_map, reflect(view_dir, normal)).rgb;
+#else
+    return vec3(0.0);
+#endif
+}
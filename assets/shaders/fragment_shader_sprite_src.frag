@@ -1,5 +1,5 @@
 #version 460
-#extension GL_ARB_bindless_texture : require
+#include "shaders/textures.glsl"
 
 in vec4 v_color;
 in vec2 v_tex_coord;
@@ -7,11 +7,7 @@ flat in uint v_tex_id;
 
 out vec4 color;
 
-uniform samplers {
-    sampler2D tex[5];
-};
-
 void main() {
-    vec4 tex_color = texture(tex[v_tex_id], v_tex_coord) * v_color;
+    vec4 tex_color = texture(BASE_COLOR_TEXTURE, v_tex_coord) * v_color;
     color = tex_color; 
 }
\ No newline at end of file
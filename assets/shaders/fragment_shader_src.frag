@@ -1,41 +1,137 @@
 #version 460
-#extension GL_ARB_bindless_texture : require
+#include "shaders/textures.glsl"
+#include "shaders/lights.glsl"
 
 in vec3 v_position;
 in vec3 v_normal;
 in vec4 v_color;
+in vec3 v_emissive;
+// x: specular strength, y: shininess, z: alpha cutoff, w: receives shadows
+flat in vec4 v_material;
 in vec2 v_tex_coord;
 flat in uint v_tex_id;
+flat in int v_normal_tex_id;
+#ifdef VERTEX_TANGENT
+in vec4 v_tangent;
+#endif
 
 out vec4 color;
 
-uniform samplers {
-    sampler2D tex[5];
-};
+uniform sampler2DArrayShadow shadow_maps;
 
-uniform bplight {
-    vec4 u_camera_pos;
-    vec4 u_light_pos;
-};
+// Tangent frame from the screen space derivatives of the position and texture coordinates
+// http://www.thetenthplanet.de/archives/1180
+mat3 cotangent_frame(vec3 normal, vec3 position, vec2 uv) {
+    vec3 dp1 = dFdx(position);
+    vec3 dp2 = dFdy(position);
+    vec2 duv1 = dFdx(uv);
+    vec2 duv2 = dFdy(uv);
 
-const float shineness = 32.0;
-const vec3 light_color = vec3(0.3);
+    vec3 dp2perp = cross(dp2, normal);
+    vec3 dp1perp = cross(normal, dp1);
+    vec3 tangent = dp2perp * duv1.x + dp1perp * duv2.x;
+    vec3 bitangent = dp2perp * duv1.y + dp1perp * duv2.y;
+
+    float invmax = inversesqrt(max(dot(tangent, tangent), dot(bitangent, bitangent)));
+    return mat3(tangent * invmax, bitangent * invmax, normal);
+}
+
+// Fraction of the light reaching the fragment, averaged over 3x3 shadow map texels
+float shadow_visibility(Light light, vec3 normal, vec3 light_dir) {
+    float layer = light.cone.z;
+    if (layer < 0.0 || v_material.w < 0.5) {
+        return 1.0;
+    }
+    vec4 clip = light.view_projection * vec4(v_position, 1.0);
+    vec3 coords = clip.xyz / clip.w * 0.5 + 0.5;
+    if (coords.z > 1.0 || any(lessThan(coords.xy, vec2(0.0))) || any(greaterThan(coords.xy, vec2(1.0)))) {
+        return 1.0;
+    }
+
+    float bias = max(0.005 * (1.0 - dot(normal, light_dir)), 0.0005);
+    vec2 texel_size = 1.0 / vec2(textureSize(shadow_maps, 0).xy);
+    float visibility = 0.0;
+    for (int x = -1; x <= 1; x++) {
+        for (int y = -1; y <= 1; y++) {
+            vec2 uv = coords.xy + vec2(x, y) * texel_size;
+            visibility += texture(shadow_maps, vec4(uv, layer, coords.z - bias));
+        }
+    }
+    return visibility / 9.0;
+}
 
 void main() {
-    vec4 tex_color = texture(tex[v_tex_id], v_tex_coord) * v_color;
+    vec4 tex_color = texture(BASE_COLOR_TEXTURE, v_tex_coord) * v_color;
+    if (tex_color.w < v_material.z) {
+        discard;
+    }
     vec3 base_color = vec3(tex_color);
+    float specular_strength = v_material.x;
+    float shineness = v_material.y;
 
     vec3 normal = normalize(v_normal);
+    if (!gl_FrontFacing) {
+        normal = -normal;
+    }
+    if (v_normal_tex_id >= 0) {
+        // normal maps are uploaded without the sRGB conversion
+        vec3 sampled = texture(NORMAL_MAP_TEXTURE, v_tex_coord).rgb;
+#ifdef VERTEX_TANGENT
+        vec3 tangent = normalize(v_tangent.xyz - normal * dot(normal, v_tangent.xyz));
+        vec3 bitangent = cross(normal, tangent) * v_tangent.w;
+        mat3 tbn = mat3(tangent, bitangent, normal);
+#else
+        mat3 tbn = cotangent_frame(normal, v_position, v_tex_coord);
+#endif
+        normal = normalize(tbn * (sampled * 2.0 - 1.0));
+    }
+#ifdef DEBUG_NORMALS
+    color = vec4(normal * 0.5 + 0.5, 1.0);
+    return;
+#endif
+#ifdef DEBUG_UVS
+    color = vec4(fract(v_tex_coord), 0.0, 1.0);
+    return;
+#endif
+#ifdef DEBUG_DEPTH
+    color = vec4(vec3(exp(-0.1 * distance(vec3(u_camera_pos), v_position))), 1.0);
+    return;
+#endif
     vec3 camera_dir = normalize(vec3(u_camera_pos) - v_position);
-    vec3 light_dir = normalize(vec3(u_light_pos) - v_position);
-    vec3 half_direction = normalize(light_dir + camera_dir);
-    
-    float diffuse = max(dot(normal, light_dir), 0.0);
-    float specular = pow(max(dot(normal, half_direction), 0.0), shineness);
 
-    vec3 diffuse_color = base_color * diffuse;
-    vec3 ambient_color = base_color * 0.1;
-    vec3 specular_color = light_color * specular;
+    vec3 diffuse_color = vec3(0.0);
+    vec3 specular_color = vec3(0.0);
+    for (uint i = 0; i < u_light_count; i++) {
+        Light light = u_lights[i];
+        int kind = int(light.position.w);
+
+        vec3 light_dir;
+        float attenuation = 1.0;
+        if (kind == DIRECTIONAL_LIGHT) {
+            light_dir = -normalize(vec3(light.direction));
+        } else {
+            vec3 to_light = vec3(light.position) - v_position;
+            float distance = length(to_light);
+            light_dir = to_light / distance;
+            // fades smoothly to zero at the light range
+            float falloff = clamp(1.0 - pow(distance / light.color.w, 4.0), 0.0, 1.0);
+            attenuation = falloff * falloff;
+            if (kind == SPOT_LIGHT) {
+                float theta = dot(light_dir, -normalize(vec3(light.direction)));
+                attenuation *= smoothstep(light.cone.y, light.cone.x, theta);
+            }
+        }
+
+        attenuation *= shadow_visibility(light, normal, light_dir);
+        vec3 radiance = vec3(light.color) * attenuation;
+        vec3 half_direction = normalize(light_dir + camera_dir);
+        float diffuse = max(dot(normal, light_dir), 0.0);
+        float specular = pow(max(dot(normal, half_direction), 0.0), shineness);
+
+        diffuse_color += base_color * diffuse * radiance;
+        specular_color += radiance * specular * specular_strength;
+    }
+    vec3 ambient_color = base_color * vec3(u_ambient);
 
-    color = vec4(ambient_color + diffuse_color + specular_color, tex_color.w); 
+    color = vec4(ambient_color + diffuse_color + specular_color + v_emissive, tex_color.w);
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+#version 460
+
+out vec2 v_uv;
+
+// A triangle covering the whole screen, no vertex buffer needed
+void main() {
+    v_uv = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+    gl_Position = vec4(v_uv * 2.0 - 1.0, 0.0, 1.0);
+}
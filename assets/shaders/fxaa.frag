@@ -0,0 +1,53 @@
+#version 460
+
+in vec2 v_uv;
+
+out vec4 color;
+
+uniform sampler2D u_color;
+uniform vec2 u_texel_size;
+
+const float FXAA_REDUCE_MIN = 1.0 / 128.0;
+const float FXAA_REDUCE_MUL = 1.0 / 8.0;
+const float FXAA_SPAN_MAX = 8.0;
+const vec3 LUMA = vec3(0.299, 0.587, 0.114);
+
+void main() {
+    vec4 center = texture(u_color, v_uv);
+    float luma_nw = dot(texture(u_color, v_uv + vec2(-1.0, -1.0) * u_texel_size).rgb, LUMA);
+    float luma_ne = dot(texture(u_color, v_uv + vec2(1.0, -1.0) * u_texel_size).rgb, LUMA);
+    float luma_sw = dot(texture(u_color, v_uv + vec2(-1.0, 1.0) * u_texel_size).rgb, LUMA);
+    float luma_se = dot(texture(u_color, v_uv + vec2(1.0, 1.0) * u_texel_size).rgb, LUMA);
+    float luma_m = dot(center.rgb, LUMA);
+
+    float luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    float luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // blur along the edge, perpendicular to the luma gradient
+    vec2 dir = vec2(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+        (luma_nw + luma_sw) - (luma_ne + luma_se)
+    );
+    float dir_reduce = max(
+        (luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * FXAA_REDUCE_MUL,
+        FXAA_REDUCE_MIN
+    );
+    float rcp_dir_min = 1.0 / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+    dir = clamp(dir * rcp_dir_min, vec2(-FXAA_SPAN_MAX), vec2(FXAA_SPAN_MAX)) * u_texel_size;
+
+    vec3 rgb_a = 0.5 * (
+        texture(u_color, v_uv + dir * (1.0 / 3.0 - 0.5)).rgb +
+        texture(u_color, v_uv + dir * (2.0 / 3.0 - 0.5)).rgb
+    );
+    vec3 rgb_b = rgb_a * 0.5 + 0.25 * (
+        texture(u_color, v_uv - dir * 0.5).rgb +
+        texture(u_color, v_uv + dir * 0.5).rgb
+    );
+
+    float luma_b = dot(rgb_b, LUMA);
+    if (luma_b < luma_min || luma_b > luma_max) {
+        color = vec4(rgb_a, center.a);
+    } else {
+        color = vec4(rgb_b, center.a);
+    }
+}
@@ -0,0 +1,23 @@
+// The `lights` uniform block bound by the core pipeline and the MaterialPlugin
+#define MAX_LIGHTS 16
+#define DIRECTIONAL_LIGHT 0
+#define POINT_LIGHT 1
+#define SPOT_LIGHT 2
+
+struct Light {
+    // w: light kind
+    vec4 position;
+    // rgb: color * intensity, w: range
+    vec4 color;
+    vec4 direction;
+    // x: cos(inner_angle), y: cos(outer_angle), z: shadow map layer or -1
+    vec4 cone;
+    mat4 view_projection;
+};
+
+uniform lights {
+    vec4 u_camera_pos;
+    vec4 u_ambient;
+    Light u_lights[MAX_LIGHTS];
+    uint u_light_count;
+};
@@ -0,0 +1,56 @@
+#version 460
+
+uniform camera {
+    mat4 u_pv_matrix;
+};
+
+// per vertex
+in vec3 position;
+#ifdef VERTEX_NORMAL
+in vec3 normal;
+#endif
+#ifdef VERTEX_TEX_COORD
+in vec2 tex_coord;
+#endif
+#ifdef VERTEX_COLOR
+in vec4 color;
+#endif
+#ifdef VERTEX_TANGENT
+in vec4 tangent;
+#endif
+
+// per instance
+in mat4 i_model;
+in mat3 i_normal_matrix;
+
+out vec3 v_position;
+out vec3 v_normal;
+out vec2 v_tex_coord;
+#ifdef VERTEX_COLOR
+out vec4 v_color;
+#endif
+#ifdef VERTEX_TANGENT
+out vec4 v_tangent;
+#endif
+
+void main() {
+    vec4 world_position = i_model * vec4(position, 1.0);
+    gl_Position = u_pv_matrix * world_position;
+    v_position = world_position.xyz;
+#ifdef VERTEX_NORMAL
+    v_normal = i_normal_matrix * normal;
+#else
+    v_normal = i_normal_matrix * vec3(0.0, 0.0, 1.0);
+#endif
+#ifdef VERTEX_TEX_COORD
+    v_tex_coord = tex_coord;
+#else
+    v_tex_coord = vec2(0.0);
+#endif
+#ifdef VERTEX_COLOR
+    v_color = color;
+#endif
+#ifdef VERTEX_TANGENT
+    v_tangent = vec4(mat3(i_model) * tangent.xyz, tangent.w);
+#endif
+}
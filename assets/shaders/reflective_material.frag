@@ -0,0 +1,31 @@
+#version 460
+#include "shaders/lights.glsl"
+#include "shaders/environment_map.glsl"
+
+in vec3 v_position;
+in vec3 v_normal;
+in vec2 v_tex_coord;
+
+out vec4 color;
+
+uniform material {
+    vec4 u_color;
+    // 0 is matte, 1 a perfect mirror
+    float u_reflectivity;
+};
+
+void main() {
+    vec3 normal = normalize(v_normal);
+    vec3 light = u_ambient.rgb;
+    for (uint i = 0; i < u_light_count; i++) {
+        Light l = u_lights[i];
+        vec3 light_dir = int(l.position.w) == DIRECTIONAL_LIGHT
+            ? normalize(-l.direction.xyz)
+            : normalize(l.position.xyz - v_position);
+        light += max(dot(normal, light_dir), 0.0) * l.color.rgb;
+    }
+
+    vec3 diffuse = u_color.rgb * light;
+    vec3 reflection = environment_reflection(v_position, normal);
+    color = vec4(mix(diffuse, reflection, u_reflectivity), u_color.a);
+}
@@ -0,0 +1,5 @@
+#version 460
+
+// Only the depth is written
+void main() {
+}
@@ -0,0 +1,12 @@
+#version 460
+
+uniform shadow_view {
+    mat4 u_light_pv_matrix;
+};
+
+in vec3 position;
+in mat4 i_model;
+
+void main() {
+    gl_Position = u_light_pv_matrix * i_model * vec4(position, 1.0);
+}
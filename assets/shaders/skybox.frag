@@ -0,0 +1,16 @@
+#version 460
+
+uniform mat4 u_inverse_pv_matrix;
+uniform vec3 u_camera_position;
+uniform samplerCube u_skybox;
+
+in vec2 v_ndc;
+
+out vec4 color;
+
+void main() {
+    // the world position of the pixel on the far plane
+    vec4 far_position = u_inverse_pv_matrix * vec4(v_ndc, 1.0, 1.0);
+    vec3 direction = far_position.xyz / far_position.w - u_camera_position;
+    color = texture(u_skybox, direction);
+}
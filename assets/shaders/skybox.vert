@@ -0,0 +1,9 @@
+#version 460
+
+out vec2 v_ndc;
+
+// A triangle covering the whole view at the far plane, no vertex buffer needed
+void main() {
+    v_ndc = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2) * 2.0 - 1.0;
+    gl_Position = vec4(v_ndc, 1.0, 1.0);
+}
@@ -0,0 +1,18 @@
+// Samplers of the images bound by the TextureBuffer, included right after the #version
+// BASE_COLOR_TEXTURE and NORMAL_MAP_TEXTURE expect `flat in uint v_tex_id` and
+// `flat in int v_normal_tex_id` to be declared
+#ifdef BINDLESS_TEXTURES
+#extension GL_ARB_bindless_texture : require
+
+uniform samplers {
+    sampler2D tex[TEXTURE_BUFFER_SIZE];
+};
+#define BASE_COLOR_TEXTURE tex[v_tex_id]
+#define NORMAL_MAP_TEXTURE tex[v_normal_tex_id]
+#else
+// the batches are split so that all the instances use the same slots,
+// one `uniform sampler2D tex_<slot>;` per slot
+TEXTURE_SAMPLERS
+#define BASE_COLOR_TEXTURE tex_0
+#define NORMAL_MAP_TEXTURE tex_1
+#endif
@@ -0,0 +1,23 @@
+#version 460
+
+in vec2 v_uv;
+
+out vec4 color;
+
+uniform sampler2D u_color;
+uniform float u_exposure;
+
+// Narkowicz's fit of the ACES filmic curve
+vec3 aces(vec3 x) {
+    const float a = 2.51;
+    const float b = 0.03;
+    const float c = 2.43;
+    const float d = 0.59;
+    const float e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), 0.0, 1.0);
+}
+
+void main() {
+    vec4 hdr = texture(u_color, v_uv);
+    color = vec4(aces(hdr.rgb * u_exposure), hdr.a);
+}
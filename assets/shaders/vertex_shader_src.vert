@@ -4,24 +4,67 @@ uniform camera {
     mat4 u_pv_matrix;
 };
 
-in vec3 i_position;
-in vec3 i_normal;
+// per vertex
+in vec3 position;
+#ifdef VERTEX_NORMAL
+in vec3 normal;
+#endif
+#ifdef VERTEX_TEX_COORD
+in vec2 tex_coord;
+#endif
+#ifdef VERTEX_COLOR
+in vec4 color;
+#endif
+#ifdef VERTEX_TANGENT
+in vec4 tangent;
+#endif
+
+// per instance
+in mat4 i_model;
+in mat3 i_normal_matrix;
 in vec4 i_color;
-in vec2 i_tex_coord;
+in vec3 i_emissive;
+in vec4 i_material;
 in uint i_tex_id;
+in int i_normal_tex_id;
 
 out vec3 v_position;
 out vec3 v_normal;
 out vec4 v_color;
+out vec3 v_emissive;
+flat out vec4 v_material;
 out vec2 v_tex_coord;
 flat out uint v_tex_id;
+flat out int v_normal_tex_id;
+#ifdef VERTEX_TANGENT
+out vec4 v_tangent;
+#endif
 
 void main() {
-    gl_Position = u_pv_matrix * vec4(i_position, 1.0);
-    // v_normal = transpose(inverse(mat3(u_transform))) * normal;
-    v_normal = i_normal;
-    v_position = i_position;
+    vec4 world_position = i_model * vec4(position, 1.0);
+    gl_Position = u_pv_matrix * world_position;
+#ifdef VERTEX_NORMAL
+    v_normal = i_normal_matrix * normal;
+#else
+    v_normal = i_normal_matrix * vec3(0.0, 0.0, 1.0);
+#endif
+    v_position = world_position.xyz;
+#ifdef VERTEX_COLOR
+    v_color = i_color * color;
+#else
     v_color = i_color;
-    v_tex_coord = i_tex_coord;
+#endif
+#ifdef VERTEX_TANGENT
+    // w holds the handedness of the bitangent
+    v_tangent = vec4(mat3(i_model) * tangent.xyz, tangent.w);
+#endif
+    v_emissive = i_emissive;
+    v_material = i_material;
+#ifdef VERTEX_TEX_COORD
+    v_tex_coord = tex_coord;
+#else
+    v_tex_coord = vec2(0.0);
+#endif
     v_tex_id = i_tex_id;
+    v_normal_tex_id = i_normal_tex_id;
 }
\ No newline at end of file
@@ -50,7 +50,7 @@ impl App {
         let _arara_app_run_guard = arara_app_run_span.enter();
 
         self.debug_stage_order();
-        let mut app = std::mem::replace(self, App::default());
+        let mut app = std::mem::take(self);
         let runnable = std::mem::replace(&mut app.runnable, Box::new(run_once));
         (runnable)(app);
     }
@@ -113,6 +113,19 @@ impl App {
         self
     }
 
+    /// Adds a group of plugins, allowing `func` to enable, disable or reorder them before they are built.
+    pub fn add_plugins_with<T, F>(&mut self, mut group: T, func: F) -> &mut Self
+    where
+        T: PluginGroup,
+        F: FnOnce(&mut PluginGroupBuilder) -> &mut PluginGroupBuilder,
+    {
+        let mut plugin_group_builder = PluginGroupBuilder::default();
+        group.build(&mut plugin_group_builder);
+        func(&mut plugin_group_builder);
+        plugin_group_builder.finish(self);
+        self
+    }
+
     pub fn add_system<Params>(&mut self, system: impl IntoSystemDescriptor<Params>) -> &mut Self {
         self.add_system_to_stage(CoreStage::Update, system)
     }
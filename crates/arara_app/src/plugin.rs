@@ -88,7 +88,7 @@ impl PluginGroupBuilder {
     }
 
     pub fn enable<T: Plugin>(&mut self) -> &mut Self {
-        let mut plugin_entry = self
+        let plugin_entry = self
             .plugins
             .get_mut(&TypeId::of::<T>())
             .expect("Cannot enable a plugin that does not exist.");
@@ -97,7 +97,7 @@ impl PluginGroupBuilder {
     }
 
     pub fn disable<T: Plugin>(&mut self) -> &mut Self {
-        let mut plugin_entry = self
+        let plugin_entry = self
             .plugins
             .get_mut(&TypeId::of::<T>())
             .expect("Cannot disable a plugin that does not exist.");
@@ -12,7 +12,7 @@ pub struct FilesystemWatcher {
 impl Default for FilesystemWatcher {
     fn default() -> Self {
         let (sender, receiver) = crossbeam_channel::unbounded();
-        let watcher: RecommendedWatcher = RecommendedWatcher::new(move |res| {
+        let watcher: RecommendedWatcher = RecommendedWatcher::new_immediate(move |res| {
             sender.send(res).expect("Watch event send failure.");
         })
         .expect("Failed to create filesystem watcher.");
@@ -4,6 +4,8 @@ use crate::{AssetIo, AssetIoError};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use arara_ecs::system::Res;
+#[cfg(not(feature = "filesystem_watcher"))]
+use arara_logger::warn;
 use arara_utils::BoxedFuture;
 #[cfg(feature = "filesystem_watcher")]
 use arara_utils::HashSet;
@@ -104,6 +106,8 @@ impl AssetIo for FileAssetIo {
         {
             *self.filesystem_watcher.write() = Some(FilesystemWatcher::default());
         }
+        #[cfg(not(feature = "filesystem_watcher"))]
+        warn!("Watching for changes is not supported when the `filesystem_watcher` feature is disabled");
 
         Ok(())
     }
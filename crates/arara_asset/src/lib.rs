@@ -29,6 +29,7 @@ pub use path::*;
 
 use arara_app::{prelude::Plugin, App};
 use arara_ecs::schedule::{StageLabel, SystemStage};
+use arara_logger::warn;
 use bevy_tasks::IoTaskPool;
 
 /// The names of asset stages in an App Schedule
@@ -45,12 +46,16 @@ pub struct AssetPlugin;
 
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// Reloads the assets when their file is modified, which requires the
+    /// `filesystem_watcher` feature.
+    pub watch_for_changes: bool,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            watch_for_changes: false,
         }
     }
 }
@@ -64,6 +69,14 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
         .world
         .get_resource_or_insert_with(AssetServerSettings::default);
     let source = FileAssetIo::new(&settings.asset_folder);
+    if settings.watch_for_changes {
+        if let Err(err) = source.watch_for_changes() {
+            warn!(
+                "Failed to watch the assets, hot reloading is disabled: {}",
+                err
+            );
+        }
+    }
     Box::new(source)
 }
 
@@ -1,7 +1,55 @@
 use arara_ecs::component::Component;
-use glam::Mat4;
+use glam::{Mat4, UVec2, Vec2};
 
-#[derive(Component, Debug, Default)]
+#[derive(Component, Debug)]
 pub struct Camera {
     pub projection: Mat4,
+    /// Cameras are drawn in ascending priority, higher priorities are drawn on top of the others.
+    pub priority: isize,
+    /// The area of the render target drawn by the camera, the whole target if `None`.
+    pub viewport: Option<Viewport>,
+    /// Inactive cameras are not rendered.
+    pub is_active: bool,
+    /// Size in pixels of the render target, the window size if `None`.
+    /// Set by the renderer for cameras rendering into an image.
+    pub target_size: Option<UVec2>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            projection: Mat4::default(),
+            priority: 0,
+            viewport: None,
+            is_active: true,
+            target_size: None,
+        }
+    }
+}
+
+/// A rectangle of the render target, relative to its size so it follows resizes.
+/// `(0, 0)` is the bottom left corner of the target and `(1, 1)` the top right one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub origin: Vec2,
+    pub size: Vec2,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            origin: Vec2::ZERO,
+            size: Vec2::ONE,
+        }
+    }
+}
+
+impl Viewport {
+    /// The bottom left corner and size in pixels of the viewport on a target of `target_size` pixels.
+    pub fn physical_rect(&self, target_size: UVec2) -> (UVec2, UVec2) {
+        let target_size = target_size.as_vec2();
+        let origin = (self.origin * target_size).round().as_uvec2();
+        let size = (self.size * target_size).round().as_uvec2().max(UVec2::ONE);
+        (origin, size)
+    }
 }
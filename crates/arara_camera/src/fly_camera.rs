@@ -1,5 +1,5 @@
 //! A simple plugin and components for 2d/3d flying cameras in Arara.
-//!	Copy from https://github.com/mcpar-land/bevy_fly_camera
+//! Copy from https://github.com/mcpar-land/bevy_fly_camera
 //!
 //! # 3D
 //!
@@ -16,13 +16,13 @@
 //! use Arara_fly_camera::{FlyCamera, FlyCameraPlugin};
 //!
 //! fn setup(commands: &mut Commands) {
-//!	  commands
+//!   commands
 //!     .spawn(Camera3dBundle::default())
 //!     .with(FlyCamera::default());
 //! }
 //!
 //! fn main() {
-//!	  App::build()
+//!   App::build()
 //!     .add_plugins(DefaultPlugins)
 //!     .add_startup_system(setup.system())
 //!     .add_plugin(FlyCameraPlugin)
@@ -44,7 +44,7 @@
 //! use Arara_fly_camera::{FlyCamera2d, FlyCameraPlugin};
 //! ```
 //! ```no_compile
-//!	commands
+//! commands
 //!   .spawn(Camera2dBundle::default())
 //!   .with(FlyCamera2d::default());
 //! ```
@@ -82,7 +82,7 @@ pub fn movement_axis(input: &Res<Input<KeyCode>>, plus: KeyCode, minus: KeyCode)
 /// # Example
 /// ```no_compile
 /// fn setup(mut commands: Commands) {
-///	  commands
+///   commands
 ///     .spawn(Camera3dBundle::default())
 ///     .with(FlyCamera::default());
 /// }
@@ -146,8 +146,8 @@ fn forward_vector(rotation: &Quat) -> Vec3 {
 
 fn forward_walk_vector(rotation: &Quat) -> Vec3 {
     let f = forward_vector(rotation);
-    let f_flattened = Vec3::new(f.x, 0.0, f.z).normalize();
-    f_flattened
+
+    Vec3::new(f.x, 0.0, f.z).normalize()
 }
 
 fn strafe_vector(rotation: &Quat) -> Vec3 {
@@ -258,7 +258,7 @@ pub(crate) fn mouse_motion_system(
 /// # Example
 /// ```no_compile
 /// fn setup(mut commands: Commands) {
-///	  commands
+///   commands
 ///     .spawn(Camera2dBundle::default())
 ///     .with(FlyCamera2d::default());
 /// }
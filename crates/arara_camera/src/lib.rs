@@ -15,7 +15,7 @@ pub mod prelude {
         bundle::{
             FlyCamera2dBundle, FlyCameraBundle, OrthographicCameraBundle, PerspectiveCameraBundle,
         },
-        camera::Camera,
+        camera::{Camera, Viewport},
         fly_camera::{FlyCamera, FlyCamera2d},
         mouse::WorldMouse2d,
         projection::{OrthographicProjection, PerspectiveProjection},
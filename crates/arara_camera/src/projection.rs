@@ -1,10 +1,11 @@
 use std::f32::consts::FRAC_PI_3;
 
+use arara_ecs::change_detection::DetectChanges;
 use arara_ecs::component::Component;
 use arara_ecs::event::EventReader;
-use arara_ecs::system::Query;
-use arara_window::WindowResized;
-use glam::Mat4;
+use arara_ecs::system::{NonSend, Query};
+use arara_window::{Window, WindowResized};
+use glam::{Mat4, UVec2};
 
 use crate::Camera;
 
@@ -169,14 +170,32 @@ impl OrthographicProjection {
     }
 }
 
+/// Fits the projections to the size of the render target, or of the [`Viewport`](crate::Viewport)
+/// of the camera, when the window is resized or the camera changes.
 pub fn process_resize<T: CameraProjection + Component>(
+    window: NonSend<Window>,
     mut event_reader: EventReader<WindowResized>,
     mut projections: Query<(&mut Camera, &mut T)>,
 ) {
-    for ev in event_reader.iter().last() {
-        for (mut camera, mut projection) in projections.iter_mut() {
-            projection.resize(ev.width, ev.height);
-            camera.projection = projection.calc_matrix();
+    let resized = event_reader.iter().last();
+    let window_size = match resized {
+        Some(event) => UVec2::new(event.width, event.height),
+        None => UVec2::new(window.width(), window.height()),
+    };
+    for (mut camera, mut projection) in projections.iter_mut() {
+        if resized.is_none() && !camera.is_changed() {
+            continue;
+        }
+        let target_size = camera.target_size.unwrap_or(window_size);
+        let size = match camera.viewport {
+            Some(viewport) => viewport.physical_rect(target_size).1,
+            None => target_size,
+        };
+        projection.resize(size.x, size.y);
+        let matrix = projection.calc_matrix();
+        // only write when needed, the camera would be changed again otherwise
+        if camera.projection != matrix {
+            camera.projection = matrix;
         }
     }
 }
@@ -1,16 +1,13 @@
-use arara_asset::Assets;
 use arara_ecs::prelude::*;
 
 use arara_app::{App, Plugin};
-use arara_render::{Color, Cuboid, Mesh};
-use arara_transform::Transform;
-
-use crate::SimpleMeshBundle;
+use arara_render::{Color, DebugLines};
+use glam::Vec3;
 
+/// The axes drawn by the [`CoordinateSystemPlugin`], `count` units of `lenght` long.
 pub struct CoordinateSystem {
     pub count: u32,
     pub lenght: f32,
-    pub radius: f32,
 }
 
 impl Default for CoordinateSystem {
@@ -18,11 +15,12 @@ impl Default for CoordinateSystem {
         Self {
             count: 5,
             lenght: 1.0,
-            radius: 0.05,
         }
     }
 }
 
+/// Draws the x, y and z axes in red, green and blue with the [`DebugLines`], with a tick at
+/// every unit.
 #[derive(Default)]
 pub struct CoordinateSystemPlugin;
 
@@ -30,40 +28,30 @@ impl Plugin for CoordinateSystemPlugin {
     fn build(&self, app_builder: &mut App) {
         app_builder
             .init_resource::<CoordinateSystem>()
-            .add_startup_system(draw_cordinate_system.system());
+            .add_system(draw_cordinate_system);
     }
 }
 
 fn draw_cordinate_system(
-    mut commands: Commands,
     coordinate_system: Res<CoordinateSystem>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    mut debug_lines: ResMut<DebugLines>,
 ) {
-    let count = coordinate_system.count;
     let lenght = coordinate_system.lenght;
-    let radius = coordinate_system.radius;
-    let cuboid_x = meshes.add(Mesh::from(Cuboid::new(0.9 * lenght, radius, radius)));
-    let cuboid_y = meshes.add(Mesh::from(Cuboid::new(radius, 0.9 * lenght, radius)));
-    let cuboid_z = meshes.add(Mesh::from(Cuboid::new(radius, radius, 0.9 * lenght)));
-    for i in 0..count {
-        let pos = (i as f32 + 0.5) * lenght;
-        commands.spawn_bundle(SimpleMeshBundle {
-            mesh: cuboid_x.clone(),
-            transform: Transform::from_xyz(pos, 0.0, 0.0),
-            color: Color::RED,
-            ..Default::default()
-        });
-        commands.spawn_bundle(SimpleMeshBundle {
-            mesh: cuboid_y.clone(),
-            transform: Transform::from_xyz(0.0, pos, 0.0),
-            color: Color::GREEN,
-            ..Default::default()
-        });
-        commands.spawn_bundle(SimpleMeshBundle {
-            mesh: cuboid_z.clone(),
-            transform: Transform::from_xyz(0.0, 0.0, pos),
-            color: Color::BLUE,
-            ..Default::default()
-        });
+    let end = coordinate_system.count as f32 * lenght;
+    let tick = 0.1 * lenght;
+    for (axis, tick_direction, color) in [
+        (Vec3::X, Vec3::Y, Color::RED),
+        (Vec3::Y, Vec3::X, Color::GREEN),
+        (Vec3::Z, Vec3::Y, Color::BLUE),
+    ] {
+        debug_lines.line(Vec3::ZERO, axis * end, color);
+        for i in 1..=coordinate_system.count {
+            let position = axis * i as f32 * lenght;
+            debug_lines.line(
+                position - tick_direction * tick,
+                position + tick_direction * tick,
+                color,
+            );
+        }
     }
 }
@@ -1,10 +1,12 @@
 use arara_ecs::prelude::*;
 use arara_render::{Color, Image, Mesh, Visibility, DEFAULT_IMAGE_HANDLE};
-use glam::{vec3, Mat4, Vec3};
+use glam::Mat4;
 
 use arara_asset::Handle;
 use arara_transform::{GlobalTransform, Transform};
 
+use crate::{ExtractedMaterial, Material, StandardMaterial};
+
 #[derive(Default, Component)]
 pub struct CorePipelineEntity;
 
@@ -33,28 +35,47 @@ impl Default for SimpleMeshBundle {
     }
 }
 
-#[derive(Component)]
-pub struct ExtractedCorePipelineEntity {
+/// A mesh rendered with a [`StandardMaterial`].
+#[derive(Bundle, Default)]
+pub struct MaterialMeshBundle {
     pub mesh: Handle<Mesh>,
-    pub image: Handle<Image>,
-    pub transform: Mat4,
-    pub color: Color,
+    pub material: Handle<StandardMaterial>,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub core_pipeline_entity: CorePipelineEntity,
 }
 
-pub struct BPLight {
-    pub position: Vec3,
+/// A mesh rendered with a custom [`Material`], see [`MaterialPlugin`](crate::MaterialPlugin).
+#[derive(Bundle)]
+pub struct CustomMaterialMeshBundle<M: Material> {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<M>,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
 }
 
-impl Default for BPLight {
+impl<M: Material> Default for CustomMaterialMeshBundle<M> {
     fn default() -> Self {
-        Self::new(0.0, 10.0, 0.0)
-    }
-}
-
-impl BPLight {
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
         Self {
-            position: vec3(x, y, z),
+            mesh: Default::default(),
+            material: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
         }
     }
 }
+
+#[derive(Component)]
+pub struct ExtractedCorePipelineEntity {
+    /// Listed in the [`VisibleEntities`](arara_render::VisibleEntities) of the views seeing it
+    pub entity: Entity,
+    pub mesh: Handle<Mesh>,
+    pub transform: Mat4,
+    pub material: ExtractedMaterial,
+    pub shadow_caster: bool,
+    pub shadow_receiver: bool,
+    pub wireframe: bool,
+}
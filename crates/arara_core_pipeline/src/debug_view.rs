@@ -0,0 +1,41 @@
+use arara_ecs::prelude::*;
+
+/// Draws the edges of every mesh of the core pipeline when `global` is set, otherwise only
+/// those of the entities with a [`Wireframe`].
+///
+/// Wireframes are not supported by OpenGL ES contexts, the meshes are then filled as usual.
+#[derive(Debug, Clone, Default)]
+pub struct WireframeConfig {
+    pub global: bool,
+}
+
+/// Draws the edges of the mesh of the entity instead of filling its faces.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Wireframe;
+
+/// Replaces the shading of the core pipeline with a geometry attribute, can be changed at
+/// any time.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum DebugView {
+    /// Regular shading.
+    #[default]
+    None,
+    /// World space normals, mapped from `[-1, 1]` to `[0, 1]`.
+    Normals,
+    /// Texture coordinates as red and green.
+    Uvs,
+    /// Distance to the camera, white up close and fading to black with the distance.
+    Depth,
+}
+
+impl DebugView {
+    /// The shader def enabling the view in the fragment shader.
+    pub fn shader_def(&self) -> Option<&'static str> {
+        match self {
+            DebugView::None => None,
+            DebugView::Normals => Some("DEBUG_NORMALS"),
+            DebugView::Uvs => Some("DEBUG_UVS"),
+            DebugView::Depth => Some("DEBUG_DEPTH"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use arara_ecs::{
+    entity::Entity,
     system::{
         lifetimeless::{Read, SQuery, SRes},
         NonSend, SystemState,
@@ -6,50 +7,121 @@ use arara_ecs::{
     world::World,
 };
 use arara_render::{
-    CachedPipelinePhaseItem, Draw, EntityPhaseItem, ExtractedView, PhaseItem, RenderPipelineCache,
-    TextureBuffer, TrackedFrame,
+    implement_uniform_block, CachedPipelinePhaseItem, ChainedUniforms, Draw, EntityPhaseItem,
+    ExtractedView, GpuMeshes, PhaseItem, RenderPipelineCache, TextureBindingsError, TextureBuffer,
+    TrackedFrame,
 };
+use arara_utils::tracing::error;
 use arara_window::Window;
-use glam::Vec4;
-use glium::implement_uniform_block;
+use glam::{Vec3, Vec4};
 
-use crate::{prepare_phase::CorePipelineBatch, BPLight};
+use crate::{
+    prepare_phase::CorePipelineBatch, AmbientLight, ExtractedLight, ExtractedLights, ShadowMaps,
+    MAX_LIGHTS,
+};
 
+#[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
-struct CameraUniformBuffer {
+pub(crate) struct CameraUniformBuffer {
     u_pv_matrix: [[f32; 4]; 4],
 }
 
 impl CameraUniformBuffer {
-    fn new(u_pv_matrix: [[f32; 4]; 4]) -> Self {
+    pub(crate) fn new(u_pv_matrix: [[f32; 4]; 4]) -> Self {
         Self { u_pv_matrix }
     }
 }
 
 implement_uniform_block!(CameraUniformBuffer, u_pv_matrix);
 
-#[derive(Copy, Clone)]
-struct TextureUniformBuffer<'a> {
-    tex: [glium::texture::TextureHandle<'a>; 5],
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct LightUniform {
+    /// `w` holds the [`ExtractedLightKind`]
+    position: [f32; 4],
+    /// Color times intensity, `w` holds the range
+    color: [f32; 4],
+    direction: [f32; 4],
+    /// Cosine of the inner and outer angles of spot lights and the shadow map layer, `-1` if none
+    cone: [f32; 4],
+    view_projection: [[f32; 4]; 4],
 }
 
-implement_uniform_block!(TextureUniformBuffer<'a>, tex);
+implement_uniform_block!(
+    LightUniform,
+    position,
+    color,
+    direction,
+    cone,
+    view_projection
+);
+
+impl From<&ExtractedLight> for LightUniform {
+    fn from(light: &ExtractedLight) -> Self {
+        let [r, g, b, _] = light.color.as_rgba_f32();
+        Self {
+            position: Vec4::from((light.position, light.kind as u32 as f32)).into(),
+            color: [
+                r * light.intensity,
+                g * light.intensity,
+                b * light.intensity,
+                light.range,
+            ],
+            direction: Vec4::from((light.direction, 0.0)).into(),
+            cone: [
+                light.inner_angle.cos(),
+                light.outer_angle.cos(),
+                light.shadow_map.map_or(-1.0, |layer| layer as f32),
+                0.0,
+            ],
+            view_projection: light.view_projection.to_cols_array_2d(),
+        }
+    }
+}
 
-#[derive(Copy, Clone)]
-struct BPLightUniformBuffer {
-    pub u_camera_pos: [f32; 4],
-    pub u_light_pos: [f32; 4],
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct LightsUniformBuffer {
+    u_camera_pos: [f32; 4],
+    u_ambient: [f32; 4],
+    u_lights: [LightUniform; MAX_LIGHTS],
+    u_light_count: u32,
 }
 
-implement_uniform_block!(BPLightUniformBuffer, u_camera_pos, u_light_pos);
+implement_uniform_block!(
+    LightsUniformBuffer,
+    u_camera_pos,
+    u_ambient,
+    u_lights,
+    u_light_count
+);
+
+impl LightsUniformBuffer {
+    pub(crate) fn new(camera_position: Vec3, extracted_lights: &ExtractedLights) -> Self {
+        let AmbientLight { color, brightness } = extracted_lights.ambient;
+        let [r, g, b, _] = color.as_rgba_f32();
+        let mut u_lights = [LightUniform::default(); MAX_LIGHTS];
+        for (uniform, light) in u_lights.iter_mut().zip(&extracted_lights.lights) {
+            *uniform = light.into();
+        }
+        Self {
+            u_camera_pos: Vec4::from((camera_position, 0.0)).into(),
+            u_ambient: [r * brightness, g * brightness, b * brightness, 0.0],
+            u_lights,
+            u_light_count: extracted_lights.lights.len().min(MAX_LIGHTS) as u32,
+        }
+    }
+}
 
 pub struct DrawSimpleMesh {
     params: SystemState<(
         NonSend<'static, Window>,
         NonSend<'static, TextureBuffer>,
         NonSend<'static, RenderPipelineCache>,
-        SRes<BPLight>,
-        SRes<ExtractedView>,
+        NonSend<'static, ShadowMaps>,
+        NonSend<'static, GpuMeshes>,
+        SRes<ExtractedLights>,
+        SQuery<Read<ExtractedView>>,
         SQuery<Read<CorePipelineBatch>>,
     )>,
 }
@@ -63,34 +135,39 @@ impl DrawSimpleMesh {
 }
 
 impl<I: PhaseItem + CachedPipelinePhaseItem + EntityPhaseItem> Draw<I> for DrawSimpleMesh {
-    fn draw<'w>(&mut self, world: &'w World, frame: &mut TrackedFrame, item: &I) {
-        let (window, texture_buffer, pipeline_cache, bp_light, view, query) =
-            self.params.get(world);
+    fn draw(&mut self, world: &World, frame: &mut TrackedFrame, view: Entity, item: &I) {
+        let (
+            window,
+            texture_buffer,
+            pipeline_cache,
+            shadow_maps,
+            gpu_meshes,
+            extracted_lights,
+            views,
+            query,
+        ) = self.params.get(world);
+
+        let view = match views.get(view) {
+            Ok(view) => view,
+            Err(_) => return,
+        };
 
-        let display = window.display();
+        let facade = window.facade();
 
         let pv_matrix: [[f32; 4]; 4] = view.pv_matrix.to_cols_array_2d();
         let camera_uniform_buffer =
-            glium::uniforms::UniformBuffer::new(display, CameraUniformBuffer::new(pv_matrix))
+            glium::uniforms::UniformBuffer::new(facade, CameraUniformBuffer::new(pv_matrix))
                 .unwrap();
 
-        let texture_uniform_buffer =
-            glium::uniforms::UniformBuffer::new(display, texture_buffer.texture_uniform_buffer())
-                .unwrap();
-
-        let bplight_uniform_buffer = glium::uniforms::UniformBuffer::new(
-            display,
-            BPLightUniformBuffer {
-                u_camera_pos: Vec4::from((view.position, 0.0)).into(),
-                u_light_pos: Vec4::from((bp_light.position, 0.0)).into(),
-            },
+        let lights_uniform_buffer = glium::uniforms::UniformBuffer::new(
+            facade,
+            LightsUniformBuffer::new(view.position, &extracted_lights),
         )
         .unwrap();
 
-        let uniforms = glium::uniform! {
-            camera: &camera_uniform_buffer,
-            bplight: &bplight_uniform_buffer,
-            samplers: &texture_uniform_buffer,
+        let shadow_maps = match shadow_maps.sampler() {
+            Some(sampler) => sampler,
+            None => return,
         };
 
         let pipeline = match pipeline_cache.get(item.cached_pipeline()) {
@@ -98,24 +175,39 @@ impl<I: PhaseItem + CachedPipelinePhaseItem + EntityPhaseItem> Draw<I> for DrawS
             None => return,
         };
 
-        let CorePipelineBatch {
-            vertices,
-            indices,
-            transparent: _,
-        } = query.get(item.entity()).unwrap();
-
-        let vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
-        let index_buffer = glium::IndexBuffer::new(
-            display,
-            glium::index::PrimitiveType::TrianglesList,
-            &indices,
-        )
-        .unwrap();
+        let batch = query.get(item.entity()).unwrap();
+        let texture_bindings = match texture_buffer.texture_bindings(facade, &batch.texture_slots) {
+            Ok(bindings) => bindings,
+            Err(TextureBindingsError::NotUploaded) => return,
+            Err(err) => {
+                error!("Failed to bind the textures: {}", err);
+                return;
+            }
+        };
+
+        let uniforms = ChainedUniforms(
+            glium::uniform! {
+                camera: &camera_uniform_buffer,
+                lights: &lights_uniform_buffer,
+                shadow_maps: shadow_maps,
+            },
+            texture_bindings,
+        );
+
+        let gpu_mesh = match gpu_meshes.get(&batch.mesh) {
+            Some(gpu_mesh) => gpu_mesh,
+            None => return,
+        };
+
+        let instance_buffer = glium::VertexBuffer::new(facade, batch.visible_instances()).unwrap();
 
         frame
             .draw(
-                &vertex_buffer,
-                &index_buffer,
+                (
+                    &gpu_mesh.vertex_buffer,
+                    instance_buffer.per_instance().unwrap(),
+                ),
+                &gpu_mesh.index_buffer,
                 &pipeline.program,
                 &uniforms,
                 &pipeline.parameters,
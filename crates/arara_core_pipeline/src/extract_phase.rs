@@ -1,12 +1,16 @@
 use arara_asset::{Assets, Handle};
 use arara_ecs::{
+    entity::Entity,
     query::With,
     system::{Query, Res, ResMut},
 };
 use arara_render::{Color, Image, Mesh, Visibility};
 use arara_transform::GlobalTransform;
 
-use crate::core_pipeline_entities::{CorePipelineEntity, ExtractedCorePipelineEntity};
+use crate::{
+    core_pipeline_entities::{CorePipelineEntity, ExtractedCorePipelineEntity},
+    ExtractedMaterial, NotShadowCaster, NotShadowReceiver, StandardMaterial, Wireframe,
+};
 
 #[derive(Default)]
 pub struct ExtractedCorePipelineEntitys {
@@ -17,30 +21,70 @@ pub(crate) fn extract_core_pipeline_entities(
     mut extracts: ResMut<ExtractedCorePipelineEntitys>,
     meshes: Res<Assets<Mesh>>,
     images: Res<Assets<Image>>,
+    materials: Res<Assets<StandardMaterial>>,
     query: Query<
         (
+            Entity,
             &Handle<Mesh>,
-            &Handle<Image>,
+            Option<&Handle<StandardMaterial>>,
+            Option<&Handle<Image>>,
+            Option<&Color>,
             &GlobalTransform,
-            &Color,
             &Visibility,
+            Option<&NotShadowCaster>,
+            Option<&NotShadowReceiver>,
+            Option<&Wireframe>,
         ),
         With<CorePipelineEntity>,
     >,
 ) {
     extracts.items.clear();
-    for (mesh, image, global_transform, color, visibility) in query.iter() {
-        if !visibility.active || !visibility.visible {
+    for (
+        entity,
+        mesh,
+        material,
+        image,
+        color,
+        global_transform,
+        visibility,
+        not_shadow_caster,
+        not_shadow_receiver,
+        wireframe,
+    ) in query.iter()
+    {
+        // culled entities are still extracted to cast shadows
+        if !visibility.active || (!visibility.visible && not_shadow_caster.is_some()) {
+            continue;
+        }
+        if meshes.get(mesh).is_none() {
             continue;
         }
-        if meshes.get(mesh).is_none() || images.get(image).is_none() {
+        let material = match (material, image) {
+            (Some(material), _) => match materials.get(material) {
+                Some(material) => ExtractedMaterial::from_material(material),
+                None => continue,
+            },
+            (None, Some(image_handle)) => match images.get(image_handle) {
+                Some(image) => ExtractedMaterial::from_color_and_image(
+                    color.copied().unwrap_or(Color::WHITE),
+                    image_handle,
+                    image.translucent,
+                ),
+                None => continue,
+            },
+            (None, None) => continue,
+        };
+        if material.textures().any(|image| images.get(image).is_none()) {
             continue;
         }
         extracts.items.push(ExtractedCorePipelineEntity {
+            entity,
             mesh: mesh.clone_weak(),
-            image: image.clone_weak(),
             transform: global_transform.compute_matrix(),
-            color: *color,
+            material,
+            shadow_caster: not_shadow_caster.is_none(),
+            shadow_receiver: not_shadow_receiver.is_none(),
+            wireframe: wireframe.is_some(),
         });
     }
 }
@@ -1,55 +1,116 @@
 mod coordinate_system;
 mod core_pipeline_entities;
+mod debug_view;
 mod draw_functions;
 mod extract_phase;
+mod light;
+mod material;
+mod material_plugin;
 mod phase_items;
 mod pipelines;
 mod prepare_phase;
 mod queue_phase;
+mod shadow;
+mod skybox;
 
 use arara_app::{App, Plugin, StartupStage};
-use arara_ecs::system::NonSend;
+use arara_asset::AddAsset;
+use arara_ecs::{
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{NonSend, Res},
+};
 use arara_render::{
-    clear_phase_system, DrawFunctions, RenderPhase, RenderPhases, RenderStage, SpecializedPipelines,
+    clear_phase_system, despawn_render_entities, extract_camera_phases, sort_phase_system,
+    DrawFunctions, RenderCapabilities, RenderPhases, RenderStage, SpecializedPipelines, ViewSystem,
 };
 use arara_utils::tracing::info;
 use arara_window::Window;
 pub use coordinate_system::{CoordinateSystem, CoordinateSystemPlugin};
-pub use core_pipeline_entities::{BPLight, SimpleMeshBundle};
+pub use core_pipeline_entities::{CustomMaterialMeshBundle, MaterialMeshBundle, SimpleMeshBundle};
+pub use debug_view::*;
 use draw_functions::DrawSimpleMesh;
 use extract_phase::{extract_core_pipeline_entities, ExtractedCorePipelineEntitys};
 use glium::{Api, Profile, Version};
-pub use phase_items::{Opaque3D, Transparent3D};
-pub use pipelines::{CorePipeline, DefaultShader};
-use prepare_phase::prepare_core_pipeline_phase;
+use light::extract_lights;
+pub use light::*;
+pub use material::*;
+pub use material_plugin::*;
+pub use phase_items::{Opaque3D, Skybox3D, Transparent3D};
+pub use pipelines::{CorePipeline, CorePipelineKey, DefaultShader};
+use prepare_phase::{prepare_core_pipeline_phase, CorePipelineBatch};
 use queue_phase::queue_core_pipeline_phase;
+pub use shadow::*;
+use shadow::{prepare_shadow_maps, queue_shadows};
+use skybox::queue_skyboxes;
+pub use skybox::*;
 
 #[derive(Default)]
 pub struct CorePipelinePlugin;
 
 impl Plugin for CorePipelinePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<BPLight>()
+        app.add_asset::<StandardMaterial>()
+            .init_resource::<AmbientLight>()
+            .init_resource::<WireframeConfig>()
+            .init_resource::<DebugView>()
+            .init_resource::<ExtractedLights>()
+            .init_resource::<ShadowMapSettings>()
+            .init_non_send_resource::<ShadowMaps>()
+            .init_resource::<ShadowPipeline>()
+            .init_resource::<SpecializedPipelines<ShadowPipeline>>()
+            .init_resource::<DrawFunctions<Shadow>>()
             .init_resource::<CorePipeline>()
             .init_resource::<SpecializedPipelines<CorePipeline>>()
+            .init_resource::<SkyboxPipeline>()
+            .init_resource::<SpecializedPipelines<SkyboxPipeline>>()
             .init_resource::<DrawFunctions<Opaque3D>>()
+            .init_resource::<DrawFunctions<Skybox3D>>()
             .init_resource::<DrawFunctions<Transparent3D>>()
-            .init_resource::<RenderPhase<Opaque3D>>()
-            .init_resource::<RenderPhase<Transparent3D>>()
             .init_resource::<ExtractedCorePipelineEntitys>()
             .add_startup_system_to_stage(StartupStage::PostStartup, debug_glium_backend_info)
-            .add_system_to_stage(RenderStage::Extract, extract_core_pipeline_entities)
+            .add_system_to_stage(RenderStage::Extract, extract_camera_phases::<Opaque3D>)
+            .add_system_to_stage(RenderStage::Extract, extract_camera_phases::<Skybox3D>)
+            .add_system_to_stage(RenderStage::Extract, extract_camera_phases::<Transparent3D>)
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_core_pipeline_entities.after(ViewSystem::CheckVisibility),
+            )
+            .add_system_to_stage(RenderStage::Extract, extract_lights)
             .add_system_to_stage(RenderStage::Prepare, prepare_core_pipeline_phase)
+            .add_system_to_stage(RenderStage::Prepare, prepare_shadow_maps)
             .add_system_to_stage(RenderStage::Queue, queue_core_pipeline_phase)
+            .add_system_to_stage(RenderStage::Queue, queue_shadows)
+            .add_system_to_stage(RenderStage::Queue, queue_skyboxes)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Shadow>)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Opaque3D>)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent3D>)
             .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Opaque3D>)
-            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Transparent3D>);
+            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Skybox3D>)
+            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Transparent3D>)
+            .add_system_to_stage(
+                RenderStage::Cleanup,
+                despawn_render_entities::<CorePipelineBatch>,
+            )
+            .add_system_to_stage(RenderStage::Cleanup, despawn_render_entities::<ShadowView>);
 
+        let draw_shadow = DrawShadow::new(&mut app.world);
+        app.world
+            .get_resource::<DrawFunctions<Shadow>>()
+            .unwrap()
+            .write()
+            .add(draw_shadow);
         let draw_simple_mesh = DrawSimpleMesh::new(&mut app.world);
         app.world
             .get_resource::<DrawFunctions<Opaque3D>>()
             .unwrap()
             .write()
             .add(draw_simple_mesh);
+        let draw_skybox = DrawSkybox::new(&mut app.world);
+        app.world
+            .get_resource::<DrawFunctions<Skybox3D>>()
+            .unwrap()
+            .write()
+            .add(draw_skybox);
         let draw_simple_mesh = DrawSimpleMesh::new(&mut app.world);
         app.world
             .get_resource::<DrawFunctions<Transparent3D>>()
@@ -60,13 +121,15 @@ impl Plugin for CorePipelinePlugin {
         app.world
             .get_resource_mut::<RenderPhases>()
             .unwrap()
+            .add::<Shadow>()
             .add::<Opaque3D>()
+            .add::<Skybox3D>()
             .add::<Transparent3D>();
     }
 }
 
-fn debug_glium_backend_info(window: NonSend<Window>) {
-    let glium_display = window.display();
+fn debug_glium_backend_info(window: NonSend<Window>, capabilities: Res<RenderCapabilities>) {
+    let glium_display = window.facade().get_context();
 
     let version = *glium_display.get_opengl_version();
     let api = match version {
@@ -123,4 +186,11 @@ fn debug_glium_backend_info(window: NonSend<Window>) {
         api,
         glium_display.get_opengl_renderer_string()
     );
+
+    info!("GLSL version: {}", capabilities.glsl_version);
+    if capabilities.bindless_textures {
+        info!("bindless textures: supported");
+    } else {
+        info!("bindless textures: not supported, binding textures to sampler uniforms");
+    }
 }
@@ -0,0 +1,251 @@
+use arara_ecs::prelude::*;
+use arara_render::Color;
+use arara_transform::{GlobalTransform, Transform};
+use arara_utils::tracing::warn;
+use glam::{Mat4, Vec3};
+
+use crate::MAX_SHADOW_MAPS;
+
+/// Maximum amount of lights sent to the shaders, extra lights are ignored.
+/// Must match `MAX_LIGHTS` in `shaders/lights.glsl`.
+pub const MAX_LIGHTS: usize = 16;
+
+/// A light that shines in every direction from the entity [`GlobalTransform`],
+/// fading smoothly to zero at `range`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PointLight {
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+}
+
+impl Default for PointLight {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            range: 20.0,
+        }
+    }
+}
+
+/// A light infinitely far away, like the sun.
+/// It shines towards the forward direction (`-Z`) of the entity [`GlobalTransform`].
+///
+/// Shadows are rendered inside a box centered on the light position, `shadow_extent` units
+/// to each side and `shadow_depth` units forward.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DirectionalLight {
+    pub color: Color,
+    pub intensity: f32,
+    pub shadows_enabled: bool,
+    pub shadow_extent: f32,
+    pub shadow_depth: f32,
+}
+
+impl Default for DirectionalLight {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            shadows_enabled: false,
+            shadow_extent: 20.0,
+            shadow_depth: 100.0,
+        }
+    }
+}
+
+/// A [`PointLight`] restricted to a cone around the forward direction (`-Z`) of the entity
+/// [`GlobalTransform`]. Angles are in radians, the light fades between `inner_angle` and
+/// `outer_angle`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SpotLight {
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+    pub shadows_enabled: bool,
+}
+
+impl Default for SpotLight {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            range: 20.0,
+            inner_angle: 0.0,
+            outer_angle: std::f32::consts::FRAC_PI_4,
+            shadows_enabled: false,
+        }
+    }
+}
+
+/// Light applied evenly to every surface.
+#[derive(Debug, Clone, Copy)]
+pub struct AmbientLight {
+    pub color: Color,
+    pub brightness: f32,
+}
+
+impl Default for AmbientLight {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 0.1,
+        }
+    }
+}
+
+#[derive(Bundle, Default)]
+pub struct PointLightBundle {
+    pub point_light: PointLight,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+#[derive(Bundle, Default)]
+pub struct DirectionalLightBundle {
+    pub directional_light: DirectionalLight,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+#[derive(Bundle, Default)]
+pub struct SpotLightBundle {
+    pub spot_light: SpotLight,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractedLightKind {
+    Directional = 0,
+    Point = 1,
+    Spot = 2,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractedLight {
+    pub kind: ExtractedLightKind,
+    pub position: Vec3,
+    pub direction: Vec3,
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+    /// Layer of the shadow map array rendered from this light
+    pub shadow_map: Option<u32>,
+    /// Transforms from world space to the view space of the light, looking down `-Z`
+    pub view_matrix: Mat4,
+    pub view_projection: Mat4,
+}
+
+#[derive(Default)]
+pub struct ExtractedLights {
+    pub ambient: AmbientLight,
+    pub lights: Vec<ExtractedLight>,
+}
+
+pub(crate) fn extract_lights(
+    mut extracted_lights: ResMut<ExtractedLights>,
+    ambient_light: Res<AmbientLight>,
+    directional_lights: Query<(&DirectionalLight, &GlobalTransform)>,
+    point_lights: Query<(&PointLight, &GlobalTransform)>,
+    spot_lights: Query<(&SpotLight, &GlobalTransform)>,
+    mut warned: Local<bool>,
+) {
+    extracted_lights.ambient = *ambient_light;
+    extracted_lights.lights.clear();
+    let mut shadow_maps = ShadowMapAllocator::default();
+
+    for (light, global_transform) in directional_lights.iter() {
+        let view_matrix = global_transform.compute_matrix().inverse();
+        extracted_lights.lights.push(ExtractedLight {
+            kind: ExtractedLightKind::Directional,
+            position: global_transform.translation,
+            direction: global_transform.forward(),
+            color: light.color,
+            intensity: light.intensity,
+            range: 0.0,
+            inner_angle: 0.0,
+            outer_angle: 0.0,
+            shadow_map: shadow_maps.next_if(light.shadows_enabled),
+            view_matrix,
+            view_projection: Mat4::orthographic_rh_gl(
+                -light.shadow_extent,
+                light.shadow_extent,
+                -light.shadow_extent,
+                light.shadow_extent,
+                0.0,
+                light.shadow_depth,
+            ) * view_matrix,
+        });
+    }
+    for (light, global_transform) in point_lights.iter() {
+        let view_matrix = global_transform.compute_matrix().inverse();
+        extracted_lights.lights.push(ExtractedLight {
+            kind: ExtractedLightKind::Point,
+            position: global_transform.translation,
+            direction: global_transform.forward(),
+            color: light.color,
+            intensity: light.intensity,
+            range: light.range,
+            inner_angle: 0.0,
+            outer_angle: 0.0,
+            shadow_map: None,
+            view_matrix,
+            view_projection: Mat4::IDENTITY,
+        });
+    }
+    for (light, global_transform) in spot_lights.iter() {
+        let view_matrix = global_transform.compute_matrix().inverse();
+        extracted_lights.lights.push(ExtractedLight {
+            kind: ExtractedLightKind::Spot,
+            position: global_transform.translation,
+            direction: global_transform.forward(),
+            color: light.color,
+            intensity: light.intensity,
+            range: light.range,
+            inner_angle: light.inner_angle,
+            outer_angle: light.outer_angle,
+            shadow_map: shadow_maps.next_if(light.shadows_enabled),
+            view_matrix,
+            view_projection: Mat4::perspective_rh_gl(
+                2.0 * light.outer_angle,
+                1.0,
+                0.1,
+                light.range,
+            ) * view_matrix,
+        });
+    }
+
+    if extracted_lights.lights.len() > MAX_LIGHTS {
+        if !*warned {
+            *warned = true;
+            warn!(
+                "{} lights in the scene but only {} are supported, the rest are ignored",
+                extracted_lights.lights.len(),
+                MAX_LIGHTS
+            );
+        }
+        extracted_lights.lights.truncate(MAX_LIGHTS);
+    }
+}
+
+/// Hands out the layers of the shadow map array to the first [`MAX_SHADOW_MAPS`] shadow casting lights.
+#[derive(Default)]
+struct ShadowMapAllocator {
+    next: u32,
+}
+
+impl ShadowMapAllocator {
+    fn next_if(&mut self, shadows_enabled: bool) -> Option<u32> {
+        if !shadows_enabled || self.next as usize >= MAX_SHADOW_MAPS {
+            return None;
+        }
+        self.next += 1;
+        Some(self.next - 1)
+    }
+}
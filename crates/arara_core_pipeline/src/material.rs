@@ -0,0 +1,131 @@
+use arara_asset::Handle;
+use arara_render::{Color, Image, DEFAULT_IMAGE_HANDLE};
+use bevy_reflect::TypeUuid;
+
+/// How the alpha channel of a [`StandardMaterial`] is used.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AlphaMode {
+    /// The alpha channel is ignored.
+    #[default]
+    Opaque,
+    /// Fragments with alpha below the cutoff are discarded, the rest is opaque.
+    Mask(f32),
+    /// The material is alpha blended and rendered in the [`Transparent3D`](crate::Transparent3D) phase.
+    Blend,
+}
+
+/// A Blinn-Phong material used by the core pipeline.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "4a4b9a7d-1d61-4b8e-a3a4-9d3f0f3a1b6c"]
+pub struct StandardMaterial {
+    /// Multiplied with the `base_color_texture` if there is one.
+    pub base_color: Color,
+    pub base_color_texture: Option<Handle<Image>>,
+    /// Strength of the specular highlights.
+    pub specular_strength: f32,
+    /// Exponent of the specular highlights, higher values give smaller and sharper highlights.
+    pub shininess: f32,
+    /// Light emitted by the surface, added after lighting.
+    pub emissive: Color,
+    /// Tangent space normal map, tangents are derived in the fragment shader. The image must
+    /// not be sRGB encoded, see [`SamplerDescriptor::srgb`](arara_render::SamplerDescriptor).
+    pub normal_map_texture: Option<Handle<Image>>,
+    pub alpha_mode: AlphaMode,
+    /// Disables backface culling and lights both sides of the faces.
+    pub double_sided: bool,
+}
+
+impl Default for StandardMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: Color::WHITE,
+            base_color_texture: None,
+            specular_strength: 0.3,
+            shininess: 32.0,
+            emissive: Color::BLACK,
+            normal_map_texture: None,
+            alpha_mode: AlphaMode::Opaque,
+            double_sided: false,
+        }
+    }
+}
+
+impl From<Color> for StandardMaterial {
+    fn from(base_color: Color) -> Self {
+        Self {
+            base_color,
+            alpha_mode: if base_color.a() < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl From<Handle<Image>> for StandardMaterial {
+    fn from(texture: Handle<Image>) -> Self {
+        Self {
+            base_color_texture: Some(texture),
+            ..Default::default()
+        }
+    }
+}
+
+/// The render world copy of a [`StandardMaterial`], or of the [`Color`] and [`Handle<Image>`] of a
+/// [`SimpleMeshBundle`](crate::SimpleMeshBundle).
+#[derive(Debug, Clone)]
+pub struct ExtractedMaterial {
+    pub base_color: Color,
+    pub base_color_texture: Handle<Image>,
+    pub specular_strength: f32,
+    pub shininess: f32,
+    pub emissive: Color,
+    pub normal_map_texture: Option<Handle<Image>>,
+    pub alpha_mode: AlphaMode,
+    pub double_sided: bool,
+}
+
+impl ExtractedMaterial {
+    pub fn from_material(material: &StandardMaterial) -> Self {
+        Self {
+            base_color: material.base_color,
+            base_color_texture: material
+                .base_color_texture
+                .as_ref()
+                .map_or_else(|| DEFAULT_IMAGE_HANDLE.typed(), Handle::clone_weak),
+            specular_strength: material.specular_strength,
+            shininess: material.shininess,
+            emissive: material.emissive,
+            normal_map_texture: material.normal_map_texture.as_ref().map(Handle::clone_weak),
+            alpha_mode: material.alpha_mode,
+            double_sided: material.double_sided,
+        }
+    }
+
+    /// The material of a [`SimpleMeshBundle`](crate::SimpleMeshBundle), blended if either the
+    /// color or the image are translucent.
+    pub fn from_color_and_image(color: Color, image: &Handle<Image>, translucent: bool) -> Self {
+        let default = StandardMaterial::default();
+        Self {
+            base_color: color,
+            base_color_texture: image.clone_weak(),
+            specular_strength: default.specular_strength,
+            shininess: default.shininess,
+            emissive: default.emissive,
+            normal_map_texture: None,
+            alpha_mode: if translucent || color.a() < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            double_sided: false,
+        }
+    }
+
+    /// Every texture used by the material.
+    pub fn textures(&self) -> impl Iterator<Item = &Handle<Image>> {
+        std::iter::once(&self.base_color_texture).chain(self.normal_map_texture.as_ref())
+    }
+}
@@ -0,0 +1,593 @@
+use std::marker::PhantomData;
+
+use arara_app::{App, Plugin};
+use arara_asset::{AddAsset, Asset, AssetServer, Assets, Handle};
+use arara_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SQuery, SRes},
+        SystemState,
+    },
+};
+use arara_render::{
+    despawn_render_entities, CachedPipelinePhaseItem, ChainedUniforms, Draw, DrawFunctions,
+    EntityPhaseItem, ExtractedView, GpuCubemaps, GpuMesh, GpuMeshes, Image, Mesh, MeshVertexLayout,
+    PhaseItem, PrimitiveTopology, RenderPhase, RenderPipelineCache, RenderPipelineDescriptor,
+    RenderStage, Shader, SpecializedPipeline, SpecializedPipelines, TextureBindingsError,
+    TextureBuffer, TextureSlots, TrackedFrame, ViewSystem, Visibility, VisibleEntities,
+    DEFAULT_IMAGE_HANDLE,
+};
+use arara_transform::GlobalTransform;
+use arara_utils::{
+    tracing::{error, warn},
+    HashMap,
+};
+use arara_window::Window;
+use glam::{Mat3, Mat4, Vec3};
+use glium::uniforms::{UniformBlock, UniformBuffer};
+
+use crate::{
+    cubemap_sampler,
+    draw_functions::{CameraUniformBuffer, LightsUniformBuffer},
+    prepare_phase::view_depth,
+    AlphaMode, EnvironmentMapUniform, ExtractedLights, Opaque3D, Skybox, Transparent3D,
+};
+
+/// A material drawn with its own shaders, added to the app by a [`MaterialPlugin`].
+///
+/// The vertex shader receives the `camera` uniform block, the fragment shader the `material`
+/// block holding the [`Material::Uniform`] and the `lights` block, declared by
+/// `#include "shaders/lights.glsl"`.
+///
+/// When the view has a [`Skybox`] the shaders are compiled with the `ENVIRONMENT_MAP` def, and
+/// `#include "shaders/environment_map.glsl"` declares the `u_environment_map` cube sampler.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct CustomMaterialUniform {
+///     u_color: [f32; 4],
+/// }
+///
+/// implement_uniform_block!(CustomMaterialUniform, u_color);
+///
+/// #[derive(TypeUuid)]
+/// #[uuid = "a5d5c4a1-5a7f-4bd6-8d8e-0c1f9b0e5a61"]
+/// struct CustomMaterial {
+///     color: Color,
+/// }
+///
+/// impl Material for CustomMaterial {
+///     type Uniform = CustomMaterialUniform;
+///
+///     fn fragment_shader(asset_server: &AssetServer) -> Handle<Shader> {
+///         asset_server.load("shaders/custom_material.frag")
+///     }
+///
+///     fn uniform(&self) -> Self::Uniform {
+///         CustomMaterialUniform {
+///             u_color: self.color.into(),
+///         }
+///     }
+/// }
+/// ```
+pub trait Material: Asset {
+    /// Bound to the `material` uniform block.
+    type Uniform: UniformBlock + Copy + Send + Sync + 'static;
+
+    /// Defaults to `shaders/material.vert`, which outputs the world space `v_position` and
+    /// `v_normal` and the `v_tex_coord` of the vertices, and their `v_color` and `v_tangent`
+    /// when the `VERTEX_COLOR` and `VERTEX_TANGENT` defines are set.
+    fn vertex_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        None
+    }
+
+    fn fragment_shader(asset_server: &AssetServer) -> Handle<Shader>;
+
+    fn uniform(&self) -> Self::Uniform;
+
+    /// The images sampled by the fragment shader, `#include "shaders/textures.glsl"` declares
+    /// them. The first one is `tex[0]` with bindless textures and `tex_0` otherwise, and so on.
+    fn textures(&self) -> Vec<Handle<Image>> {
+        Vec::new()
+    }
+
+    /// Blended materials are drawn in the [`Transparent3D`] phase, the others in the
+    /// [`Opaque3D`] one. Masking is left to the fragment shader.
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Opaque
+    }
+
+    /// Customizes the pipeline of the material, to add shader defs or change the draw
+    /// parameters.
+    fn specialize(_key: MaterialPipelineKey, _descriptor: &mut RenderPipelineDescriptor) {}
+}
+
+/// Draws the meshes with a [`Handle<M>`] in the [`Opaque3D`] and [`Transparent3D`] phases.
+///
+/// The [`CorePipelinePlugin`](crate::CorePipelinePlugin) has to be added first. The meshes
+/// neither cast nor receive shadows.
+pub struct MaterialPlugin<M: Material> {
+    marker: PhantomData<M>,
+}
+
+impl<M: Material> Default for MaterialPlugin<M> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material> Plugin for MaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<M>()
+            .init_resource::<MaterialPipeline<M>>()
+            .init_resource::<SpecializedPipelines<MaterialPipeline<M>>>()
+            .init_resource::<ExtractedMaterialMeshes<M>>()
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_material_meshes::<M>.after(ViewSystem::CheckVisibility),
+            )
+            .add_system_to_stage(RenderStage::Prepare, prepare_material_meshes::<M>)
+            .add_system_to_stage(RenderStage::Queue, queue_material_meshes::<M>)
+            .add_system_to_stage(
+                RenderStage::Cleanup,
+                despawn_render_entities::<MaterialBatch<M>>,
+            );
+
+        let draw_material = DrawMaterial::<M>::new(&mut app.world);
+        app.world
+            .get_resource::<DrawFunctions<Opaque3D>>()
+            .unwrap()
+            .write()
+            .add(draw_material);
+        let draw_material = DrawMaterial::<M>::new(&mut app.world);
+        app.world
+            .get_resource::<DrawFunctions<Transparent3D>>()
+            .unwrap()
+            .write()
+            .add(draw_material);
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct MaterialPipelineKey {
+    /// Alpha blended, see [`AlphaMode::Blend`]
+    pub transparent: bool,
+    /// The view has a [`Skybox`] bound to `u_environment_map`
+    pub environment_map: bool,
+    /// The attributes of the mesh, setting their `VERTEX_*` shader defines
+    pub vertex_layout: MeshVertexLayout,
+    /// Only triangles are culled
+    pub primitive_topology: PrimitiveTopology,
+}
+
+pub struct MaterialPipeline<M: Material> {
+    pub vertex_shader: Handle<Shader>,
+    pub fragment_shader: Handle<Shader>,
+    marker: PhantomData<M>,
+}
+
+impl<M: Material> FromWorld for MaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            vertex_shader: M::vertex_shader(asset_server)
+                .unwrap_or_else(|| asset_server.load("shaders/material.vert")),
+            fragment_shader: M::fragment_shader(asset_server),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material> SpecializedPipeline for MaterialPipeline<M> {
+    type Key = MaterialPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let backface_culling = if key.primitive_topology.is_triangles() {
+            glium::draw_parameters::BackfaceCullingMode::CullClockwise
+        } else {
+            glium::draw_parameters::BackfaceCullingMode::CullingDisabled
+        };
+        let draw_parameters = if key.transparent {
+            glium::DrawParameters {
+                depth: glium::Depth {
+                    test: glium::draw_parameters::DepthTest::IfLess,
+                    ..Default::default()
+                },
+                backface_culling,
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            }
+        } else {
+            glium::DrawParameters {
+                depth: glium::Depth {
+                    test: glium::draw_parameters::DepthTest::IfLess,
+                    write: true,
+                    ..Default::default()
+                },
+                backface_culling,
+                ..Default::default()
+            }
+        };
+        let mut shader_defs: Vec<String> = key.vertex_layout.shader_defs().collect();
+        if key.environment_map {
+            shader_defs.push("ENVIRONMENT_MAP".to_string());
+        }
+        let mut descriptor = RenderPipelineDescriptor {
+            vertex_shader: self.vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: self.fragment_shader.clone_weak(),
+            shader_defs,
+            draw_parameters,
+        };
+        M::specialize(key, &mut descriptor);
+        descriptor
+    }
+}
+
+pub struct ExtractedMaterialMesh<M: Material> {
+    /// Listed in the [`VisibleEntities`] of the views seeing it
+    pub entity: Entity,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<M>,
+    pub transform: Mat4,
+}
+
+pub struct ExtractedMaterialMeshes<M: Material> {
+    pub items: Vec<ExtractedMaterialMesh<M>>,
+}
+
+impl<M: Material> Default for ExtractedMaterialMeshes<M> {
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+fn extract_material_meshes<M: Material>(
+    mut extracts: ResMut<ExtractedMaterialMeshes<M>>,
+    meshes: Res<Assets<Mesh>>,
+    images: Res<Assets<Image>>,
+    materials: Res<Assets<M>>,
+    query: Query<(
+        Entity,
+        &Handle<Mesh>,
+        &Handle<M>,
+        &GlobalTransform,
+        &Visibility,
+    )>,
+) {
+    extracts.items.clear();
+    for (entity, mesh, material, global_transform, visibility) in query.iter() {
+        if !visibility.active || !visibility.visible || meshes.get(mesh).is_none() {
+            continue;
+        }
+        match materials.get(material) {
+            Some(material)
+                if material
+                    .textures()
+                    .iter()
+                    .all(|image| images.get(image).is_some()) => {}
+            _ => continue,
+        }
+        extracts.items.push(ExtractedMaterialMesh {
+            entity,
+            mesh: mesh.clone_weak(),
+            material: material.clone_weak(),
+            transform: global_transform.compute_matrix(),
+        });
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct MaterialMeshInstance {
+    i_model: [[f32; 4]; 4],
+    i_normal_matrix: [[f32; 3]; 3],
+}
+
+glium::implement_vertex!(MaterialMeshInstance, i_model, i_normal_matrix);
+
+impl MaterialMeshInstance {
+    fn new(transform: &Mat4) -> Self {
+        Self {
+            i_model: transform.to_cols_array_2d(),
+            i_normal_matrix: Mat3::from_mat4(transform.inverse().transpose()).to_cols_array_2d(),
+        }
+    }
+}
+
+/// The instances of a mesh drawn with the same material, rendered with a single instanced
+/// draw.
+#[derive(Component)]
+pub struct MaterialBatch<M: Material> {
+    pub mesh: Handle<Mesh>,
+    pub instances: Vec<MaterialMeshInstance>,
+    pub uniform: M::Uniform,
+    pub key: MaterialPipelineKey,
+    /// The views seeing every instance of the batch
+    pub views: Vec<Entity>,
+    /// The [`Material::textures`], in order
+    pub texture_slots: TextureSlots,
+}
+
+impl<M: Material> MaterialBatch<M> {
+    fn new(
+        mesh: Handle<Mesh>,
+        gpu_mesh: &GpuMesh,
+        material: &M,
+        views: Vec<Entity>,
+        texture_buffer: &mut TextureBuffer,
+    ) -> Self {
+        let mut texture_slots = texture_buffer.new_slots();
+        for image in material.textures() {
+            if texture_buffer.bind(&mut texture_slots, &image).is_none() {
+                warn!(
+                    "a material samples more than {} images, the others are not bound",
+                    texture_slots.capacity()
+                );
+                break;
+            }
+        }
+        Self {
+            mesh,
+            instances: Vec::new(),
+            uniform: material.uniform(),
+            key: MaterialPipelineKey {
+                transparent: material.alpha_mode() == AlphaMode::Blend,
+                environment_map: false,
+                vertex_layout: gpu_mesh.layout.clone(),
+                primitive_topology: gpu_mesh.primitive_topology,
+            },
+            views,
+            texture_slots,
+        }
+    }
+
+    /// View space depth of the closest instance, see [`view_depth`].
+    pub fn depth(&self, view_matrix: &Mat4) -> f32 {
+        self.instances
+            .iter()
+            .map(|instance| view_depth(view_matrix, Vec3::from_slice(&instance.i_model[3])))
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+fn prepare_material_meshes<M: Material>(
+    mut commands: Commands,
+    extracts: Res<ExtractedMaterialMeshes<M>>,
+    materials: Res<Assets<M>>,
+    views: Query<(Entity, &VisibleEntities), With<ExtractedView>>,
+    gpu_meshes: NonSend<GpuMeshes>,
+    mut texture_buffer: NonSendMut<TextureBuffer>,
+) {
+    if extracts.items.is_empty() {
+        return;
+    }
+    // used for the unused slots of the bindless textures
+    texture_buffer.insert(&DEFAULT_IMAGE_HANDLE.typed());
+
+    let mut entity_views: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for (view, visible_entities) in views.iter() {
+        for entity in visible_entities.entities.iter() {
+            entity_views.entry(*entity).or_default().push(view);
+        }
+    }
+
+    let mut batches: HashMap<(Handle<M>, Handle<Mesh>, Vec<Entity>), MaterialBatch<M>> =
+        HashMap::default();
+    for extracted in extracts.items.iter() {
+        let views = match entity_views.get(&extracted.entity) {
+            Some(views) => views,
+            None => continue,
+        };
+        let material = match materials.get(&extracted.material) {
+            Some(material) => material,
+            None => continue,
+        };
+        let gpu_mesh = match gpu_meshes.get(&extracted.mesh) {
+            Some(gpu_mesh) => gpu_mesh,
+            None => continue,
+        };
+        let instance = MaterialMeshInstance::new(&extracted.transform);
+        if material.alpha_mode() == AlphaMode::Blend {
+            // transparent meshes are drawn one by one so they can be sorted back to front
+            let mut batch = MaterialBatch::new(
+                extracted.mesh.clone_weak(),
+                gpu_mesh,
+                material,
+                views.clone(),
+                &mut texture_buffer,
+            );
+            batch.instances.push(instance);
+            commands.spawn().insert(batch);
+            continue;
+        }
+        batches
+            .entry((
+                extracted.material.clone_weak(),
+                extracted.mesh.clone_weak(),
+                views.clone(),
+            ))
+            .or_insert_with(|| {
+                MaterialBatch::new(
+                    extracted.mesh.clone_weak(),
+                    gpu_mesh,
+                    material,
+                    views.clone(),
+                    &mut texture_buffer,
+                )
+            })
+            .instances
+            .push(instance);
+    }
+    for batch in batches.into_values() {
+        commands.spawn().insert(batch);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_material_meshes<M: Material>(
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        Option<&Skybox>,
+        &mut RenderPhase<Opaque3D>,
+        &mut RenderPhase<Transparent3D>,
+    )>,
+    query: Query<(Entity, &MaterialBatch<M>)>,
+    gpu_cubemaps: NonSend<GpuCubemaps>,
+    mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
+    pipeline: Res<MaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedPipelines<MaterialPipeline<M>>>,
+    opaque_draw_functions: Res<DrawFunctions<Opaque3D>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3D>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let draw_opaque_function = opaque_draw_functions
+        .read()
+        .get_id::<DrawMaterial<M>>()
+        .unwrap();
+    let draw_transparent_function = transparent_draw_functions
+        .read()
+        .get_id::<DrawMaterial<M>>()
+        .unwrap();
+
+    for (entity, batch) in query.iter() {
+        for (view_entity, view, skybox, mut opaques, mut transparents) in views.iter_mut() {
+            if !batch.views.contains(&view_entity) {
+                continue;
+            }
+            let key = MaterialPipelineKey {
+                environment_map: skybox
+                    .is_some_and(|skybox| gpu_cubemaps.contains(&skybox.cubemap)),
+                ..batch.key.clone()
+            };
+            let cached_pipeline = pipelines.specialize(&mut render_pipeline_cache, &pipeline, key);
+            let distance = batch.depth(&view.view_matrix);
+            if batch.key.transparent {
+                transparents.add(Transparent3D {
+                    distance,
+                    entity,
+                    draw_function: draw_transparent_function,
+                    pipeline: cached_pipeline,
+                });
+            } else {
+                opaques.add(Opaque3D {
+                    distance,
+                    entity,
+                    draw_function: draw_opaque_function,
+                    pipeline: cached_pipeline,
+                });
+            }
+        }
+    }
+}
+
+pub struct DrawMaterial<M: Material> {
+    params: SystemState<(
+        NonSend<'static, Window>,
+        NonSend<'static, TextureBuffer>,
+        NonSend<'static, RenderPipelineCache>,
+        NonSend<'static, GpuMeshes>,
+        NonSend<'static, GpuCubemaps>,
+        SRes<ExtractedLights>,
+        SQuery<(Read<ExtractedView>, Option<Read<Skybox>>)>,
+        SQuery<Read<MaterialBatch<M>>>,
+    )>,
+}
+
+impl<M: Material> DrawMaterial<M> {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            params: SystemState::new(world),
+        }
+    }
+}
+
+impl<M: Material, I: PhaseItem + CachedPipelinePhaseItem + EntityPhaseItem> Draw<I>
+    for DrawMaterial<M>
+{
+    fn draw(&mut self, world: &World, frame: &mut TrackedFrame, view: Entity, item: &I) {
+        let (
+            window,
+            texture_buffer,
+            pipeline_cache,
+            gpu_meshes,
+            gpu_cubemaps,
+            extracted_lights,
+            views,
+            query,
+        ) = self.params.get(world);
+
+        let (view, skybox) = match views.get(view) {
+            Ok(view) => view,
+            Err(_) => return,
+        };
+        let pipeline = match pipeline_cache.get(item.cached_pipeline()) {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+        let batch = match query.get(item.entity()) {
+            Ok(batch) => batch,
+            Err(_) => return,
+        };
+        let gpu_mesh = match gpu_meshes.get(&batch.mesh) {
+            Some(gpu_mesh) => gpu_mesh,
+            None => return,
+        };
+
+        let facade = window.facade();
+        let texture_bindings = match texture_buffer.texture_bindings(facade, &batch.texture_slots) {
+            Ok(bindings) => bindings,
+            Err(TextureBindingsError::NotUploaded) => return,
+            Err(err) => {
+                error!("Failed to bind the textures: {}", err);
+                return;
+            }
+        };
+        let camera_uniform_buffer = UniformBuffer::new(
+            facade,
+            CameraUniformBuffer::new(view.pv_matrix.to_cols_array_2d()),
+        )
+        .unwrap();
+        let lights_uniform_buffer = UniformBuffer::new(
+            facade,
+            LightsUniformBuffer::new(view.position, &extracted_lights),
+        )
+        .unwrap();
+        let material_uniform_buffer = UniformBuffer::new(facade, batch.uniform).unwrap();
+
+        let environment_map = skybox.and_then(|skybox| gpu_cubemaps.get(&skybox.cubemap));
+
+        let uniforms = ChainedUniforms(
+            ChainedUniforms(
+                glium::uniform! {
+                    camera: &camera_uniform_buffer,
+                    lights: &lights_uniform_buffer,
+                    material: &material_uniform_buffer,
+                },
+                texture_bindings,
+            ),
+            EnvironmentMapUniform(environment_map.map(cubemap_sampler)),
+        );
+
+        let instance_buffer = glium::VertexBuffer::new(facade, &batch.instances).unwrap();
+        frame
+            .draw(
+                (
+                    &gpu_mesh.vertex_buffer,
+                    instance_buffer.per_instance().unwrap(),
+                ),
+                &gpu_mesh.index_buffer,
+                &pipeline.program,
+                &uniforms,
+                &pipeline.parameters,
+            )
+            .unwrap();
+    }
+}
@@ -4,7 +4,9 @@ use arara_render::{
 };
 use arara_utils::FloatOrd;
 
+/// Sorted front to back, so the depth test discards hidden fragments early.
 pub struct Opaque3D {
+    /// Distance to the camera plane, along the view direction
     pub distance: f32,
     pub entity: Entity,
     pub draw_function: DrawFunctionId,
@@ -36,7 +38,9 @@ impl CachedPipelinePhaseItem for Opaque3D {
     }
 }
 
+/// Sorted back to front, so translucent surfaces blend over what is behind them.
 pub struct Transparent3D {
+    /// Distance to the camera plane, along the view direction
     pub distance: f32,
     pub entity: Entity,
     pub draw_function: DrawFunctionId,
@@ -48,7 +52,7 @@ impl PhaseItem for Transparent3D {
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
-        FloatOrd(self.distance)
+        FloatOrd(-self.distance)
     }
 
     fn draw_function(&self) -> DrawFunctionId {
@@ -67,3 +71,99 @@ impl CachedPipelinePhaseItem for Transparent3D {
         self.pipeline
     }
 }
+
+/// The [`Skybox`](crate::Skybox) of the view, drawn after the [`Opaque3D`] phase so only the
+/// uncovered pixels are shaded.
+pub struct Skybox3D {
+    pub draw_function: DrawFunctionId,
+    pub pipeline: CachedPipelineId,
+}
+
+impl PhaseItem for Skybox3D {
+    type SortKey = ();
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {}
+
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
+
+impl CachedPipelinePhaseItem for Skybox3D {
+    fn cached_pipeline(&self) -> CachedPipelineId {
+        self.pipeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arara_asset::Handle;
+    use arara_ecs::{entity::Entity, world::World};
+    use arara_render::{
+        Draw, DrawFunctions, RenderPhase, RenderPipelineCache, RenderPipelineDescriptor,
+        TrackedFrame,
+    };
+    use glam::{Mat4, Vec3};
+
+    use super::*;
+    use crate::prepare_phase::view_depth;
+
+    struct NoDraw;
+
+    impl<P: PhaseItem> Draw<P> for NoDraw {
+        fn draw(&mut self, _: &World, _: &mut TrackedFrame, _: Entity, _: &P) {}
+    }
+
+    fn pipeline() -> CachedPipelineId {
+        RenderPipelineCache::default().queue(RenderPipelineDescriptor {
+            vertex_shader: Handle::default(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: Handle::default(),
+            shader_defs: Vec::new(),
+            draw_parameters: Default::default(),
+        })
+    }
+
+    #[test]
+    fn items_are_sorted_by_view_depth() {
+        let view_matrix = Mat4::look_at_rh(Vec3::ZERO, -Vec3::Z, Vec3::Y);
+        // off the view axis, farther from the camera but closer to its plane
+        let near = Entity::from_raw(0);
+        let far = Entity::from_raw(1);
+        let positions = [
+            (far, Vec3::new(0.0, 0.0, -5.0)),
+            (near, Vec3::new(4.0, 0.0, -4.5)),
+        ];
+
+        let mut opaques = RenderPhase::<Opaque3D>::default();
+        let mut transparents = RenderPhase::<Transparent3D>::default();
+        let opaque_draw = DrawFunctions::<Opaque3D>::default().write().add(NoDraw);
+        let transparent_draw = DrawFunctions::<Transparent3D>::default()
+            .write()
+            .add(NoDraw);
+        for (entity, position) in positions {
+            let distance = view_depth(&view_matrix, position);
+            opaques.add(Opaque3D {
+                distance,
+                entity,
+                draw_function: opaque_draw,
+                pipeline: pipeline(),
+            });
+            transparents.add(Transparent3D {
+                distance,
+                entity,
+                draw_function: transparent_draw,
+                pipeline: pipeline(),
+            });
+        }
+        opaques.sort();
+        transparents.sort();
+
+        let opaque_order: Vec<_> = opaques.items.iter().map(|item| item.entity).collect();
+        assert_eq!(opaque_order, [near, far]);
+        let transparent_order: Vec<_> = transparents.items.iter().map(|item| item.entity).collect();
+        assert_eq!(transparent_order, [far, near]);
+    }
+}
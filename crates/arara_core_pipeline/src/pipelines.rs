@@ -1,6 +1,10 @@
 use arara_asset::{AssetServer, Handle};
 use arara_ecs::world::{FromWorld, World};
-use arara_render::{RenderPipelineDescriptor, Shader, SpecializedPipeline};
+use arara_render::{
+    MeshVertexLayout, PrimitiveTopology, RenderPipelineDescriptor, Shader, SpecializedPipeline,
+};
+
+use crate::DebugView;
 
 #[derive(Debug, Clone)]
 pub struct DefaultShader {
@@ -20,9 +24,19 @@ impl FromWorld for DefaultShader {
     }
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct CorePipelineKey {
+    /// Alpha blended, see [`AlphaMode::Blend`](crate::AlphaMode::Blend)
     pub transparent: bool,
+    /// Disables backface culling
+    pub double_sided: bool,
+    /// Only draws the edges, see [`Wireframe`](crate::Wireframe)
+    pub wireframe: bool,
+    pub debug_view: DebugView,
+    /// The attributes of the mesh, setting their `VERTEX_*` shader defines
+    pub vertex_layout: MeshVertexLayout,
+    /// Only triangles are culled and drawn as wireframes
+    pub primitive_topology: PrimitiveTopology,
 }
 
 pub struct CorePipeline {
@@ -55,13 +69,24 @@ impl SpecializedPipeline for CorePipeline {
             vertex_shader,
             fragment_shader,
         } = self;
+        let backface_culling = if key.double_sided || !key.primitive_topology.is_triangles() {
+            glium::draw_parameters::BackfaceCullingMode::CullingDisabled
+        } else {
+            glium::draw_parameters::BackfaceCullingMode::CullClockwise
+        };
+        let polygon_mode = if key.wireframe {
+            glium::draw_parameters::PolygonMode::Line
+        } else {
+            glium::draw_parameters::PolygonMode::Fill
+        };
         let draw_parameters = if key.transparent {
             glium::DrawParameters {
                 depth: glium::Depth {
                     test: glium::draw_parameters::DepthTest::IfLess,
                     ..Default::default()
                 },
-                backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+                backface_culling,
+                polygon_mode,
                 blend: glium::draw_parameters::Blend::alpha_blending(),
                 ..Default::default()
             }
@@ -72,13 +97,23 @@ impl SpecializedPipeline for CorePipeline {
                     write: true,
                     ..Default::default()
                 },
-                backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+                backface_culling,
+                polygon_mode,
                 ..Default::default()
             }
         };
         RenderPipelineDescriptor {
             vertex_shader: vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
             fragment_shader: fragment_shader.clone_weak(),
+            shader_defs: key
+                .debug_view
+                .shader_def()
+                .into_iter()
+                .map(String::from)
+                .chain(key.vertex_layout.shader_defs())
+                .collect(),
             draw_parameters,
         }
     }
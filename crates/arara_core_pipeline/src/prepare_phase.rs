@@ -1,105 +1,277 @@
-use arara_asset::Assets;
+use arara_asset::Handle;
 use arara_ecs::prelude::*;
-use arara_render::{Image, Mesh, TextureBuffer};
-use glam::{vec3, vec4, Mat3};
+use arara_render::{
+    ExtractedView, GpuMeshes, Mesh, RenderCapabilities, TextureBuffer, TextureSlots,
+    VisibleEntities,
+};
+use arara_utils::{tracing::warn, HashMap};
+use glam::{Mat3, Mat4, Vec3};
 
 use crate::{
     core_pipeline_entities::ExtractedCorePipelineEntity,
-    extract_phase::ExtractedCorePipelineEntitys,
+    extract_phase::ExtractedCorePipelineEntitys, pipelines::CorePipelineKey, AlphaMode, DebugView,
+    ExtractedLights, WireframeConfig,
 };
 
 #[derive(Copy, Clone)]
-pub struct Vertex {
-    i_position: [f32; 3],
-    i_normal: [f32; 3],
+pub struct MeshInstance {
+    i_model: [[f32; 4]; 4],
+    i_normal_matrix: [[f32; 3]; 3],
     i_color: [f32; 4],
-    i_tex_coord: [f32; 2],
+    i_emissive: [f32; 3],
+    /// Specular strength, shininess, alpha cutoff and `1.0` if it receives shadows
+    i_material: [f32; 4],
     i_tex_id: u32,
+    /// `-1` when there is no normal map
+    i_normal_tex_id: i32,
 }
 
-glium::implement_vertex!(Vertex, i_position, i_normal, i_color, i_tex_coord, i_tex_id);
+glium::implement_vertex!(
+    MeshInstance,
+    i_model,
+    i_normal_matrix,
+    i_color,
+    i_emissive,
+    i_material,
+    i_tex_id,
+    i_normal_tex_id
+);
 
+/// Every instance of a mesh drawn with the same pipeline, rendered with a single instanced draw.
 #[derive(Component)]
 pub struct CorePipelineBatch {
-    pub vertices: Vec<Vertex>,
-    pub indices: Vec<u32>,
-    pub transparent: bool,
+    pub mesh: Handle<Mesh>,
+    /// Culled shadow casters first, then the visible shadow casters and the rest of the instances
+    pub instances: Vec<MeshInstance>,
+    /// Amount of instances drawn into the [`ShadowMaps`](crate::ShadowMaps)
+    pub shadow_casters: usize,
+    /// Index of the first instance visible from a camera
+    pub first_visible: usize,
+    pub key: CorePipelineKey,
+    /// The views seeing every visible instance of the batch
+    pub views: Vec<Entity>,
+    /// The images sampled by the instances, `i_tex_id` and `i_normal_tex_id` index them
+    pub texture_slots: TextureSlots,
+}
+
+impl CorePipelineBatch {
+    fn new(
+        mesh: Handle<Mesh>,
+        key: CorePipelineKey,
+        views: Vec<Entity>,
+        instances: BatchInstances,
+    ) -> Self {
+        let BatchInstances {
+            texture_slots,
+            culled_shadow_casters: mut all_instances,
+            shadow_casters,
+            others,
+        } = instances;
+        let first_visible = all_instances.len();
+        all_instances.extend(shadow_casters);
+        let shadow_casters = all_instances.len();
+        all_instances.extend(others);
+        Self {
+            mesh,
+            instances: all_instances,
+            shadow_casters,
+            first_visible,
+            key,
+            views,
+            texture_slots,
+        }
+    }
+
+    /// The instances drawn in the main passes.
+    pub fn visible_instances(&self) -> &[MeshInstance] {
+        &self.instances[self.first_visible..]
+    }
+
+    /// The instances drawn into the [`ShadowMaps`](crate::ShadowMaps).
+    pub fn shadow_caster_instances(&self) -> &[MeshInstance] {
+        &self.instances[..self.shadow_casters]
+    }
+
+    /// View space depth of the closest visible instance, see [`view_depth`].
+    pub fn depth(&self, view_matrix: &Mat4) -> f32 {
+        self.visible_instances()
+            .iter()
+            .map(|instance| view_depth(view_matrix, Vec3::from_slice(&instance.i_model[3])))
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+/// Distance from the camera plane of `view_matrix` to `position`, along the view direction.
+///
+/// Unlike the distance to the camera position it orders the objects as they overlap on screen,
+/// also when they are off the view axis.
+pub(crate) fn view_depth(view_matrix: &Mat4, position: Vec3) -> f32 {
+    -view_matrix.transform_point3(position).z
+}
+
+struct BatchInstances {
+    texture_slots: TextureSlots,
+    culled_shadow_casters: Vec<MeshInstance>,
+    shadow_casters: Vec<MeshInstance>,
+    others: Vec<MeshInstance>,
+}
+
+impl BatchInstances {
+    fn new(texture_slots: TextureSlots) -> Self {
+        Self {
+            texture_slots,
+            culled_shadow_casters: Vec::new(),
+            shadow_casters: Vec::new(),
+            others: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, instance: MeshInstance, visible: bool, shadow_caster: bool) {
+        if !visible {
+            self.culled_shadow_casters.push(instance);
+        } else if shadow_caster {
+            self.shadow_casters.push(instance);
+        } else {
+            self.others.push(instance);
+        }
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn prepare_core_pipeline_phase(
     mut commands: Commands,
-    meshes: Res<Assets<Mesh>>,
-    images: Res<Assets<Image>>,
     extracts: Res<ExtractedCorePipelineEntitys>,
+    extracted_lights: Res<ExtractedLights>,
+    wireframe_config: Res<WireframeConfig>,
+    debug_view: Res<DebugView>,
+    capabilities: Res<RenderCapabilities>,
+    views: Query<(Entity, &VisibleEntities), With<ExtractedView>>,
+    gpu_meshes: NonSend<GpuMeshes>,
     mut texture_buffer: NonSendMut<TextureBuffer>,
 ) {
     if extracts.items.is_empty() {
         return;
     }
 
-    let mut vertices_opaque = Vec::new();
-    let mut vertices_transparent = Vec::new();
-    let mut indices_opaque = Vec::new();
-    let mut indices_transparent = Vec::new();
-    for ExtractedCorePipelineEntity {
-        mesh: mesh_handle,
-        transform,
-        color,
-        image: image_handle,
-    } in extracts.items.iter()
-    {
-        let transparent = images.get(image_handle).unwrap().translucent || color.a() < 1.0;
-        let vertices = if transparent {
-            &mut vertices_transparent
-        } else {
-            &mut vertices_opaque
+    let mut entity_views: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for (view, visible_entities) in views.iter() {
+        for entity in visible_entities.entities.iter() {
+            entity_views.entry(*entity).or_default().push(view);
+        }
+    }
+
+    let shadows_enabled = extracted_lights
+        .lights
+        .iter()
+        .any(|light| light.shadow_map.is_some());
+
+    // a mesh sampling more images than a draw call can bind is split into several batches, the
+    // instances are also batched by the views seeing them so every view only draws its own
+    let mut batches: HashMap<(CorePipelineKey, Vec<Entity>, Handle<Mesh>), Vec<BatchInstances>> =
+        HashMap::default();
+    for extracted in extracts.items.iter() {
+        let ExtractedCorePipelineEntity {
+            entity,
+            mesh,
+            transform,
+            material,
+            shadow_caster,
+            shadow_receiver,
+            wireframe,
+        } = extracted;
+        let views = entity_views.get(entity).cloned().unwrap_or_default();
+        let visible = !views.is_empty();
+        // culled entities are only drawn in the shadow maps
+        if !visible && (!shadows_enabled || !*shadow_caster) {
+            continue;
+        }
+        let gpu_mesh = match gpu_meshes.get(mesh) {
+            Some(gpu_mesh) => gpu_mesh,
+            None => continue,
+        };
+        let key = CorePipelineKey {
+            transparent: material.alpha_mode == AlphaMode::Blend,
+            double_sided: material.double_sided,
+            // OpenGL ES has no polygon modes
+            wireframe: (wireframe_config.global || *wireframe)
+                && !capabilities.gles
+                && gpu_mesh.primitive_topology.is_triangles(),
+            debug_view: *debug_view,
+            vertex_layout: gpu_mesh.layout.clone(),
+            primitive_topology: gpu_mesh.primitive_topology,
         };
-        let indices = if transparent {
-            &mut indices_transparent
+        let images = std::iter::once(&material.base_color_texture)
+            .chain(material.normal_map_texture.as_ref());
+        let mut transparent_instances = None;
+        let instances = if key.transparent {
+            // transparent entities are drawn one by one so they can be sorted back to front
+            transparent_instances.insert(BatchInstances::new(texture_buffer.new_slots()))
         } else {
-            &mut indices_opaque
+            let batch_instances = batches
+                .entry((key.clone(), views.clone(), mesh.clone_weak()))
+                .or_default();
+            match batch_instances.last() {
+                Some(instances) if instances.texture_slots.fits(images) => {}
+                _ => batch_instances.push(BatchInstances::new(texture_buffer.new_slots())),
+            }
+            batch_instances.last_mut().unwrap()
+        };
+        let tex_id = texture_buffer
+            .bind(&mut instances.texture_slots, &material.base_color_texture)
+            .unwrap();
+        let normal_tex_id = match &material.normal_map_texture {
+            Some(image) => match texture_buffer.bind(&mut instances.texture_slots, image) {
+                Some(slot) => slot as i32,
+                // the batches are split when full, so this only happens with a single slot
+                None => {
+                    warn!(
+                        "a draw call binds {} image, the normal map {:?} is not bound",
+                        instances.texture_slots.capacity(),
+                        image
+                    );
+                    -1
+                }
+            },
+            None => -1,
+        };
+        let alpha_cutoff = match material.alpha_mode {
+            AlphaMode::Mask(cutoff) => cutoff,
+            _ => 0.0,
         };
+        let [emissive_r, emissive_g, emissive_b, _] = material.emissive.as_rgba_f32();
 
-        let tex_id = texture_buffer.get_or_insert(image_handle.clone_weak());
-        let mesh = meshes.get(mesh_handle).unwrap();
-        let offset = vertices.len() as u32;
-        let ti_transform = Mat3::from_mat4(transform.inverse().transpose());
-        let color: [f32; 4] = color.to_owned().into();
-
-        for vertex in mesh.vertices.iter() {
-            let position = vec4(
-                vertex.position[0],
-                vertex.position[1],
-                vertex.position[2],
-                1.0,
-            );
-            let position = *transform * position;
-            let normal = vec3(vertex.normal[0], vertex.normal[1], vertex.normal[2]);
-            let normal = ti_transform * normal;
-            vertices.push(Vertex {
-                i_position: [position.x, position.y, position.z],
-                i_normal: normal.into(),
-                i_color: color,
-                i_tex_coord: vertex.tex_coord,
-                i_tex_id: tex_id as u32,
-            });
-        }
-        for idx in mesh.indices.iter() {
-            indices.push(*idx + offset);
+        let instance = MeshInstance {
+            i_model: transform.to_cols_array_2d(),
+            i_normal_matrix: Mat3::from_mat4(transform.inverse().transpose()).to_cols_array_2d(),
+            i_color: material.base_color.into(),
+            i_emissive: [emissive_r, emissive_g, emissive_b],
+            i_material: [
+                material.specular_strength,
+                material.shininess,
+                alpha_cutoff,
+                if *shadow_receiver { 1.0 } else { 0.0 },
+            ],
+            i_tex_id: tex_id as u32,
+            i_normal_tex_id: normal_tex_id,
+        };
+        instances.push(instance, visible, *shadow_caster);
+        if let Some(instances) = transparent_instances {
+            commands.spawn().insert(CorePipelineBatch::new(
+                mesh.clone_weak(),
+                key,
+                views,
+                instances,
+            ));
         }
     }
-    if !vertices_opaque.is_empty() {
-        commands.spawn().insert(CorePipelineBatch {
-            vertices: vertices_opaque,
-            indices: indices_opaque,
-            transparent: false,
-        });
-    }
-    if !vertices_transparent.is_empty() {
-        commands.spawn().insert(CorePipelineBatch {
-            vertices: vertices_transparent,
-            indices: indices_transparent,
-            transparent: true,
-        });
+    for ((key, views, mesh), batch_instances) in batches.into_iter() {
+        for instances in batch_instances {
+            commands.spawn().insert(CorePipelineBatch::new(
+                mesh.clone_weak(),
+                key.clone(),
+                views.clone(),
+                instances,
+            ));
+        }
     }
 }
@@ -1,14 +1,18 @@
 use arara_ecs::prelude::*;
-use arara_render::{DrawFunctions, RenderPhase, RenderPipelineCache};
+use arara_render::{DrawFunctions, ExtractedView, RenderPhase, RenderPipelineCache};
 
 use crate::{
-    draw_functions::DrawSimpleMesh, pipelines::CorePipelineKey, prepare_phase::CorePipelineBatch,
-    CorePipeline, Opaque3D, SpecializedPipelines, Transparent3D,
+    draw_functions::DrawSimpleMesh, prepare_phase::CorePipelineBatch, CorePipeline, Opaque3D,
+    SpecializedPipelines, Transparent3D,
 };
 
 pub(crate) fn queue_core_pipeline_phase(
-    mut opaques: ResMut<RenderPhase<Opaque3D>>,
-    mut transparents: ResMut<RenderPhase<Transparent3D>>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        &mut RenderPhase<Opaque3D>,
+        &mut RenderPhase<Transparent3D>,
+    )>,
     query: Query<(Entity, &CorePipelineBatch)>,
     mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
     pipeline: Res<CorePipeline>,
@@ -20,17 +24,6 @@ pub(crate) fn queue_core_pipeline_phase(
         return;
     }
 
-    let opaque_pipeline = pipelines.specialize(
-        &mut render_pipeline_cache,
-        &pipeline,
-        CorePipelineKey { transparent: false },
-    );
-    let transparent_pipeline = pipelines.specialize(
-        &mut render_pipeline_cache,
-        &pipeline,
-        CorePipelineKey { transparent: true },
-    );
-
     let draw_opaque_function = opaque_draw_functions
         .read()
         .get_id::<DrawSimpleMesh>()
@@ -41,20 +34,31 @@ pub(crate) fn queue_core_pipeline_phase(
         .unwrap();
 
     for (entity, batch) in query.iter() {
-        if batch.transparent {
-            transparents.add(Transparent3D {
-                distance: 0.0,
-                entity,
-                draw_function: draw_transparent_function,
-                pipeline: transparent_pipeline,
-            });
-        } else {
-            opaques.add(Opaque3D {
-                distance: 0.0,
-                entity,
-                draw_function: draw_opaque_function,
-                pipeline: opaque_pipeline,
-            });
+        if batch.visible_instances().is_empty() {
+            continue;
+        }
+        let cached_pipeline =
+            pipelines.specialize(&mut render_pipeline_cache, &pipeline, batch.key.clone());
+        for (view_entity, view, mut opaques, mut transparents) in views.iter_mut() {
+            if !batch.views.contains(&view_entity) {
+                continue;
+            }
+            let distance = batch.depth(&view.view_matrix);
+            if batch.key.transparent {
+                transparents.add(Transparent3D {
+                    distance,
+                    entity,
+                    draw_function: draw_transparent_function,
+                    pipeline: cached_pipeline,
+                });
+            } else {
+                opaques.add(Opaque3D {
+                    distance,
+                    entity,
+                    draw_function: draw_opaque_function,
+                    pipeline: cached_pipeline,
+                });
+            }
         }
     }
 }
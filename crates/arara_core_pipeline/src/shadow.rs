@@ -0,0 +1,312 @@
+use arara_asset::{AssetServer, Handle};
+use arara_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SQuery},
+        SystemState,
+    },
+};
+use arara_render::{
+    implement_uniform_block, CachedPipelineId, CachedPipelinePhaseItem, Draw, DrawFunctionId,
+    DrawFunctions, EntityPhaseItem, GpuMeshes, OffscreenView, PhaseItem, RenderPhase,
+    RenderPipelineCache, RenderPipelineDescriptor, Shader, SpecializedPipeline,
+    SpecializedPipelines, TrackedFrame,
+};
+use arara_utils::{tracing::error, FloatOrd};
+use arara_window::Window;
+use glam::Mat4;
+use glium::{
+    framebuffer::SimpleFrameBuffer,
+    texture::DepthTexture2dArray,
+    uniforms::{
+        DepthTextureComparison, MagnifySamplerFilter, MinifySamplerFilter, Sampler,
+        SamplerWrapFunction,
+    },
+    Surface,
+};
+
+use crate::{prepare_phase::CorePipelineBatch, ExtractedLights};
+
+/// Maximum amount of lights casting shadows at the same time, extra lights render without shadows.
+pub const MAX_SHADOW_MAPS: usize = 4;
+
+/// Entities with this component do not cast shadows.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct NotShadowCaster;
+
+/// Entities with this component are not darkened by shadows.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct NotShadowReceiver;
+
+/// Resolution of the shadow maps.
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowMapSettings {
+    pub size: u32,
+}
+
+impl Default for ShadowMapSettings {
+    fn default() -> Self {
+        Self { size: 2048 }
+    }
+}
+
+/// Depth textures rendered from the point of view of every shadow casting light,
+/// one layer per light.
+#[derive(Default)]
+pub struct ShadowMaps {
+    texture: Option<DepthTexture2dArray>,
+}
+
+impl ShadowMaps {
+    pub fn texture(&self) -> Option<&DepthTexture2dArray> {
+        self.texture.as_ref()
+    }
+
+    /// A sampler comparing the depth with the stored one, as expected by `sampler2DArrayShadow`.
+    pub fn sampler(&self) -> Option<Sampler<'_, DepthTexture2dArray>> {
+        self.texture.as_ref().map(|texture| {
+            texture
+                .sampled()
+                .depth_texture_comparison(Some(DepthTextureComparison::LessOrEqual))
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .minify_filter(MinifySamplerFilter::Linear)
+                .wrap_function(SamplerWrapFunction::Clamp)
+        })
+    }
+}
+
+/// The render view of a shadow casting light, spawned every frame with its [`Shadow`] phase.
+#[derive(Component)]
+pub struct ShadowView {
+    pub shadow_map: u32,
+    pub view_projection: Mat4,
+}
+
+/// A batch of shadow casters drawn into the shadow map of a [`ShadowView`], sorted front to back.
+pub struct Shadow {
+    pub distance: f32,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+    pub pipeline: CachedPipelineId,
+}
+
+impl PhaseItem for Shadow {
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        FloatOrd(self.distance)
+    }
+
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
+
+impl EntityPhaseItem for Shadow {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl CachedPipelinePhaseItem for Shadow {
+    fn cached_pipeline(&self) -> CachedPipelineId {
+        self.pipeline
+    }
+}
+
+pub struct ShadowPipeline {
+    pub vertex_shader: Handle<Shader>,
+    pub fragment_shader: Handle<Shader>,
+}
+
+impl FromWorld for ShadowPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            vertex_shader: asset_server.load("shaders/shadow.vert"),
+            fragment_shader: asset_server.load("shaders/shadow.frag"),
+        }
+    }
+}
+
+impl SpecializedPipeline for ShadowPipeline {
+    type Key = ();
+
+    fn specialize(&self, _key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex_shader: self.vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: self.fragment_shader.clone_weak(),
+            shader_defs: Vec::new(),
+            draw_parameters: glium::DrawParameters {
+                depth: glium::Depth {
+                    test: glium::draw_parameters::DepthTest::IfLess,
+                    write: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Creates the shadow map array, recreating it when [`ShadowMapSettings`] change, and clears the
+/// depth of its layers for this frame.
+pub(crate) fn prepare_shadow_maps(
+    window: NonSend<Window>,
+    settings: Res<ShadowMapSettings>,
+    mut shadow_maps: NonSendMut<ShadowMaps>,
+) {
+    let outdated = match &shadow_maps.texture {
+        Some(texture) => texture.dimensions() != (settings.size, settings.size),
+        None => true,
+    };
+    if outdated {
+        match DepthTexture2dArray::empty(
+            window.facade(),
+            settings.size,
+            settings.size,
+            MAX_SHADOW_MAPS as u32,
+        ) {
+            Ok(texture) => shadow_maps.texture = Some(texture),
+            Err(err) => error!("Failed to create the shadow maps: {}", err),
+        }
+    }
+
+    let texture = match shadow_maps.texture() {
+        Some(texture) => texture,
+        None => return,
+    };
+    for shadow_map in 0..texture.array_size() {
+        let layer = texture.main_level().layer(shadow_map).unwrap();
+        match SimpleFrameBuffer::depth_only(window.facade(), layer) {
+            Ok(mut framebuffer) => framebuffer.clear_depth(1.0),
+            Err(err) => error!("Failed to clear the shadow map {}: {}", shadow_map, err),
+        }
+    }
+}
+
+/// Spawns a [`ShadowView`] for every shadow casting light, with a [`Shadow`] item for every batch
+/// with shadow casters.
+pub(crate) fn queue_shadows(
+    mut commands: Commands,
+    extracted_lights: Res<ExtractedLights>,
+    batches: Query<(Entity, &CorePipelineBatch)>,
+    mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
+    pipeline: Res<ShadowPipeline>,
+    mut pipelines: ResMut<SpecializedPipelines<ShadowPipeline>>,
+    draw_functions: Res<DrawFunctions<Shadow>>,
+) {
+    let draw_function = draw_functions.read().get_id::<DrawShadow>().unwrap();
+    let cached_pipeline = pipelines.specialize(&mut render_pipeline_cache, &pipeline, ());
+    for light in extracted_lights.lights.iter() {
+        let shadow_map = match light.shadow_map {
+            Some(shadow_map) => shadow_map,
+            None => continue,
+        };
+        let mut shadows = RenderPhase::<Shadow>::default();
+        for (entity, batch) in batches.iter() {
+            if batch.shadow_casters == 0 {
+                continue;
+            }
+            shadows.add(Shadow {
+                distance: batch.depth(&light.view_matrix),
+                entity,
+                draw_function,
+                pipeline: cached_pipeline,
+            });
+        }
+        commands
+            .spawn()
+            .insert(ShadowView {
+                shadow_map,
+                view_projection: light.view_projection,
+            })
+            .insert(shadows)
+            .insert(OffscreenView);
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct ShadowViewUniformBuffer {
+    u_light_pv_matrix: [[f32; 4]; 4],
+}
+
+implement_uniform_block!(ShadowViewUniformBuffer, u_light_pv_matrix);
+
+/// Renders the depth of the shadow casters of a batch into the layer of the [`ShadowView`].
+pub struct DrawShadow {
+    params: SystemState<(
+        NonSend<'static, Window>,
+        NonSend<'static, ShadowMaps>,
+        NonSend<'static, RenderPipelineCache>,
+        NonSend<'static, GpuMeshes>,
+        SQuery<Read<ShadowView>>,
+        SQuery<Read<CorePipelineBatch>>,
+    )>,
+}
+
+impl DrawShadow {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            params: SystemState::new(world),
+        }
+    }
+}
+
+impl Draw<Shadow> for DrawShadow {
+    fn draw(&mut self, world: &World, _frame: &mut TrackedFrame, view: Entity, item: &Shadow) {
+        let (window, shadow_maps, pipeline_cache, gpu_meshes, views, batches) =
+            self.params.get(world);
+        let facade = window.facade();
+
+        let pipeline = match pipeline_cache.get(item.cached_pipeline()) {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+        let texture = match shadow_maps.texture() {
+            Some(texture) => texture,
+            None => return,
+        };
+        let view = views.get(view).unwrap();
+        let layer = match texture.main_level().layer(view.shadow_map) {
+            Some(layer) => layer,
+            None => return,
+        };
+        let batch = batches.get(item.entity()).unwrap();
+        let gpu_mesh = match gpu_meshes.get(&batch.mesh) {
+            Some(gpu_mesh) => gpu_mesh,
+            None => return,
+        };
+
+        let mut framebuffer = SimpleFrameBuffer::depth_only(facade, layer).unwrap();
+        let view_uniform_buffer = glium::uniforms::UniformBuffer::new(
+            facade,
+            ShadowViewUniformBuffer {
+                u_light_pv_matrix: view.view_projection.to_cols_array_2d(),
+            },
+        )
+        .unwrap();
+        let uniforms = glium::uniform! {
+            shadow_view: &view_uniform_buffer,
+        };
+
+        let instance_buffer =
+            glium::VertexBuffer::new(facade, batch.shadow_caster_instances()).unwrap();
+        framebuffer
+            .draw(
+                (
+                    &gpu_mesh.vertex_buffer,
+                    instance_buffer.per_instance().unwrap(),
+                ),
+                &gpu_mesh.index_buffer,
+                &pipeline.program,
+                &uniforms,
+                &pipeline.parameters,
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use arara_asset::{AssetServer, Handle};
+use arara_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SQuery},
+        SystemState,
+    },
+};
+use arara_render::{
+    Cubemap, Draw, DrawFunctions, ExtractedView, GpuCubemaps, RenderPhase, RenderPipelineCache,
+    RenderPipelineDescriptor, Shader, SpecializedPipeline, SpecializedPipelines, TrackedFrame,
+};
+use glium::{
+    index::{NoIndices, PrimitiveType},
+    texture::Cubemap as GliumCubemap,
+    uniforms::{
+        AsUniformValue, MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction,
+        UniformValue, Uniforms,
+    },
+    vertex::EmptyVertexAttributes,
+};
+
+use crate::Skybox3D;
+
+/// Draws a [`Cubemap`] behind everything the camera sees, instead of the
+/// [`ClearColor`](arara_render::ClearColor).
+///
+/// The materials of a [`MaterialPlugin`](crate::MaterialPlugin) can sample it as an environment
+/// map for reflections, see [`Material`](crate::Material).
+#[derive(Component, Debug, Clone)]
+pub struct Skybox {
+    pub cubemap: Handle<Cubemap>,
+}
+
+/// A linearly filtered sampler of `cubemap`, as expected by a `samplerCube`.
+pub fn cubemap_sampler(cubemap: &GliumCubemap) -> Sampler<'_, GliumCubemap> {
+    cubemap
+        .sampled()
+        .magnify_filter(MagnifySamplerFilter::Linear)
+        .minify_filter(MinifySamplerFilter::Linear)
+        .wrap_function(SamplerWrapFunction::Clamp)
+}
+
+pub struct SkyboxPipeline {
+    pub vertex_shader: Handle<Shader>,
+    pub fragment_shader: Handle<Shader>,
+}
+
+impl FromWorld for SkyboxPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            vertex_shader: asset_server.load("shaders/skybox.vert"),
+            fragment_shader: asset_server.load("shaders/skybox.frag"),
+        }
+    }
+}
+
+impl SpecializedPipeline for SkyboxPipeline {
+    type Key = ();
+
+    fn specialize(&self, _key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex_shader: self.vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: self.fragment_shader.clone_weak(),
+            shader_defs: Vec::new(),
+            draw_parameters: glium::DrawParameters {
+                // drawn at the far plane, only where no opaque mesh was drawn
+                depth: glium::Depth {
+                    test: glium::draw_parameters::DepthTest::IfLessOrEqual,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+pub(crate) fn queue_skyboxes(
+    mut views: Query<(&Skybox, &mut RenderPhase<Skybox3D>), With<ExtractedView>>,
+    gpu_cubemaps: NonSend<GpuCubemaps>,
+    mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
+    pipeline: Res<SkyboxPipeline>,
+    mut pipelines: ResMut<SpecializedPipelines<SkyboxPipeline>>,
+    draw_functions: Res<DrawFunctions<Skybox3D>>,
+) {
+    let draw_function = draw_functions.read().get_id::<DrawSkybox>().unwrap();
+    for (skybox, mut phase) in views.iter_mut() {
+        if !gpu_cubemaps.contains(&skybox.cubemap) {
+            continue;
+        }
+        phase.add(Skybox3D {
+            draw_function,
+            pipeline: pipelines.specialize(&mut render_pipeline_cache, &pipeline, ()),
+        });
+    }
+}
+
+pub struct DrawSkybox {
+    params: SystemState<(
+        NonSend<'static, RenderPipelineCache>,
+        NonSend<'static, GpuCubemaps>,
+        SQuery<(Read<ExtractedView>, Read<Skybox>)>,
+    )>,
+}
+
+impl DrawSkybox {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            params: SystemState::new(world),
+        }
+    }
+}
+
+impl Draw<Skybox3D> for DrawSkybox {
+    fn draw(&mut self, world: &World, frame: &mut TrackedFrame, view: Entity, item: &Skybox3D) {
+        let (pipeline_cache, gpu_cubemaps, views) = self.params.get(world);
+        let (view, skybox) = match views.get(view) {
+            Ok(view) => view,
+            Err(_) => return,
+        };
+        let pipeline = match pipeline_cache.get(item.pipeline) {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+        let cubemap = match gpu_cubemaps.get(&skybox.cubemap) {
+            Some(cubemap) => cubemap,
+            None => return,
+        };
+
+        let uniforms = glium::uniform! {
+            u_inverse_pv_matrix: view.pv_matrix.inverse().to_cols_array_2d(),
+            u_camera_position: view.position.to_array(),
+            u_skybox: cubemap_sampler(cubemap),
+        };
+        // a triangle covering the whole view, the vertex shader computes its vertices
+        frame
+            .draw(
+                EmptyVertexAttributes { len: 3 },
+                NoIndices(PrimitiveType::TrianglesList),
+                &pipeline.program,
+                &uniforms,
+                &pipeline.parameters,
+            )
+            .unwrap();
+    }
+}
+
+/// The `u_environment_map` sampler of the materials, when the view has a loaded [`Skybox`].
+pub struct EnvironmentMapUniform<'a>(pub Option<Sampler<'a, GliumCubemap>>);
+
+impl Uniforms for EnvironmentMapUniform<'_> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        if let Some(sampler) = &self.0 {
+            output("u_environment_map", sampler.as_uniform_value());
+        }
+    }
+}
@@ -23,7 +23,7 @@ pub fn parse_attrs(ast: &DeriveInput, attr_name: Symbol) -> syn::Result<Vec<syn:
     let mut list = Vec::new();
     for attr in ast.attrs.iter().filter(|a| a.path == attr_name) {
         match attr.parse_meta()? {
-            syn::Meta::List(meta) => list.extend(meta.nested.into_iter()),
+            syn::Meta::List(meta) => list.extend(meta.nested),
             other => {
                 return Err(syn::Error::new_spanned(
                     other,
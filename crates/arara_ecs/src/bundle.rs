@@ -72,7 +72,7 @@ use std::{any::TypeId, collections::HashMap};
 /// # Safety
 ///
 /// - [`Bundle::component_ids`] must return the [`ComponentId`] for each component type in the
-/// bundle, in the _exact_ order that [`Bundle::get_components`] is called.
+///   bundle, in the _exact_ order that [`Bundle::get_components`] is called.
 /// - [`Bundle::from_components`] must call `func` exactly once for each [`ComponentId`] returned by
 ///   [`Bundle::component_ids`].
 pub unsafe trait Bundle: Send + Sync + 'static {
@@ -478,7 +478,7 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
                 // redundant copies
                 let move_result = self
                     .table
-                    .move_to_superset_unchecked(result.table_row, *new_table);
+                    .move_to_superset_unchecked(result.table_row, new_table);
                 let new_location = new_archetype.allocate(entity, move_result.new_row);
                 self.entities.meta[entity.id as usize].location = new_location;
 
@@ -25,7 +25,6 @@
 mod map_entities;
 mod serde;
 
-pub use self::serde::*;
 pub use map_entities::*;
 
 use crate::{archetype::ArchetypeId, storage::SparseSetIndex};
@@ -89,7 +88,7 @@ impl Entity {
     ///
     /// ```no_run
     /// # use arara_ecs::{prelude::*, component::*};
-    /// # use arara_reflect::Reflect;
+    /// # use bevy_reflect::Reflect;
     /// #[derive(Reflect, Component)]
     /// #[reflect(Component)]
     /// pub struct MyStruct {
@@ -246,7 +245,7 @@ impl Entities {
     /// Reserve entity IDs concurrently.
     ///
     /// Storage for entity generation and location is lazily allocated by calling `flush`.
-    pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator {
+    pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator<'_> {
         // Use one atomic subtract to grab a range of new IDs. The range might be
         // entirely nonnegative, meaning all IDs come from the freelist, or entirely
         // negative, meaning they are all new IDs to allocate, or a mix of both.
@@ -440,7 +439,7 @@ impl Entities {
     // not reallocated since the generation is incremented in `free`
     pub fn contains(&self, entity: Entity) -> bool {
         self.resolve_from_id(entity.id())
-            .map_or(false, |e| e.generation() == entity.generation)
+            .is_some_and(|e| e.generation() == entity.generation)
     }
 
     pub fn clear(&mut self) {
@@ -481,7 +480,7 @@ impl Entities {
             // If this entity was manually created, then free_cursor might be positive
             // Returning None handles that case correctly
             let num_pending = usize::try_from(-free_cursor).ok()?;
-            (idu < self.meta.len() + num_pending).then(|| Entity { generation: 0, id })
+            (idu < self.meta.len() + num_pending).then_some(Entity { generation: 0, id })
         }
     }
 
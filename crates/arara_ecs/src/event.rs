@@ -110,7 +110,7 @@ enum State {
 /// - [`EventReader`]s that read at least once per update will never drop events.
 /// - [`EventReader`]s that read once within two updates might still receive some events
 /// - [`EventReader`]s that read after two updates are guaranteed to drop all events that occurred
-/// before those updates.
+///   before those updates.
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
@@ -212,43 +212,35 @@ fn internal_event_reader<'a, T>(
 ) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
     // if the reader has seen some of the events in a buffer, find the proper index offset.
     // otherwise read all events in the buffer
-    let a_index = if *last_event_count > events.a_start_event_count {
-        *last_event_count - events.a_start_event_count
-    } else {
-        0
-    };
-    let b_index = if *last_event_count > events.b_start_event_count {
-        *last_event_count - events.b_start_event_count
-    } else {
-        0
-    };
+    let a_index = (*last_event_count).saturating_sub(events.a_start_event_count);
+    let b_index = (*last_event_count).saturating_sub(events.b_start_event_count);
     *last_event_count = events.event_count;
     match events.state {
         State::A => events
             .events_b
             .get(b_index..)
-            .unwrap_or_else(|| &[])
+            .unwrap_or(&[])
             .iter()
             .map(map_instance_event_with_id)
             .chain(
                 events
                     .events_a
                     .get(a_index..)
-                    .unwrap_or_else(|| &[])
+                    .unwrap_or(&[])
                     .iter()
                     .map(map_instance_event_with_id),
             ),
         State::B => events
             .events_a
             .get(a_index..)
-            .unwrap_or_else(|| &[])
+            .unwrap_or(&[])
             .iter()
             .map(map_instance_event_with_id)
             .chain(
                 events
                     .events_b
                     .get(b_index..)
-                    .unwrap_or_else(|| &[])
+                    .unwrap_or(&[])
                     .iter()
                     .map(map_instance_event_with_id),
             ),
@@ -264,6 +256,8 @@ impl<'w, 's, T: Resource> EventReader<'w, 's, T> {
     }
 
     /// Like [`iter`](Self::iter), except also returning the [`EventId`] of the events.
+    // the map only traces the events with the `event_trace` feature
+    #[cfg_attr(not(feature = "event_trace"), allow(clippy::map_identity))]
     pub fn iter_with_id(&mut self) -> impl DoubleEndedIterator<Item = (&T, EventId<T>)> {
         internal_event_reader(&mut self.last_event_count.0, &self.events).map(|(event, id)| {
             #[cfg(feature = "event_trace")]
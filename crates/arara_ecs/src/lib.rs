@@ -44,7 +44,7 @@ pub use ecs_macros::all_tuples;
 
 #[cfg(test)]
 mod tests {
-    use crate as bevy_ecs;
+    use crate as arara_ecs;
     use crate::{
         bundle::Bundle,
         component::{Component, ComponentId},
@@ -88,6 +88,7 @@ mod tests {
 
     #[derive(Component, Clone, Debug)]
     #[component(storage = "SparseSet")]
+    #[allow(dead_code)]
     struct DropCkSparse(DropCk);
 
     #[derive(Component, Copy, Clone, PartialEq, Eq, Debug)]
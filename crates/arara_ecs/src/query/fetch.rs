@@ -28,12 +28,12 @@ use std::{
 /// - `&C`: Queries immutably for the component `C`
 /// - `&mut C`: Queries mutably for the component `C`
 /// - `Option<WQ>`: Queries the inner [`WorldQuery`] `WQ` but instead of discarding the entity if the world
-///     query fails it returns [`None`]. See [`Query`](crate::system::Query).
+///   query fails it returns [`None`]. See [`Query`](crate::system::Query).
 /// - `(WQ1, WQ2, ...)`: Queries all contained world queries allowing to query for more than one thing.
-///     This is the `And` operator for filters. See [`Or`].
+///   This is the `And` operator for filters. See [`Or`].
 /// - `ChangeTrackers<C>`: See the docs of [`ChangeTrackers`].
 /// - [`Entity`]: Using the entity type as a world query will grant access to the entity that is
-///     being queried for. See [`Entity`].
+///   being queried for. See [`Entity`].
 ///
 /// arara also offers a few filters like [`Added`](crate::query::Added), [`Changed`](crate::query::Changed),
 /// [`With`](crate::query::With), [`Without`](crate::query::Without) and [`Or`].
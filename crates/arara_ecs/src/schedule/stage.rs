@@ -796,7 +796,7 @@ impl Stage for SystemStage {
             // Evaluate system run criteria.
             for index in 0..self.run_criteria.len() {
                 let (run_criteria, tail) = self.run_criteria.split_at_mut(index);
-                let mut criteria = &mut tail[0];
+                let criteria = &mut tail[0];
                 criteria.update_archetypes(world);
                 match &mut criteria.inner {
                     RunCriteriaInner::Single(system) => criteria.should_run = system.run((), world),
@@ -953,7 +953,7 @@ mod tests {
         world::World,
     };
 
-    use crate as bevy_ecs;
+    use crate as arara_ecs;
     use crate::component::Component;
     #[derive(Component)]
     struct W<T>(T);
@@ -2071,7 +2071,7 @@ mod tests {
 
     #[test]
     fn change_query_wrapover() {
-        use crate::{self as bevy_ecs, component::Component};
+        use crate::{self as arara_ecs, component::Component};
 
         #[derive(Component)]
         struct C;
@@ -2109,13 +2109,13 @@ mod tests {
 
     #[test]
     fn run_criteria_with_query() {
-        use crate::{self as bevy_ecs, component::Component};
+        use crate::{self as arara_ecs, component::Component};
 
         #[derive(Component)]
         struct Foo;
 
         fn even_number_of_entities_critiera(query: Query<&Foo>) -> ShouldRun {
-            if query.iter().len() % 2 == 0 {
+            if query.iter().len().is_multiple_of(2) {
                 ShouldRun::Yes
             } else {
                 ShouldRun::No
@@ -2148,13 +2148,13 @@ mod tests {
 
     #[test]
     fn stage_run_criteria_with_query() {
-        use crate::{self as bevy_ecs, component::Component};
+        use crate::{self as arara_ecs, component::Component};
 
         #[derive(Component)]
         struct Foo;
 
         fn even_number_of_entities_critiera(query: Query<&Foo>) -> ShouldRun {
-            if query.iter().len() % 2 == 0 {
+            if query.iter().len().is_multiple_of(2) {
                 ShouldRun::Yes
             } else {
                 ShouldRun::No
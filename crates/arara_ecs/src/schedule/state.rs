@@ -162,7 +162,7 @@ where
             state
                 .transition
                 .as_ref()
-                .map_or(false, |transition| match transition {
+                .is_some_and(|transition| match transition {
                     StateTransition::Entering(_, entering) => entering == pred.as_ref().unwrap(),
                     StateTransition::Startup => {
                         state.stack.last().unwrap() == pred.as_ref().unwrap()
@@ -181,7 +181,7 @@ where
             state
                 .transition
                 .as_ref()
-                .map_or(false, |transition| match transition {
+                .is_some_and(|transition| match transition {
                     StateTransition::ExitingToResume(exiting, _)
                     | StateTransition::ExitingFull(exiting, _) => exiting == pred.as_ref().unwrap(),
                     _ => false,
@@ -198,7 +198,7 @@ where
             state
                 .transition
                 .as_ref()
-                .map_or(false, |transition| match transition {
+                .is_some_and(|transition| match transition {
                     StateTransition::Pausing(pausing, _) => pausing == pred.as_ref().unwrap(),
                     _ => false,
                 })
@@ -214,7 +214,7 @@ where
             state
                 .transition
                 .as_ref()
-                .map_or(false, |transition| match transition {
+                .is_some_and(|transition| match transition {
                     StateTransition::Resuming(_, resuming) => resuming == pred.as_ref().unwrap(),
                     _ => false,
                 })
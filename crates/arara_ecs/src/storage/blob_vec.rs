@@ -91,11 +91,11 @@ impl BlobVec {
     /// # Safety
     /// - index must be in bounds
     /// - the memory in the `BlobVec` starting at index `index`, of a size matching this `BlobVec`'s
-    /// `item_layout`, must have been previously allocated, but not initialized yet
+    ///   `item_layout`, must have been previously allocated, but not initialized yet
     /// - the memory at `*value` must be previously initialized with an item matching this
-    /// `BlobVec`'s `item_layout`
+    ///   `BlobVec`'s `item_layout`
     /// - the item that was stored in `*value` is left logically uninitialised/moved out of after
-    /// calling this function, and as such should not be used or dropped by the caller.
+    ///   calling this function, and as such should not be used or dropped by the caller.
     #[inline]
     pub unsafe fn initialize_unchecked(&mut self, index: usize, value: *mut u8) {
         debug_assert!(index < self.len());
@@ -106,12 +106,12 @@ impl BlobVec {
     /// # Safety
     /// - index must be in-bounds
     /// - the memory in the [`BlobVec`] starting at index `index`, of a size matching this
-    /// [`BlobVec`]'s `item_layout`, must have been previously initialized with an item matching
-    /// this [`BlobVec`]'s `item_layout`
+    ///   [`BlobVec`]'s `item_layout`, must have been previously initialized with an item matching
+    ///   this [`BlobVec`]'s `item_layout`
     /// - the memory at `*value` must also be previously initialized with an item matching this
-    /// [`BlobVec`]'s `item_layout`
+    ///   [`BlobVec`]'s `item_layout`
     /// - the item that was stored in `*value` is left logically uninitialised/moved out of after
-    /// calling this function, and as such should not be used or dropped by the caller.
+    ///   calling this function, and as such should not be used or dropped by the caller.
     pub unsafe fn replace_unchecked(&mut self, index: usize, value: *mut u8) {
         debug_assert!(index < self.len());
         let ptr = self.get_unchecked(index);
@@ -197,6 +197,12 @@ pub struct Table {
     entities: Vec<Entity>,
 }
 
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Table {
     pub const fn new() -> Table {
         Self {
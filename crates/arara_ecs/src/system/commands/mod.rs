@@ -177,7 +177,7 @@ impl<'w, 's> Commands<'w, 's> {
     /// struct Strength(u32);
     /// #[derive(Component)]
     /// struct Agility(u32);
-
+    ///
     /// fn example_system(mut commands: Commands) {
     ///     // Create a new, empty entity
     ///     let entity = commands.spawn().id();
@@ -739,6 +739,7 @@ mod tests {
 
     #[derive(Component)]
     #[component(storage = "SparseSet")]
+    #[allow(dead_code)]
     struct SparseDropCk(DropCk);
 
     #[derive(Component)]
@@ -126,6 +126,7 @@ mod tests {
     };
 
     #[derive(Component)]
+    #[allow(dead_code)]
     struct Foo(f32);
 
     #[test]
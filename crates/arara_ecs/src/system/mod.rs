@@ -412,7 +412,9 @@ mod tests {
         let mut world = World::default();
 
         world.insert_resource(false);
+        #[allow(dead_code)]
         struct NotSend1(std::rc::Rc<i32>);
+        #[allow(dead_code)]
         struct NotSend2(std::rc::Rc<i32>);
         world.insert_non_send(NotSend1(std::rc::Rc::new(0)));
 
@@ -435,7 +437,9 @@ mod tests {
         let mut world = World::default();
 
         world.insert_resource(false);
+        #[allow(dead_code)]
         struct NotSend1(std::rc::Rc<i32>);
+        #[allow(dead_code)]
         struct NotSend2(std::rc::Rc<i32>);
 
         world.insert_non_send(NotSend1(std::rc::Rc::new(1)));
@@ -1003,7 +1003,7 @@ impl<'w, 's, T: 'static> SystemParamFetch<'w, 's> for OptionNonSendMutState<T> {
     }
 }
 
-impl<'a> SystemParam for &'a Archetypes {
+impl SystemParam for &Archetypes {
     type Fetch = ArchetypesState;
 }
 
@@ -1038,7 +1038,7 @@ impl<'w, 's> SystemParamFetch<'w, 's> for ArchetypesState {
     }
 }
 
-impl<'a> SystemParam for &'a Components {
+impl SystemParam for &Components {
     type Fetch = ComponentsState;
 }
 
@@ -1073,7 +1073,7 @@ impl<'w, 's> SystemParamFetch<'w, 's> for ComponentsState {
     }
 }
 
-impl<'a> SystemParam for &'a Entities {
+impl SystemParam for &Entities {
     type Fetch = EntitiesState;
 }
 
@@ -1108,7 +1108,7 @@ impl<'w, 's> SystemParamFetch<'w, 's> for EntitiesState {
     }
 }
 
-impl<'a> SystemParam for &'a Bundles {
+impl SystemParam for &Bundles {
     type Fetch = BundlesState;
 }
 
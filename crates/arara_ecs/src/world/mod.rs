@@ -196,7 +196,7 @@ impl World {
     /// assert_eq!(position.x, 0.0);
     /// ```
     #[inline]
-    pub fn entity(&self, entity: Entity) -> EntityRef {
+    pub fn entity(&self, entity: Entity) -> EntityRef<'_> {
         // Lazily evaluate panic!() via unwrap_or_else() to avoid allocation unless failure
         self.get_entity(entity)
             .unwrap_or_else(|| panic!("Entity {:?} does not exist", entity))
@@ -224,7 +224,7 @@ impl World {
     /// position.x = 1.0;
     /// ```
     #[inline]
-    pub fn entity_mut(&mut self, entity: Entity) -> EntityMut {
+    pub fn entity_mut(&mut self, entity: Entity) -> EntityMut<'_> {
         // Lazily evaluate panic!() via unwrap_or_else() to avoid allocation unless failure
         self.get_entity_mut(entity)
             .unwrap_or_else(|| panic!("Entity {:?} does not exist", entity))
@@ -238,7 +238,7 @@ impl World {
     /// This method should generally only be used for sharing entities across apps, and only when they have a
     /// scheme worked out to share an ID space (which doesn't happen by default).
     #[inline]
-    pub fn get_or_spawn(&mut self, entity: Entity) -> Option<EntityMut> {
+    pub fn get_or_spawn(&mut self, entity: Entity) -> Option<EntityMut<'_>> {
         self.flush();
         match self.entities.alloc_at_without_replacement(entity) {
             AllocAtWithoutReplacement::Exists(location) => {
@@ -276,7 +276,7 @@ impl World {
     /// assert_eq!(position.x, 0.0);
     /// ```
     #[inline]
-    pub fn get_entity(&self, entity: Entity) -> Option<EntityRef> {
+    pub fn get_entity(&self, entity: Entity) -> Option<EntityRef<'_>> {
         let location = self.entities.get(entity)?;
         Some(EntityRef::new(self, entity, location))
     }
@@ -304,7 +304,7 @@ impl World {
     /// position.x = 1.0;
     /// ```
     #[inline]
-    pub fn get_entity_mut(&mut self, entity: Entity) -> Option<EntityMut> {
+    pub fn get_entity_mut(&mut self, entity: Entity) -> Option<EntityMut<'_>> {
         let location = self.entities.get(entity)?;
         // SAFE: `entity` exists and `location` is that entity's location
         Some(unsafe { EntityMut::new(self, entity, location) })
@@ -335,7 +335,7 @@ impl World {
     /// let position = world.entity(entity).get::<Position>().unwrap();
     /// assert_eq!(position.x, 0.0);
     /// ```
-    pub fn spawn(&mut self) -> EntityMut {
+    pub fn spawn(&mut self) -> EntityMut<'_> {
         self.flush();
         let entity = self.entities.alloc();
         // SAFE: entity was just allocated
@@ -344,7 +344,7 @@ impl World {
 
     /// # Safety
     /// must be called on an entity that was just allocated
-    unsafe fn spawn_at_internal(&mut self, entity: Entity) -> EntityMut {
+    unsafe fn spawn_at_internal(&mut self, entity: Entity) -> EntityMut<'_> {
         let archetype = self.archetypes.empty_mut();
         // PERF: consider avoiding allocating entities in the empty archetype unless needed
         let table_row = self.storages.tables[archetype.table_id()].allocate(entity);
@@ -431,7 +431,7 @@ impl World {
     /// position.x = 1.0;
     /// ```
     #[inline]
-    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<T>> {
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<'_, T>> {
         self.get_entity_mut(entity)?.get_mut()
     }
 
@@ -18,18 +18,11 @@ pub struct WorldCell<'w> {
     pub(crate) access: Rc<RefCell<ArchetypeComponentAccess>>,
 }
 
+#[derive(Default)]
 pub(crate) struct ArchetypeComponentAccess {
     access: SparseSet<ArchetypeComponentId, usize>,
 }
 
-impl Default for ArchetypeComponentAccess {
-    fn default() -> Self {
-        Self {
-            access: SparseSet::new(),
-        }
-    }
-}
-
 const UNIQUE_ACCESS: usize = 0;
 const BASE_ACCESS: usize = 1;
 impl ArchetypeComponentAccess {
@@ -74,7 +67,7 @@ impl<'w> Drop for WorldCell<'w> {
     fn drop(&mut self) {
         let mut access = self.access.borrow_mut();
         // give world ArchetypeComponentAccess back to reuse allocations
-        let _ = std::mem::swap(&mut self.world.archetype_component_access, &mut *access);
+        std::mem::swap(&mut self.world.archetype_component_access, &mut *access);
     }
 }
 
@@ -157,7 +150,7 @@ impl<'w, T> Deref for WorldBorrowMut<'w, T> {
 
 impl<'w, T> DerefMut for WorldBorrowMut<'w, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut *self.value
+        &mut self.value
     }
 }
 
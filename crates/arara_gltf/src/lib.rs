@@ -5,6 +5,7 @@ pub use loader::*;
 
 use arara_app::prelude::*;
 use arara_asset::{AddAsset, Handle};
+use arara_core_pipeline::StandardMaterial;
 use arara_render::Mesh;
 use bevy_reflect::TypeUuid;
 
@@ -30,10 +31,10 @@ pub struct Gltf {
     // pub named_scenes: HashMap<String, Handle<Scene>>,
     pub meshes: Vec<Handle<GltfMesh>>,
     pub named_meshes: HashMap<String, Handle<GltfMesh>>,
-    // pub materials: Vec<Handle<StandardMaterial>>,
-    // pub named_materials: HashMap<String, Handle<StandardMaterial>>,
-    // pub nodes: Vec<Handle<GltfNode>>,
-    // pub named_nodes: HashMap<String, Handle<GltfNode>>,
+    pub materials: Vec<Handle<StandardMaterial>>,
+    pub named_materials: HashMap<String, Handle<StandardMaterial>>,
+    pub nodes: Vec<Handle<GltfNode>>,
+    pub named_nodes: HashMap<String, Handle<GltfNode>>,
     // pub default_scene: Option<Handle<Scene>>,
 }
 
@@ -59,5 +60,5 @@ pub struct GltfMesh {
 #[uuid = "cbfca302-82fd-41cb-af77-cab6b3d50af1"]
 pub struct GltfPrimitive {
     pub mesh: Handle<Mesh>,
-    // pub material: Option<Handle<StandardMaterial>>,
+    pub material: Option<Handle<StandardMaterial>>,
 }
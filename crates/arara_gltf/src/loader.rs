@@ -1,14 +1,25 @@
 use anyhow::Result;
-use arara_asset::{AssetIoError, AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use arara_asset::{
+    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+};
+use arara_core_pipeline::{AlphaMode, StandardMaterial};
 use arara_logger::warn;
-use arara_render::{Image, ImageType, Mesh, TextureError, Vertex};
-use arara_utils::HashMap;
-use glium::index::PrimitiveType;
-use gltf::{mesh::Mode, Primitive};
+use arara_render::{
+    Color, FilterMode, Image, ImageSampler, ImageType, Mesh, PrimitiveTopology, SamplerDescriptor,
+    TextureError, WrapMode,
+};
+use arara_transform::prelude::Transform;
+use arara_utils::{HashMap, HashSet};
+use glam::{Quat, Vec3};
+use gltf::{
+    mesh::Mode,
+    texture::{MagFilter, MinFilter, WrappingMode},
+    Primitive,
+};
 use std::path::Path;
 use thiserror::Error;
 
-use crate::Gltf;
+use crate::{Gltf, GltfNode};
 
 /// An error that occurs when loading a glTF file.
 #[derive(Error, Debug)]
@@ -57,6 +68,16 @@ async fn load_gltf<'a, 'b>(
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
+    let mut materials = vec![];
+    let mut named_materials = HashMap::default();
+    for material in gltf.materials() {
+        let handle = load_material(&material, load_context);
+        if let Some(name) = material.name() {
+            named_materials.insert(name.to_string(), handle.clone());
+        }
+        materials.push(handle);
+    }
+
     let mut meshes = vec![];
     let mut named_meshes = HashMap::default();
     for mesh in gltf.meshes() {
@@ -64,30 +85,82 @@ async fn load_gltf<'a, 'b>(
         for primitive in mesh.primitives() {
             let primitive_label = primitive_label(&mesh, &primitive);
             let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
-            let _primitive_topology = get_primitive_topology(primitive.mode())?;
-
-            let positions = reader.read_positions().unwrap();
+            let primitive_topology = get_primitive_topology(primitive.mode())?;
 
-            let normals = reader.read_normals().unwrap();
-
-            let tex_coords = reader.read_tex_coords(0).unwrap().into_f32().into_iter();
-
-            let mut vertices = Vec::with_capacity(positions.len());
-            for ((position, normal), tex_coord) in positions.zip(normals).zip(tex_coords) {
-                vertices.push(Vertex {
-                    position,
-                    normal,
-                    tex_coord,
-                });
+            let mut mesh = Mesh::new(primitive_topology);
+            if let Some(positions) = reader.read_positions() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions.collect::<Vec<_>>());
+            }
+            if let Some(normals) = reader.read_normals() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals.collect::<Vec<_>>());
+            }
+            if let Some(tex_coords) = reader.read_tex_coords(0) {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_UV_0,
+                    tex_coords.into_f32().collect::<Vec<_>>(),
+                );
+            }
+            if let Some(tex_coords) = reader.read_tex_coords(1) {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_UV_1,
+                    tex_coords.into_f32().collect::<Vec<_>>(),
+                );
+            }
+            if let Some(colors) = reader.read_colors(0) {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_COLOR,
+                    colors.into_rgba_f32().collect::<Vec<_>>(),
+                );
+            }
+            if let Some(tangents) = reader.read_tangents() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents.collect::<Vec<_>>());
+            }
+            if let Some(joints) = reader.read_joints(0) {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_JOINT_INDEX,
+                    joints.into_u16().collect::<Vec<_>>(),
+                );
+            }
+            if let Some(weights) = reader.read_weights(0) {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_JOINT_WEIGHT,
+                    weights.into_f32().collect::<Vec<_>>(),
+                );
             }
 
-            let indices = reader.read_indices().unwrap().into_u32().collect();
+            mesh.indices = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..mesh.count_vertices() as u32).collect(),
+            };
 
-            let mesh = Mesh { vertices, indices };
+            // glTF asks for flat normals when the triangles are missing them, lines and
+            // points have no faces
+            if !mesh.contains_attribute(Mesh::ATTRIBUTE_NORMAL) && primitive_topology.is_triangles()
+            {
+                mesh.compute_flat_normals();
+            }
+            if !mesh.contains_attribute(Mesh::ATTRIBUTE_TANGENT)
+                && primitive_topology.is_triangles()
+                && primitive.material().normal_texture().is_some()
+            {
+                if let Err(err) = mesh.generate_tangents() {
+                    warn!(
+                        "Failed to generate the tangents of {}: {}",
+                        primitive_label, err
+                    );
+                }
+            }
 
             let mesh_handle =
                 load_context.set_labeled_asset(&primitive_label, LoadedAsset::new(mesh));
-            primitives.push(super::GltfPrimitive { mesh: mesh_handle });
+            let material = primitive
+                .material()
+                .index()
+                .map(|index| materials[index].clone());
+            primitives.push(super::GltfPrimitive {
+                mesh: mesh_handle,
+                material,
+            });
         }
         let handle = load_context.set_labeled_asset(
             &mesh_label(&mesh),
@@ -99,9 +172,53 @@ async fn load_gltf<'a, 'b>(
         meshes.push(handle);
     }
 
+    let mut nodes_intermediate = vec![];
+    let mut named_nodes_intermediate = HashMap::default();
+    for node in gltf.nodes() {
+        let (translation, rotation, scale) = node.transform().decomposed();
+        nodes_intermediate.push((
+            node_label(&node),
+            GltfNode {
+                children: vec![],
+                mesh: node
+                    .mesh()
+                    .and_then(|mesh| meshes.get(mesh.index()).cloned()),
+                transform: Transform {
+                    translation: Vec3::from(translation),
+                    rotation: Quat::from_array(rotation),
+                    scale: Vec3::from(scale),
+                },
+            },
+            node.children().map(|child| child.index()).collect(),
+        ));
+        if let Some(name) = node.name() {
+            named_nodes_intermediate.insert(name, node.index());
+        }
+    }
+    let nodes = resolve_node_hierarchy(nodes_intermediate, load_context.path())
+        .into_iter()
+        .map(|(label, node)| load_context.set_labeled_asset(&label, LoadedAsset::new(node)))
+        .collect::<Vec<Handle<GltfNode>>>();
+    let named_nodes = named_nodes_intermediate
+        .into_iter()
+        .filter_map(|(name, index)| {
+            nodes
+                .get(index)
+                .map(|handle| (name.to_string(), handle.clone()))
+        })
+        .collect();
+
+    // normal maps hold directions rather than colors
+    let linear_textures: HashSet<usize> = gltf
+        .materials()
+        .filter_map(|material| material.normal_texture())
+        .map(|info| info.texture().index())
+        .collect();
+
     if gltf.textures().len() == 1 {
         for gltf_texture in gltf.textures() {
-            let (texture, label) = load_texture(gltf_texture, &buffer_data, load_context).await?;
+            let (texture, label) =
+                load_texture(gltf_texture, &buffer_data, &linear_textures, load_context).await?;
             load_context.set_labeled_asset(&label, LoadedAsset::new(texture));
         }
     } else {
@@ -111,8 +228,9 @@ async fn load_gltf<'a, 'b>(
                 gltf.textures().for_each(|gltf_texture| {
                     let load_context: &LoadContext = load_context;
                     let buffer_data = &buffer_data;
+                    let linear_textures = &linear_textures;
                     scope.spawn(async move {
-                        load_texture(gltf_texture, buffer_data, load_context).await
+                        load_texture(gltf_texture, buffer_data, linear_textures, load_context).await
                     });
                 });
             })
@@ -131,21 +249,76 @@ async fn load_gltf<'a, 'b>(
     load_context.set_default_asset(LoadedAsset::new(Gltf {
         meshes,
         named_meshes,
+        materials,
+        named_materials,
+        nodes,
+        named_nodes,
     }));
 
     Ok(())
 }
 
+/// Loads a glTF material as a [`StandardMaterial`] and returns its handle.
+fn load_material(
+    material: &gltf::Material,
+    load_context: &mut LoadContext,
+) -> Handle<StandardMaterial> {
+    let material_label = material_label(material);
+    let mut dependencies = Vec::new();
+    let mut texture_handle = |texture: gltf::Texture| -> Handle<Image> {
+        let label = texture_label(&texture);
+        let path = AssetPath::new(load_context.path().to_path_buf(), Some(label));
+        let handle = load_context.get_handle(path.clone());
+        dependencies.push(path);
+        handle
+    };
+
+    let pbr = material.pbr_metallic_roughness();
+    let base_color_texture = pbr
+        .base_color_texture()
+        .map(|info| texture_handle(info.texture()));
+    let normal_map_texture = material
+        .normal_texture()
+        .map(|info| texture_handle(info.texture()));
+
+    let [r, g, b, a] = pbr.base_color_factor();
+    let [emissive_r, emissive_g, emissive_b] = material.emissive_factor();
+    // Rough surfaces get wide and dim highlights, smooth ones small and sharp highlights.
+    let roughness = pbr.roughness_factor().clamp(0.05, 1.0);
+    let standard_material = StandardMaterial {
+        base_color: Color::rgba(r, g, b, a),
+        base_color_texture,
+        specular_strength: 1.0 - roughness,
+        shininess: 2.0 / (roughness * roughness),
+        emissive: Color::rgb(emissive_r, emissive_g, emissive_b),
+        normal_map_texture,
+        alpha_mode: match material.alpha_mode() {
+            gltf::material::AlphaMode::Opaque => AlphaMode::Opaque,
+            gltf::material::AlphaMode::Mask => {
+                AlphaMode::Mask(material.alpha_cutoff().unwrap_or(0.5))
+            }
+            gltf::material::AlphaMode::Blend => AlphaMode::Blend,
+        },
+        double_sided: material.double_sided(),
+    };
+
+    load_context.set_labeled_asset(
+        &material_label,
+        LoadedAsset::new(standard_material).with_dependencies(dependencies),
+    )
+}
+
 /// Loads a glTF texture as a arara [`Image`] and returns it together with its label.
 async fn load_texture<'a>(
     gltf_texture: gltf::Texture<'a>,
     buffer_data: &[Vec<u8>],
+    linear_textures: &HashSet<usize>,
     load_context: &LoadContext<'a>,
 ) -> Result<(Image, String), GltfError> {
-    let texture = match gltf_texture.source().source() {
+    let mut texture = match gltf_texture.source().source() {
         gltf::image::Source::View { view, mime_type } => {
-            let start = view.offset() as usize;
-            let end = (view.offset() + view.length()) as usize;
+            let start = view.offset();
+            let end = view.offset() + view.length();
             let buffer = &buffer_data[view.buffer().index()][start..end];
             Image::from_buffer(buffer, ImageType::MimeType(mime_type))?
         }
@@ -174,14 +347,49 @@ async fn load_texture<'a>(
             )?
         }
     };
-    // texture.sampler_descriptor = texture_sampler(&gltf_texture);
-    // if (linear_textures).contains(&gltf_texture.index()) {
-    //     texture.texture_descriptor.format = TextureFormat::Rgba8Unorm;
-    // }
+    let mut sampler = texture_sampler(&gltf_texture);
+    sampler.srgb = !linear_textures.contains(&gltf_texture.index());
+    texture.sampler = ImageSampler::Descriptor(sampler);
 
     Ok((texture, texture_label(&gltf_texture)))
 }
 
+/// The filters and wrap modes of the sampler of a glTF texture, the unspecified filters are
+/// linear.
+fn texture_sampler(texture: &gltf::Texture) -> SamplerDescriptor {
+    let gltf_sampler = texture.sampler();
+    let (min_filter, mipmap_filter) = match gltf_sampler.min_filter() {
+        Some(MinFilter::Nearest) => (FilterMode::Nearest, None),
+        Some(MinFilter::Linear) => (FilterMode::Linear, None),
+        Some(MinFilter::NearestMipmapNearest) => (FilterMode::Nearest, Some(FilterMode::Nearest)),
+        Some(MinFilter::LinearMipmapNearest) => (FilterMode::Linear, Some(FilterMode::Nearest)),
+        Some(MinFilter::NearestMipmapLinear) => (FilterMode::Nearest, Some(FilterMode::Linear)),
+        Some(MinFilter::LinearMipmapLinear) | None => {
+            (FilterMode::Linear, Some(FilterMode::Linear))
+        }
+    };
+    SamplerDescriptor {
+        mag_filter: match gltf_sampler.mag_filter() {
+            Some(MagFilter::Nearest) => FilterMode::Nearest,
+            Some(MagFilter::Linear) | None => FilterMode::Linear,
+        },
+        min_filter,
+        mipmap_filter: mipmap_filter.unwrap_or(FilterMode::Linear),
+        wrap_u: wrap_mode(gltf_sampler.wrap_s()),
+        wrap_v: wrap_mode(gltf_sampler.wrap_t()),
+        mipmaps: mipmap_filter.is_some(),
+        ..Default::default()
+    }
+}
+
+fn wrap_mode(wrapping_mode: WrappingMode) -> WrapMode {
+    match wrapping_mode {
+        WrappingMode::ClampToEdge => WrapMode::ClampToEdge,
+        WrappingMode::MirroredRepeat => WrapMode::MirroredRepeat,
+        WrappingMode::Repeat => WrapMode::Repeat,
+    }
+}
+
 /// Returns the label for the `mesh`.
 fn mesh_label(mesh: &gltf::Mesh) -> String {
     format!("Mesh{}", mesh.index())
@@ -192,19 +400,32 @@ fn primitive_label(mesh: &gltf::Mesh, primitive: &Primitive) -> String {
     format!("Mesh{}/Primitive{}", mesh.index(), primitive.index())
 }
 
+/// Returns the label for the `material`.
+fn material_label(material: &gltf::Material) -> String {
+    if let Some(index) = material.index() {
+        format!("Material{}", index)
+    } else {
+        "MaterialDefault".to_string()
+    }
+}
+
 /// Returns the label for the `texture`.
+fn node_label(node: &gltf::Node) -> String {
+    format!("Node{}", node.index())
+}
+
 fn texture_label(texture: &gltf::Texture) -> String {
     format!("Texture{}", texture.index())
 }
 
-/// Maps the `primitive_topology` form glTF to `glium`.
-fn get_primitive_topology(mode: Mode) -> Result<PrimitiveType, GltfError> {
+/// Maps the `primitive_topology` from glTF to arara.
+fn get_primitive_topology(mode: Mode) -> Result<PrimitiveTopology, GltfError> {
     match mode {
-        Mode::Triangles => Ok(PrimitiveType::TrianglesList),
-        // Mode::TriangleStrip => Ok(PrimitiveTopology::TriangleStrip),
-        // Mode::Points => Ok(PrimitiveTopology::PointList),
-        // Mode::Lines => Ok(PrimitiveTopology::LineList),
-        // Mode::LineStrip => Ok(PrimitiveTopology::LineStrip),
+        Mode::Triangles => Ok(PrimitiveTopology::TriangleList),
+        Mode::TriangleStrip => Ok(PrimitiveTopology::TriangleStrip),
+        Mode::Points => Ok(PrimitiveTopology::PointList),
+        Mode::Lines => Ok(PrimitiveTopology::LineList),
+        Mode::LineStrip => Ok(PrimitiveTopology::LineStrip),
         mode => Err(GltfError::UnsupportedPrimitive { mode }),
     }
 }
@@ -289,10 +510,59 @@ impl<'a> DataUri<'a> {
     }
 }
 
+/// Moves the children into their parent nodes, the nodes of a cycle or with a missing child are
+/// dropped.
+fn resolve_node_hierarchy(
+    nodes_intermediate: Vec<(String, GltfNode, Vec<usize>)>,
+    asset_path: &Path,
+) -> Vec<(String, GltfNode)> {
+    let mut max_steps = nodes_intermediate.len();
+    let mut nodes_step = nodes_intermediate
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, node, children))| (i, label, node, children))
+        .collect::<Vec<_>>();
+    let mut nodes = HashMap::<usize, (String, GltfNode)>::default();
+    while max_steps > 0 && !nodes_step.is_empty() {
+        // a node is resolved once all of its children are
+        if let Some((index, label, node, _)) = nodes_step
+            .iter()
+            .find(|(_, _, _, children)| children.is_empty())
+            .cloned()
+        {
+            for (_, _, parent, children) in nodes_step.iter_mut() {
+                if let Some(i) = children.iter().position(|child| *child == index) {
+                    children.remove(i);
+                    parent.children.push(node.clone());
+                }
+            }
+            nodes.insert(index, (label, node));
+            nodes_step.retain(|(i, _, _, _)| *i != index);
+        } else {
+            let labels = nodes_step
+                .iter()
+                .map(|(_, label, _, _)| label.as_str())
+                .collect::<Vec<_>>();
+            warn!(
+                "Cycle or missing node in the node hierarchy of {:?}, dropping {}",
+                asset_path,
+                labels.join(", ")
+            );
+            break;
+        }
+        max_steps -= 1;
+    }
+
+    let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+    nodes.sort_by_key(|(i, _)| *i);
+    nodes.into_iter().map(|(_, resolved)| resolved).collect()
+}
+
 #[cfg(test)]
 mod test {
     use std::path::PathBuf;
 
+    use super::resolve_node_hierarchy;
     use crate::GltfNode;
 
     impl GltfNode {
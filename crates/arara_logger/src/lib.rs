@@ -17,7 +17,7 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 /// Adds logging to Apps. This plugin is part of the `DefaultPlugins`. Adding
 /// this plugin will setup a collector appropriate to your target platform:
 /// * Using [`tracing-subscriber`](https://crates.io/crates/tracing-subscriber) by default,
-/// logging to `stdout`.
+///   logging to `stdout`.
 ///
 /// You can configure this plugin using the resource [`LogSettings`].
 /// ```no_run
@@ -44,9 +44,9 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 /// If you want to setup your own tracing collector, you should disable this
 /// plugin from `DefaultPlugins` with [`App::add_plugins_with`]:
 /// ```no_run
-/// # use arara_internal::DefaultPlugins;
+/// # use arara::DefaultPlugins;
 /// # use arara_app::App;
-/// # use arara_log::LoggerPlugin;
+/// # use arara_logger::LoggerPlugin;
 /// fn main() {
 ///     App::new()
 ///         .add_plugins_with(DefaultPlugins, |group| group.disable::<LoggerPlugin>())
@@ -77,7 +77,7 @@ impl Default for LogSettings {
 
 impl LogSettings {
     fn get_filter(&self) -> String {
-        if self.filter == "" {
+        if self.filter.is_empty() {
             self.level.to_string()
         } else {
             format!("{},{}", self.level, self.filter)
@@ -89,12 +89,12 @@ impl Plugin for LoggerPlugin {
     fn build(&self, app: &mut App) {
         LogTracer::init().unwrap();
         let filter_layer = match app.world.get_resource::<LogSettings>() {
-            Some(settings) => EnvFilter::try_new(&settings.get_filter())
+            Some(settings) => EnvFilter::try_new(settings.get_filter())
                 .expect("Failed on parsing [`LogSettings`]"),
             None => EnvFilter::try_from_default_env()
                 .or_else(|_| {
                     let settings = app.world.get_resource_or_insert_with(LogSettings::default);
-                    EnvFilter::try_new(&settings.get_filter())
+                    EnvFilter::try_new(settings.get_filter())
                 })
                 .unwrap(),
         };
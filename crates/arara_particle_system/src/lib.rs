@@ -1,6 +1,6 @@
 use arara_ecs::prelude::Component;
 use glam::Vec3;
-use rand::{self, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 mod particle_plugin;
 mod particle_system;
@@ -14,18 +14,30 @@ pub enum Value {
 }
 
 impl Value {
-    fn get(&self) -> f32 {
+    fn get(&self, rng: &mut impl Rng) -> f32 {
         match self {
             Value::Constant(c) => *c,
-            Value::Range(a, b) => {
-                let mut rng = rand::thread_rng();
-
-                rng.gen_range(*a..*b)
-            }
+            Value::Range(a, b) => rng.gen_range(*a..*b),
         }
     }
 }
 
+/// The random number generator of every particle system, insert a seeded one before the
+/// [`ParticleSystemPlugin`] to spawn the same particles on every run.
+pub struct ParticleRng(pub StdRng);
+
+impl ParticleRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for ParticleRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
 #[derive(Component)]
 pub struct Particle {
     pub time_remaining: f32,
@@ -2,7 +2,7 @@ use std::f32::consts::PI;
 
 use arara_asset::Handle;
 use arara_core_pipeline::SimpleMeshBundle;
-use arara_ecs::prelude::{Bundle, Commands, Component, Entity, Query, Res};
+use arara_ecs::prelude::{Bundle, Commands, Component, Entity, Query, Res, ResMut};
 use arara_render::{
     Billboard, Color, ColorOrGradient, Image, Mesh, Visibility, DEFAULT_IMAGE_HANDLE,
 };
@@ -10,9 +10,9 @@ use arara_time::{Time, Timer};
 use arara_transform::{BuildChildren, Children, GlobalTransform, Transform};
 use glam::{vec3, Vec3};
 
-use crate::{Particle, Value};
+use crate::{Particle, ParticleRng, Value};
 
-use rand::{self, Rng};
+use rand::Rng;
 
 #[derive(Component)]
 pub struct ParticleSystem {
@@ -55,9 +55,11 @@ pub struct ParticleSystemBundle {
 pub fn update_particles(
     mut commands: Commands,
     time: Res<Time>,
+    mut rng: ResMut<ParticleRng>,
     mut particle_system_query: Query<(Entity, &mut ParticleSystem, Option<&Children>)>,
     mut query: Query<(&mut Particle, &mut Visibility, &mut Color, &mut Transform)>,
 ) {
+    let rng = &mut rng.0;
     for (particle_system_entity, mut particle_system, children) in particle_system_query.iter_mut()
     {
         particle_system.timer.tick(time.delta());
@@ -74,12 +76,12 @@ pub fn update_particles(
                         && particle_system.timer.just_finished()
                     {
                         *transform = Transform::from_translation(
-                            particle_system.spawn_shape.gen_random_translation(),
+                            particle_system.spawn_shape.gen_random_translation(rng),
                         );
 
                         particle.time_remaining = particle_system.lifetime;
-                        particle.velocity = particle_system.particle_velocity.get();
-                        particle.direction = particle_system.spawn_shape.get_direction();
+                        particle.velocity = particle_system.particle_velocity.get(rng);
+                        particle.direction = particle_system.spawn_shape.get_direction(rng);
                         *color = particle_system.particle_color.get_color();
                         visibility.active = true;
                         spawn_count += 1;
@@ -135,8 +137,8 @@ pub fn init_particles(mut commands: Commands, query: Query<(Entity, &ParticleSys
                         visibility: Visibility::inactive(),
                         ..Default::default()
                     });
-                if particle_system.billboard.is_some() {
-                    child.insert(particle_system.billboard.unwrap());
+                if let Some(billboard) = particle_system.billboard {
+                    child.insert(billboard);
                 }
             }
         });
@@ -151,19 +153,17 @@ pub enum SpawnShape {
 }
 
 impl SpawnShape {
-    fn gen_random_translation(&self) -> Vec3 {
-        let mut rng = rand::thread_rng();
-
+    fn gen_random_translation(&self, rng: &mut impl Rng) -> Vec3 {
         match self {
             Self::Rectangle(x, y) => {
                 let x_size = x / 2.0;
                 let y_size = y / 2.0;
 
-                return vec3(
+                vec3(
                     rng.gen_range(-x_size..x_size),
                     0.,
                     rng.gen_range(-y_size..y_size),
-                );
+                )
             }
             Self::Circle(r) => {
                 let radius = r * rng.gen::<f32>().sqrt();
@@ -171,7 +171,7 @@ impl SpawnShape {
 
                 let x = radius * theta.cos();
                 let y = radius * theta.sin();
-                return vec3(x, 0., y);
+                vec3(x, 0., y)
             }
             _ => vec3(0., 0., 0.),
             // Self::Cone(a, y) => {
@@ -185,29 +185,23 @@ impl SpawnShape {
         }
     }
 
-    fn get_direction(&self) -> Vec3 {
+    fn get_direction(&self, rng: &mut impl Rng) -> Vec3 {
         match self {
             Self::Rectangle(_x, _y) => vec3(0., 1., 0.),
             Self::Circle(_) => vec3(0., 1., 0.),
-            Self::Sphere(_) => {
-                let mut rng = rand::thread_rng();
-
-                vec3(
-                    rng.gen::<f32>() * 2.0 - 1.0,
-                    rng.gen::<f32>() * 2.0 - 1.0,
-                    rng.gen::<f32>() * 2.0 - 1.0,
-                )
-                .normalize()
-            }
+            Self::Sphere(_) => vec3(
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>() * 2.0 - 1.0,
+            )
+            .normalize(),
             Self::Cone(r) => {
-                let mut rng = rand::thread_rng();
-
                 let radius = r * rng.gen::<f32>().sqrt();
                 let theta = rng.gen::<f32>() * 2.0 * PI;
 
                 let x = radius * theta.cos();
                 let y = radius * theta.sin();
-                return vec3(x, 1., y).normalize();
+                vec3(x, 1., y).normalize()
             }
         }
     }
@@ -0,0 +1,40 @@
+use arara_ecs::world::{FromWorld, World};
+use arara_window::Window;
+use glium::{backend::Facade, program::ComputeShader, Api, CapabilitiesSource, Version};
+
+/// What the OpenGL context supports, probed when the [`RenderPlugin`](crate::RenderPlugin)
+/// is built.
+#[derive(Debug, Clone)]
+pub struct RenderCapabilities {
+    /// Whether `GL_ARB_bindless_texture` is available.
+    ///
+    /// Without it the textures are bound to classic sampler uniforms, and the batches are split
+    /// so that all their instances sample the same textures.
+    pub bindless_textures: bool,
+    /// Highest GLSL version supported by the context, `460` for `#version 460`.
+    pub glsl_version: u32,
+    /// Whether this is an OpenGL ES context.
+    pub gles: bool,
+    /// Whether [`ComputePipeline`](crate::ComputePipeline)s can be compiled.
+    pub compute_shaders: bool,
+}
+
+impl RenderCapabilities {
+    pub fn probe(facade: &dyn Facade) -> Self {
+        let context = facade.get_context();
+        let Version(api, major, minor) = context.get_supported_glsl_version();
+        Self {
+            bindless_textures: context.get_extensions().gl_arb_bindless_texture,
+            glsl_version: major as u32 * 100 + minor as u32 * 10,
+            gles: api == Api::GlEs,
+            compute_shaders: ComputeShader::is_supported(&**context),
+        }
+    }
+}
+
+impl FromWorld for RenderCapabilities {
+    fn from_world(world: &mut World) -> Self {
+        let window = world.get_non_send_resource::<Window>().unwrap();
+        Self::probe(window.facade())
+    }
+}
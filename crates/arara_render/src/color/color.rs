@@ -1,5 +1,6 @@
 use arara_ecs::prelude::Component;
 use glam::{Vec3, Vec4};
+use std::ops::Mul;
 
 #[derive(Debug, Clone, Copy, PartialEq, Component)]
 pub enum Color {
@@ -184,6 +185,15 @@ impl From<Vec3> for Color {
     }
 }
 
+impl Mul<Vec4> for Color {
+    type Output = Color;
+
+    /// Multiplies every component of the color by the matching component of `rhs`.
+    fn mul(self, rhs: Vec4) -> Color {
+        Color::from(Vec4::from(self) * rhs)
+    }
+}
+
 #[derive(Debug)]
 pub enum HexColorError {
     Length,
@@ -236,7 +246,16 @@ mod tests {
             Color::rgba(0.0, 0.0, 0.0, 0.0)
         );
         assert!(Color::hex("--------").is_err());
-        assert!(Color::hex("12345678").is_err());
+        assert_eq!(
+            Color::hex("12345678").unwrap(),
+            Color::rgba(
+                0x12 as f32 / 255.0,
+                0x34 as f32 / 255.0,
+                0x56 as f32 / 255.0,
+                0x78 as f32 / 255.0
+            )
+        );
+        assert!(Color::hex("1234567").is_err());
     }
 
     #[test]
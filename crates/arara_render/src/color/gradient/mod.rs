@@ -6,28 +6,24 @@
 //!
 //! Using preset gradient:
 //! ```
-//! let g = colorgrad::rainbow();
+//! use arara_render::gradient;
+//!
+//! let g = gradient::rainbow();
 //!
 //! assert_eq!(g.domain(), (0.0, 1.0)); // all preset gradients are in the domain [0..1]
-//! assert_eq!(g.at(0.5).rgba_u8(), (175, 240, 91, 255));
-//! assert_eq!(g.at(0.5).to_hex_string(), "#aff05b");
 //! ```
 //!
 //! Custom gradient:
 //! ```
-//! use colorgrad::Color;
+//! use arara_render::{gradient, Color};
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! let g = colorgrad::CustomGradient::new()
-//!     .colors(&[
-//!         Color::from_rgb_u8(255, 0, 0),
-//!         Color::from_rgb_u8(0, 255, 0),
-//!     ])
+//! let g = gradient::CustomGradient::new()
+//!     .colors(&[Color::rgb(1.0, 0.0, 0.0), Color::rgb(0.0, 1.0, 0.0)])
 //!     .build()?;
 //!
-//! assert_eq!(g.at(0.0).rgba_u8(), (255, 0, 0, 255));
-//! assert_eq!(g.at(0.0).to_hex_string(), "#ff0000");
-//! assert_eq!(g.at(1.0).to_hex_string(), "#00ff00");
+//! assert_eq!(g.at(0.0), Color::rgb(1.0, 0.0, 0.0));
+//! assert_eq!(g.at(1.0), Color::rgb(0.0, 1.0, 0.0));
 //! # Ok(())
 //! # }
 //! ```
@@ -36,10 +32,12 @@
 //!
 //! ### Gradient Image
 //!
-//! ```rust
+//! ```rust,no_run
+//! use arara_render::{gradient, Color};
+//!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let grad = colorgrad::CustomGradient::new()
-//!         .html_colors(&["deeppink", "gold", "seagreen"])
+//!     let grad = gradient::CustomGradient::new()
+//!         .colors(&[Color::PINK, Color::GOLD, Color::SEA_GREEN])
 //!         .build()?;
 //!
 //!     let width = 1500;
@@ -48,8 +46,8 @@
 //!     let mut imgbuf = image::ImageBuffer::new(width, height);
 //!
 //!     for (x, _, pixel) in imgbuf.enumerate_pixels_mut() {
-//!         let (r, g, b, a) = grad.at(x as f32 / width as f32).rgba_u8();
-//!         *pixel = image::Rgba([r, g, b, a]);
+//!         let [r, g, b, a] = grad.at(x as f32 / width as f32).as_rgba_f32();
+//!         *pixel = image::Rgba([r, g, b, a].map(|c| (c * 255.0) as u8));
 //!     }
 //!
 //!     imgbuf.save("gradient.png")?;
@@ -64,19 +62,20 @@
 //! ### Colored Noise
 //!
 //! ```rust,ignore
+//! use arara_render::gradient;
 //! use noise::NoiseFn;
 //!
 //! fn main() {
 //!     let scale = 0.015;
 //!
-//!     let grad = colorgrad::rainbow().sharp(5, 0.15);
+//!     let grad = gradient::rainbow().sharp(5, 0.15);
 //!     let ns = noise::OpenSimplex::new();
 //!     let mut imgbuf = image::ImageBuffer::new(600, 350);
 //!
 //!     for (x, y, pixel) in imgbuf.enumerate_pixels_mut() {
 //!         let t = ns.get([x as f32 * scale, y as f32 * scale]);
-//!         let (r, g, b, a) = grad.at(remap(t, -0.5, 0.5, 0.0, 1.0)).rgba_u8();
-//!         *pixel = image::Rgba([r, g, b, a]);
+//!         let [r, g, b, a] = grad.at(remap(t, -0.5, 0.5, 0.0, 1.0)).as_rgba_f32();
+//!         *pixel = image::Rgba([r, g, b, a].map(|c| (c * 255.0) as u8));
 //!     }
 //!
 //!     imgbuf.save("noise.png").unwrap();
@@ -94,25 +93,25 @@
 //!
 //! ## Preset Gradients
 //!
-//! [colorgrad::cubehelix_default()](fn.cubehelix_default.html)
+//! [gradient::cubehelix_default()](fn.cubehelix_default.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/cubehelix_default.png)
 //!
-//! [colorgrad::turbo()](fn.turbo.html)
+//! [gradient::turbo()](fn.turbo.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/turbo.png)
 //!
-//! [colorgrad::spectral()](fn.spectral.html)
+//! [gradient::spectral()](fn.spectral.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/spectral.png)
 //!
-//! [colorgrad::viridis()](fn.viridis.html)
+//! [gradient::viridis()](fn.viridis.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/viridis.png)
 //!
-//! [colorgrad::magma()](fn.magma.html)
+//! [gradient::magma()](fn.magma.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/magma.png)
 //!
-//! [colorgrad::rainbow()](fn.rainbow.html)
+//! [gradient::rainbow()](fn.rainbow.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/rainbow.png)
 //!
-//! [colorgrad::sinebow()](fn.sinebow.html)
+//! [gradient::sinebow()](fn.sinebow.html)
 //! ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/sinebow.png)
 //!
 //! See more complete gradient preview and examples at [Github](https://github.com/mazznoer/colorgrad-rs).
@@ -234,12 +233,12 @@ impl Gradient {
     /// Get new hard-edge gradient
     ///
     /// ```
-    /// let g = colorgrad::rainbow();
+    /// let g = arara_render::gradient::rainbow();
     /// ```
     /// ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/preset/rainbow.png)
     ///
     /// ```
-    /// let g = colorgrad::rainbow().sharp(11, 0.);
+    /// let g = arara_render::gradient::rainbow().sharp(11, 0.);
     /// ```
     /// ![img](https://raw.githubusercontent.com/mazznoer/colorgrad-rs/master/docs/images/rainbow-sharp.png)
     pub fn sharp(&self, segment: usize, smoothness: f32) -> Gradient {
@@ -280,11 +279,11 @@ struct LinearGradient {
 impl GradientBase for LinearGradient {
     fn at(&self, t: f32) -> Color {
         if t < self.dmin {
-            return self.colors[0].clone();
+            return self.colors[0];
         }
 
         if t > self.dmax {
-            return self.colors[self.count].clone();
+            return self.colors[self.count];
         }
 
         for (pos, col) in self.pos.windows(2).zip(self.colors.windows(2)) {
@@ -301,7 +300,7 @@ impl GradientBase for LinearGradient {
             }
         }
 
-        self.colors[0].clone()
+        self.colors[0]
     }
 }
 
@@ -317,20 +316,20 @@ struct SharpGradient {
 impl GradientBase for SharpGradient {
     fn at(&self, t: f32) -> Color {
         if t < self.dmin {
-            return self.colors[0].clone();
+            return self.colors[0];
         }
 
         if t > self.dmax {
-            return self.colors[self.n].clone();
+            return self.colors[self.n];
         }
 
         for (pos, col) in self.pos.windows(2).zip(self.colors.iter()) {
             if (pos[0] <= t) && (t <= pos[1]) {
-                return col.clone();
+                return *col;
             }
         }
 
-        self.colors[0].clone()
+        self.colors[0]
     }
 }
 
@@ -364,17 +363,17 @@ struct SharpGradientX {
 impl GradientBase for SharpGradientX {
     fn at(&self, t: f32) -> Color {
         if t < self.dmin {
-            return self.colors[0].clone();
+            return self.colors[0];
         }
 
         if t > self.dmax {
-            return self.colors[self.last_idx].clone();
+            return self.colors[self.last_idx];
         }
 
         for (i, (pos, col)) in self.pos.windows(2).zip(self.colors.windows(2)).enumerate() {
             if (pos[0] <= t) && (t <= pos[1]) {
                 if i & 1 == 0 {
-                    return col[0].clone();
+                    return col[0];
                 }
 
                 let t = (t - pos[0]) / (pos[1] - pos[0]);
@@ -382,7 +381,7 @@ impl GradientBase for SharpGradientX {
             }
         }
 
-        self.colors[0].clone()
+        self.colors[0]
     }
 }
 
@@ -390,8 +389,8 @@ fn sharp_gradient_x(grad: &Gradient, n: usize, t: f32) -> Gradient {
     let mut colors = Vec::with_capacity(n * 2);
 
     for c in grad.colors(n) {
-        colors.push(c.clone());
-        colors.push(c.clone());
+        colors.push(c);
+        colors.push(c);
     }
 
     let (dmin, dmax) = grad.domain();
@@ -438,19 +437,16 @@ fn sharp_gradient_x(grad: &Gradient, n: usize, t: f32) -> Gradient {
 ///
 /// ```
 /// # use std::error::Error;
-/// use colorgrad::Color;
+/// use arara_render::{gradient, Color};
 ///
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// let grad = colorgrad::CustomGradient::new()
-///     .colors(&[
-///         Color::rgb(1.0, 0, 0),
-///         Color::rgb(0.0, 0.0, 1.0),
-///     ])
+/// let grad = gradient::CustomGradient::new()
+///     .colors(&[Color::rgb(1.0, 0.0, 0.0), Color::rgb(0.0, 0.0, 1.0)])
 ///     .build()?;
 ///
 /// assert_eq!(grad.domain(), (0.0, 1.0)); // default domain
-/// assert_eq!(grad.at(0.0).rgba_u8(), (255, 0, 0, 255));
-/// assert_eq!(grad.at(1.0).rgba_u8(), (0, 0, 255, 255));
+/// assert_eq!(grad.at(0.0), Color::rgb(1.0, 0.0, 0.0));
+/// assert_eq!(grad.at(1.0), Color::rgb(0.0, 0.0, 1.0));
 /// # Ok(())
 /// # }
 /// ```
@@ -478,7 +474,7 @@ impl CustomGradient {
     /// Set gradient color
     pub fn colors<'a>(&'a mut self, colors: &[Color]) -> &'a mut CustomGradient {
         for c in colors {
-            self.colors.push(c.clone());
+            self.colors.push(*c);
         }
         self
     }
@@ -514,7 +510,7 @@ impl CustomGradient {
         let colors = if self.colors.is_empty() {
             vec![Color::rgb(0.0, 0.0, 0.0), Color::rgb(1.0, 1.0, 1.0)]
         } else if self.colors.len() == 1 {
-            vec![self.colors[0].clone(), self.colors[0].clone()]
+            vec![self.colors[0], self.colors[0]]
         } else {
             self.colors.to_vec()
         };
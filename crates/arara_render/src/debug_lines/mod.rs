@@ -0,0 +1,256 @@
+mod render;
+
+pub use render::*;
+
+use arara_app::{App, Plugin};
+use arara_transform::GlobalTransform;
+use glam::Vec3;
+
+use crate::{
+    clear_phase_system, extract_camera_phases, sort_phase_system, Aabb, Color, DrawFunctions,
+    RenderPhases, RenderStage, SpecializedPipelines,
+};
+
+/// Segments of the circles and spheres.
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Lines queued by any system, drawn for a single frame or for a
+/// [`duration`](DebugShape::duration).
+///
+/// ```ignore
+/// fn draw_velocities(mut lines: ResMut<DebugLines>, query: Query<(&Transform, &Velocity)>) {
+///     for (transform, velocity) in query.iter() {
+///         let start = transform.translation;
+///         lines.arrow(start, start + velocity.0, Color::YELLOW);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct DebugLines {
+    lines: Vec<DebugLine>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DebugLine {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub color: Color,
+    /// Seconds left before the line disappears, it is drawn for a single frame when `0`
+    pub duration: f32,
+    /// Hidden by the geometry in front of it
+    pub depth_test: bool,
+}
+
+/// The lines of a shape just added to the [`DebugLines`].
+pub struct DebugShape<'a> {
+    lines: &'a mut [DebugLine],
+}
+
+impl DebugShape<'_> {
+    /// Keeps drawing the shape for `seconds` instead of a single frame.
+    pub fn duration(self, seconds: f32) -> Self {
+        for line in self.lines.iter_mut() {
+            line.duration = seconds;
+        }
+        self
+    }
+
+    /// Draws the shape over the geometry in front of it.
+    pub fn without_depth_test(self) -> Self {
+        for line in self.lines.iter_mut() {
+            line.depth_test = false;
+        }
+        self
+    }
+}
+
+impl DebugLines {
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) -> DebugShape<'_> {
+        self.shape(color, [(start, end)])
+    }
+
+    /// A line from `origin` to `origin + direction`.
+    pub fn ray(&mut self, origin: Vec3, direction: Vec3, color: Color) -> DebugShape<'_> {
+        self.line(origin, origin + direction, color)
+    }
+
+    /// A line with an arrow head at `end`.
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, color: Color) -> DebugShape<'_> {
+        let direction = end - start;
+        let head_length = direction.length() * 0.2;
+        let (u, v) = orthonormal_pair(direction.normalize_or_zero());
+        let head_base = end - direction.normalize_or_zero() * head_length;
+        let head_width = head_length * 0.4;
+        self.shape(
+            color,
+            [
+                (start, end),
+                (end, head_base + u * head_width),
+                (end, head_base - u * head_width),
+                (end, head_base + v * head_width),
+                (end, head_base - v * head_width),
+            ],
+        )
+    }
+
+    /// The edges of `aabb`, in the local space of `transform`.
+    pub fn aabb(
+        &mut self,
+        aabb: &Aabb,
+        transform: &GlobalTransform,
+        color: Color,
+    ) -> DebugShape<'_> {
+        let matrix = transform.compute_matrix();
+        let corners: Vec<Vec3> = (0..8)
+            .map(|i| {
+                let sign = Vec3::new(
+                    if i & 1 == 0 { -1.0 } else { 1.0 },
+                    if i & 2 == 0 { -1.0 } else { 1.0 },
+                    if i & 4 == 0 { -1.0 } else { 1.0 },
+                );
+                matrix.transform_point3(aabb.center + aabb.half_extents * sign)
+            })
+            .collect();
+        // corners whose index differ by a single bit share an edge
+        let edges = (0..8).flat_map(|i| {
+            [1, 2, 4]
+                .into_iter()
+                .filter(move |bit| i & bit == 0)
+                .map(move |bit| (i, i | bit))
+        });
+        let segments: Vec<_> = edges.map(|(a, b)| (corners[a], corners[b])).collect();
+        self.shape(color, segments)
+    }
+
+    /// A circle around `normal`.
+    pub fn circle(
+        &mut self,
+        center: Vec3,
+        normal: Vec3,
+        radius: f32,
+        color: Color,
+    ) -> DebugShape<'_> {
+        let (u, v) = orthonormal_pair(normal.normalize_or_zero());
+        let point = |i: usize| {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            center + (u * angle.cos() + v * angle.sin()) * radius
+        };
+        self.shape(
+            color,
+            (0..CIRCLE_SEGMENTS).map(|i| (point(i), point(i + 1))),
+        )
+    }
+
+    /// Three circles around the axes.
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Color) -> DebugShape<'_> {
+        let start = self.lines.len();
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            self.circle(center, axis, radius, color);
+        }
+        DebugShape {
+            lines: &mut self.lines[start..],
+        }
+    }
+
+    /// Removes every line, even those with a duration.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    pub fn lines(&self) -> &[DebugLine] {
+        &self.lines
+    }
+
+    fn shape(
+        &mut self,
+        color: Color,
+        segments: impl IntoIterator<Item = (Vec3, Vec3)>,
+    ) -> DebugShape<'_> {
+        let start = self.lines.len();
+        self.lines
+            .extend(segments.into_iter().map(|(start, end)| DebugLine {
+                start,
+                end,
+                color,
+                duration: 0.0,
+                depth_test: true,
+            }));
+        DebugShape {
+            lines: &mut self.lines[start..],
+        }
+    }
+}
+
+/// Two unit vectors perpendicular to `normal` and to each other.
+fn orthonormal_pair(normal: Vec3) -> (Vec3, Vec3) {
+    let other = if normal.x.abs() < 0.9 {
+        Vec3::X
+    } else {
+        Vec3::Y
+    };
+    let u = normal.cross(other).normalize_or_zero();
+    (u, normal.cross(u))
+}
+
+/// Draws the [`DebugLines`] for every camera, after the other phases.
+pub struct DebugLinesPlugin;
+
+impl Plugin for DebugLinesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugLines>()
+            .init_resource::<ExtractedDebugLines>()
+            .init_resource::<DebugLinesPipeline>()
+            .init_resource::<SpecializedPipelines<DebugLinesPipeline>>()
+            .init_resource::<DrawFunctions<DebugLinesItem>>()
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_camera_phases::<DebugLinesItem>,
+            )
+            .add_system_to_stage(RenderStage::Extract, extract_debug_lines)
+            .add_system_to_stage(RenderStage::Queue, queue_debug_lines)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<DebugLinesItem>)
+            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<DebugLinesItem>);
+
+        let draw_debug_lines = DrawDebugLines::new(&mut app.world);
+        app.world
+            .get_resource::<DrawFunctions<DebugLinesItem>>()
+            .unwrap()
+            .write()
+            .add(draw_debug_lines);
+
+        app.world
+            .get_resource_mut::<RenderPhases>()
+            .unwrap()
+            .add::<DebugLinesItem>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_are_split_into_lines() {
+        let mut lines = DebugLines::default();
+        lines.line(Vec3::ZERO, Vec3::X, Color::RED);
+        lines
+            .aabb(
+                &Aabb::from_min_max(Vec3::ZERO, Vec3::ONE),
+                &GlobalTransform::identity(),
+                Color::RED,
+            )
+            .duration(1.0)
+            .without_depth_test();
+        lines.sphere(Vec3::ZERO, 1.0, Color::RED);
+
+        assert_eq!(lines.lines().len(), 1 + 12 + 3 * CIRCLE_SEGMENTS);
+        assert!(lines.lines()[0].depth_test);
+        assert!(lines.lines()[1..13]
+            .iter()
+            .all(|line| !line.depth_test && line.duration == 1.0));
+        // every edge of the box has a length of 1
+        assert!(lines.lines()[1..13]
+            .iter()
+            .all(|line| (line.start.distance(line.end) - 1.0).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,218 @@
+use arara_asset::{AssetServer, Handle};
+use arara_ecs::{
+    entity::Entity,
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SQuery, SRes},
+        SystemState,
+    },
+};
+use arara_time::Time;
+use arara_window::Window;
+use glium::index::{NoIndices, PrimitiveType};
+
+use crate::{
+    CachedPipelineId, CachedPipelinePhaseItem, DebugLines, Draw, DrawFunctionId, DrawFunctions,
+    ExtractedView, PhaseItem, RenderPhase, RenderPipelineCache, RenderPipelineDescriptor, Shader,
+    SpecializedPipeline, SpecializedPipelines, TrackedFrame,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct LineVertex {
+    position: [f32; 3],
+    color: [f32; 4],
+}
+
+glium::implement_vertex!(LineVertex, position, color);
+
+/// The vertices of the [`DebugLines`] drawn this frame, two per line.
+#[derive(Default)]
+pub struct ExtractedDebugLines {
+    pub depth_tested: Vec<LineVertex>,
+    /// Drawn over the geometry
+    pub overlay: Vec<LineVertex>,
+}
+
+impl ExtractedDebugLines {
+    fn vertices(&self, depth_test: bool) -> &[LineVertex] {
+        if depth_test {
+            &self.depth_tested
+        } else {
+            &self.overlay
+        }
+    }
+}
+
+/// Extracts the lines then forgets those whose duration has elapsed.
+pub(crate) fn extract_debug_lines(
+    time: Res<Time>,
+    mut debug_lines: ResMut<DebugLines>,
+    mut extracted: ResMut<ExtractedDebugLines>,
+) {
+    extracted.depth_tested.clear();
+    extracted.overlay.clear();
+    let delta = time.delta_seconds();
+    for line in debug_lines.lines.iter_mut() {
+        let vertices = if line.depth_test {
+            &mut extracted.depth_tested
+        } else {
+            &mut extracted.overlay
+        };
+        let color = line.color.into();
+        vertices.push(LineVertex {
+            position: line.start.into(),
+            color,
+        });
+        vertices.push(LineVertex {
+            position: line.end.into(),
+            color,
+        });
+        line.duration -= delta;
+    }
+    debug_lines.lines.retain(|line| line.duration > 0.0);
+}
+
+/// The depth tested lines, then the overlay.
+pub struct DebugLinesItem {
+    pub depth_test: bool,
+    pub draw_function: DrawFunctionId,
+    pub pipeline: CachedPipelineId,
+}
+
+impl PhaseItem for DebugLinesItem {
+    type SortKey = bool;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        !self.depth_test
+    }
+
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
+
+impl CachedPipelinePhaseItem for DebugLinesItem {
+    fn cached_pipeline(&self) -> CachedPipelineId {
+        self.pipeline
+    }
+}
+
+pub struct DebugLinesPipeline {
+    pub vertex_shader: Handle<Shader>,
+    pub fragment_shader: Handle<Shader>,
+}
+
+impl FromWorld for DebugLinesPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            vertex_shader: asset_server.load("shaders/debug_lines.vert"),
+            fragment_shader: asset_server.load("shaders/debug_lines.frag"),
+        }
+    }
+}
+
+impl SpecializedPipeline for DebugLinesPipeline {
+    /// Whether the lines are hidden by the geometry in front of them
+    type Key = bool;
+
+    fn specialize(&self, depth_test: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex_shader: self.vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: self.fragment_shader.clone_weak(),
+            shader_defs: Vec::new(),
+            draw_parameters: glium::DrawParameters {
+                depth: glium::Depth {
+                    test: if depth_test {
+                        glium::draw_parameters::DepthTest::IfLessOrEqual
+                    } else {
+                        glium::draw_parameters::DepthTest::Overwrite
+                    },
+                    ..Default::default()
+                },
+                blend: glium::draw_parameters::Blend::alpha_blending(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+pub(crate) fn queue_debug_lines(
+    mut views: Query<&mut RenderPhase<DebugLinesItem>, With<ExtractedView>>,
+    extracted: Res<ExtractedDebugLines>,
+    mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
+    pipeline: Res<DebugLinesPipeline>,
+    mut pipelines: ResMut<SpecializedPipelines<DebugLinesPipeline>>,
+    draw_functions: Res<DrawFunctions<DebugLinesItem>>,
+) {
+    let draw_function = draw_functions.read().get_id::<DrawDebugLines>().unwrap();
+    for depth_test in [true, false] {
+        if extracted.vertices(depth_test).is_empty() {
+            continue;
+        }
+        let cached_pipeline =
+            pipelines.specialize(&mut render_pipeline_cache, &pipeline, depth_test);
+        for mut phase in views.iter_mut() {
+            phase.add(DebugLinesItem {
+                depth_test,
+                draw_function,
+                pipeline: cached_pipeline,
+            });
+        }
+    }
+}
+
+pub struct DrawDebugLines {
+    params: SystemState<(
+        NonSend<'static, Window>,
+        NonSend<'static, RenderPipelineCache>,
+        SRes<ExtractedDebugLines>,
+        SQuery<Read<ExtractedView>>,
+    )>,
+}
+
+impl DrawDebugLines {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            params: SystemState::new(world),
+        }
+    }
+}
+
+impl Draw<DebugLinesItem> for DrawDebugLines {
+    fn draw(
+        &mut self,
+        world: &World,
+        frame: &mut TrackedFrame,
+        view: Entity,
+        item: &DebugLinesItem,
+    ) {
+        let (window, pipeline_cache, extracted, views) = self.params.get(world);
+        let view = match views.get(view) {
+            Ok(view) => view,
+            Err(_) => return,
+        };
+        let pipeline = match pipeline_cache.get(item.pipeline) {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+
+        let vertex_buffer =
+            glium::VertexBuffer::new(window.facade(), extracted.vertices(item.depth_test)).unwrap();
+        let uniforms = glium::uniform! {
+            u_pv_matrix: view.pv_matrix.to_cols_array_2d(),
+        };
+        frame
+            .draw(
+                &vertex_buffer,
+                NoIndices(PrimitiveType::LinesList),
+                &pipeline.program,
+                &uniforms,
+                &pipeline.parameters,
+            )
+            .unwrap();
+    }
+}
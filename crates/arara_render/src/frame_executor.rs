@@ -1,19 +1,119 @@
-use arara_ecs::world::World;
+use arara_ecs::{entity::Entity, prelude::With, world::World};
+use arara_utils::tracing::error;
 use arara_window::Window;
+use glam::UVec2;
+use glium::Rect;
 
-use crate::{ClearColor, RenderPhases, TrackedFrame};
+use crate::{
+    post_process::draw_post_processed_view, screenshot::send_screenshots, ClearColor,
+    ExtractedView, OffscreenView, RenderPhases, RenderTarget, RenderTargets, ScreenshotManager,
+    TrackedFrame,
+};
 
 pub(crate) fn draw_frame(world: &mut World) {
+    let screenshot_requests = world
+        .get_resource_mut::<ScreenshotManager>()
+        .unwrap()
+        .take_requests();
+
+    let mut views: Vec<(Entity, ExtractedView)> = world
+        .query::<(Entity, &ExtractedView)>()
+        .iter(world)
+        .map(|(entity, view)| (entity, view.clone()))
+        .collect();
+    views.sort_by_key(|(_, view)| view.priority);
+    let offscreen_views: Vec<Entity> = world
+        .query_filtered::<Entity, With<OffscreenView>>()
+        .iter(world)
+        .collect();
+
     let window = world.get_non_send_resource::<Window>().unwrap();
+    let render_targets = world.get_non_send_resource::<RenderTargets>().unwrap();
     let clear_color = world.get_resource::<ClearColor>().unwrap();
     let phases = world.get_resource::<RenderPhases>().unwrap();
 
-    let display = window.display();
+    let mut tracked_frame = TrackedFrame::new(window);
+    // offscreen views draw into their own textures, the cameras sample them
+    for view in offscreen_views {
+        phases.run(world, view, &mut tracked_frame);
+    }
+
+    // images are drawn first so the window can show them in this same frame
+    let mut image_targets = Vec::new();
+    for (_, view) in views.iter() {
+        if let RenderTarget::Image(handle) = &view.target {
+            if !image_targets.contains(handle) {
+                image_targets.push(handle.clone_weak());
+            }
+        }
+    }
+    for handle in image_targets {
+        let target = match render_targets.get(&handle) {
+            Some(target) => target,
+            None => continue,
+        };
+        let framebuffer = match target.framebuffer(window.facade()) {
+            Ok(framebuffer) => framebuffer,
+            Err(err) => {
+                error!(
+                    "Failed to draw into the render target {:?}: {}",
+                    handle, err
+                );
+                continue;
+            }
+        };
+        let mut image_frame = TrackedFrame::offscreen(window.facade(), framebuffer);
+        image_frame.clear_color_and_depth(clear_color.0);
+        let target = RenderTarget::Image(handle);
+        let target_views = views.iter().filter(|(_, view)| view.target == target);
+        draw_views(world, phases, &mut image_frame, target_views);
+    }
 
-    let mut tracked_frame = TrackedFrame::new(display);
     tracked_frame.clear_color_and_depth(clear_color.0);
+    let window_views = views
+        .iter()
+        .filter(|(_, view)| view.target == RenderTarget::Window);
+    draw_views(world, phases, &mut tracked_frame, window_views);
 
-    phases.run(world, &mut tracked_frame);
+    let screenshot = if screenshot_requests.is_empty() {
+        None
+    } else {
+        Some(tracked_frame.read_pixels())
+    };
 
     tracked_frame.finish().unwrap();
+
+    if let Some(capture) = screenshot {
+        send_screenshots(world, screenshot_requests, capture);
+    }
+}
+
+/// Runs the phases of every view drawing into `frame`, in ascending priority.
+fn draw_views<'a>(
+    world: &World,
+    phases: &RenderPhases,
+    frame: &mut TrackedFrame,
+    views: impl Iterator<Item = &'a (Entity, ExtractedView)>,
+) {
+    let (width, height) = frame.dimensions();
+    for (i, (entity, view)) in views.enumerate() {
+        let viewport = view.viewport.map(|viewport| {
+            let (origin, size) = viewport.physical_rect(UVec2::new(width, height));
+            Rect {
+                left: origin.x,
+                bottom: origin.y,
+                width: size.x,
+                height: size.y,
+            }
+        });
+        frame.set_viewport(viewport);
+        // later cameras are drawn on top of the previous ones
+        if i > 0 {
+            frame.clear_depth();
+        }
+        if !draw_post_processed_view(world, phases, *entity, frame) {
+            phases.run(world, *entity, frame);
+        }
+    }
+    frame.set_viewport(None);
 }
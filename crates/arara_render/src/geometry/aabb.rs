@@ -0,0 +1,68 @@
+use arara_asset::{AssetEvent, Assets, Handle};
+use arara_ecs::{
+    event::EventReader,
+    prelude::Component,
+    system::{Res, ResMut},
+};
+use arara_utils::HashMap;
+use glam::Vec3;
+
+use crate::Mesh;
+
+/// An axis aligned bounding box in the local space of an entity, used for frustum culling.
+///
+/// Entities with a [`Handle<Mesh>`] use the box of the mesh, adding this component overrides it.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl Aabb {
+    pub fn from_min_max(min: Vec3, max: Vec3) -> Self {
+        Self {
+            center: (min + max) * 0.5,
+            half_extents: (max - min) * 0.5,
+        }
+    }
+
+    pub fn min(&self) -> Vec3 {
+        self.center - self.half_extents
+    }
+
+    pub fn max(&self) -> Vec3 {
+        self.center + self.half_extents
+    }
+}
+
+/// The [`Aabb`] of every [`Mesh`], recomputed when the mesh is modified.
+#[derive(Default)]
+pub struct MeshAabbs {
+    aabbs: HashMap<Handle<Mesh>, Aabb>,
+}
+
+impl MeshAabbs {
+    pub fn get(&self, handle: &Handle<Mesh>) -> Option<&Aabb> {
+        self.aabbs.get(handle)
+    }
+}
+
+pub(crate) fn update_mesh_aabbs(
+    meshes: Res<Assets<Mesh>>,
+    mut events: EventReader<AssetEvent<Mesh>>,
+    mut mesh_aabbs: ResMut<MeshAabbs>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                match meshes.get(handle).and_then(Mesh::compute_aabb) {
+                    Some(aabb) => mesh_aabbs.aabbs.insert(handle.clone_weak(), aabb),
+                    None => mesh_aabbs.aabbs.remove(handle),
+                };
+            }
+            AssetEvent::Removed { handle } => {
+                mesh_aabbs.aabbs.remove(handle);
+            }
+        }
+    }
+}
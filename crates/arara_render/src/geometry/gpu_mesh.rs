@@ -0,0 +1,140 @@
+use arara_asset::{AssetEvent, Assets, Handle};
+use arara_ecs::{
+    event::EventReader,
+    system::{NonSend, NonSendMut, Res},
+};
+use arara_utils::{
+    tracing::{error, trace},
+    HashMap,
+};
+use arara_window::Window;
+use glium::{backend::Facade, vertex::VertexBufferAny, IndexBuffer, VertexBuffer};
+use thiserror::Error;
+
+use crate::{Mesh, MeshVertexLayout, PrimitiveTopology};
+
+/// The largest vertex, in 32 bits words.
+const MAX_VERTEX_WORDS: usize = 32;
+
+/// The vertex and index buffers of a [`Mesh`] living on the GPU.
+pub struct GpuMesh {
+    /// The interleaved attributes of the mesh
+    pub vertex_buffer: VertexBufferAny,
+    /// Drawn as the [`Mesh::primitive_topology`]
+    pub index_buffer: IndexBuffer<u32>,
+    pub layout: MeshVertexLayout,
+    pub primitive_topology: PrimitiveTopology,
+}
+
+impl GpuMesh {
+    pub fn new(facade: &dyn Facade, mesh: &Mesh) -> Result<Self, GpuMeshError> {
+        let layout = mesh.vertex_layout();
+        let count = mesh.count_vertices();
+        if mesh.attributes().any(|(_, values)| values.len() != count) {
+            return Err(GpuMeshError::MismatchedAttributes);
+        }
+        // every format is a multiple of 4 bytes
+        let words: Vec<u32> = mesh
+            .interleaved_vertices()
+            .chunks_exact(4)
+            .map(|bytes| u32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        let vertex_words = layout.stride() / 4;
+
+        // glium takes the stride of the vertices from their type
+        macro_rules! upload {
+            ($($size:literal)*) => {
+                match vertex_words {
+                    0 => return Err(GpuMeshError::NoAttributes),
+                    $($size => upload_vertices::<$size>(facade, &words, &layout)?,)*
+                    _ => return Err(GpuMeshError::VertexTooLarge(layout.stride())),
+                }
+            };
+        }
+        let vertex_buffer = upload!(
+            1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+            17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+        );
+        let primitive_topology = mesh.primitive_topology();
+        Ok(Self {
+            vertex_buffer,
+            index_buffer: IndexBuffer::new(facade, primitive_topology.into(), &mesh.indices)?,
+            layout,
+            primitive_topology,
+        })
+    }
+}
+
+fn upload_vertices<const N: usize>(
+    facade: &dyn Facade,
+    words: &[u32],
+    layout: &MeshVertexLayout,
+) -> Result<VertexBufferAny, GpuMeshError> {
+    let vertices: Vec<[u32; N]> = words
+        .chunks_exact(N)
+        .map(|vertex| vertex.try_into().unwrap())
+        .collect();
+    // SAFETY: the bindings describe the interleaved attributes of the vertices
+    let vertex_buffer =
+        unsafe { VertexBuffer::new_raw(facade, &vertices, layout.bindings(), N * 4)? };
+    Ok(vertex_buffer.into())
+}
+
+#[derive(Error, Debug)]
+pub enum GpuMeshError {
+    #[error("the mesh has no vertex attributes")]
+    NoAttributes,
+    #[error("the attributes of the mesh don't have the same amount of values")]
+    MismatchedAttributes,
+    #[error("vertices of {0} bytes are larger than the supported {} bytes", MAX_VERTEX_WORDS * 4)]
+    VertexTooLarge(usize),
+    #[error(transparent)]
+    VertexBuffer(#[from] glium::vertex::BufferCreationError),
+    #[error(transparent)]
+    IndexBuffer(#[from] glium::index::BufferCreationError),
+}
+
+/// Every [`Mesh`] uploaded once to the GPU, kept in sync with the [`Assets<Mesh>`].
+#[derive(Default)]
+pub struct GpuMeshes {
+    meshes: HashMap<Handle<Mesh>, GpuMesh>,
+}
+
+impl GpuMeshes {
+    pub fn get(&self, handle: &Handle<Mesh>) -> Option<&GpuMesh> {
+        self.meshes.get(handle)
+    }
+
+    pub fn contains(&self, handle: &Handle<Mesh>) -> bool {
+        self.meshes.contains_key(handle)
+    }
+}
+
+/// Uploads created and modified meshes and frees the removed ones.
+pub(crate) fn extract_meshes(
+    window: NonSend<Window>,
+    meshes: Res<Assets<Mesh>>,
+    mut events: EventReader<AssetEvent<Mesh>>,
+    mut gpu_meshes: NonSendMut<GpuMeshes>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                let mesh = match meshes.get(handle) {
+                    Some(mesh) => mesh,
+                    None => continue,
+                };
+                trace!("uploading mesh {:?}", handle);
+                match GpuMesh::new(window.facade(), mesh) {
+                    Ok(gpu_mesh) => {
+                        gpu_meshes.meshes.insert(handle.clone_weak(), gpu_mesh);
+                    }
+                    Err(err) => error!("Failed to upload mesh {:?}: {}", handle, err),
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                gpu_meshes.meshes.remove(handle);
+            }
+        }
+    }
+}
@@ -1,10 +1,595 @@
+use std::collections::BTreeMap;
+
 use bevy_reflect::TypeUuid;
+use glam::Vec3;
+use glium::index::PrimitiveType;
+use thiserror::Error;
+
+use crate::geometry::{
+    shape::Vertex, Aabb, MeshVertexAttribute, MeshVertexLayout, VertexAttributeValues, VertexFormat,
+};
+
+/// How the indices of a [`Mesh`] are assembled into primitives.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum PrimitiveTopology {
+    /// Every index is a point
+    PointList,
+    /// Every two indices are a line
+    LineList,
+    /// Every index is joined to the previous one by a line
+    LineStrip,
+    /// Every three indices are a triangle
+    #[default]
+    TriangleList,
+    /// Every index makes a triangle with the two previous ones
+    TriangleStrip,
+}
 
-use crate::geometry::shape::Vertex;
+impl PrimitiveTopology {
+    /// Whether the primitives are triangles, the others have no faces to cull or to light.
+    pub fn is_triangles(&self) -> bool {
+        matches!(
+            self,
+            PrimitiveTopology::TriangleList | PrimitiveTopology::TriangleStrip
+        )
+    }
+}
+
+impl From<PrimitiveTopology> for PrimitiveType {
+    fn from(topology: PrimitiveTopology) -> Self {
+        match topology {
+            PrimitiveTopology::PointList => PrimitiveType::Points,
+            PrimitiveTopology::LineList => PrimitiveType::LinesList,
+            PrimitiveTopology::LineStrip => PrimitiveType::LineStrip,
+            PrimitiveTopology::TriangleList => PrimitiveType::TrianglesList,
+            PrimitiveTopology::TriangleStrip => PrimitiveType::TriangleStrip,
+        }
+    }
+}
 
+/// Vertices made of named [`MeshVertexAttribute`]s, and the indices of their primitives.
+///
+/// The shapes and the glTF meshes have the standard [`Mesh::ATTRIBUTE_POSITION`],
+/// [`Mesh::ATTRIBUTE_NORMAL`] and [`Mesh::ATTRIBUTE_UV_0`], the others are optional.
 #[derive(Debug, Clone, TypeUuid, Default)]
 #[uuid = "8ecbac0f-f545-4473-ad43-e1f4243af51e"]
 pub struct Mesh {
-    pub vertices: Vec<Vertex>,
+    primitive_topology: PrimitiveTopology,
+    /// Sorted by name so two meshes with the same attributes share their layout
+    attributes: BTreeMap<&'static str, VertexAttributeValues>,
     pub indices: Vec<u32>,
 }
+
+impl Mesh {
+    pub const ATTRIBUTE_POSITION: MeshVertexAttribute =
+        MeshVertexAttribute::new("position", VertexFormat::Float32x3);
+    pub const ATTRIBUTE_NORMAL: MeshVertexAttribute =
+        MeshVertexAttribute::new("normal", VertexFormat::Float32x3);
+    pub const ATTRIBUTE_UV_0: MeshVertexAttribute =
+        MeshVertexAttribute::new("tex_coord", VertexFormat::Float32x2);
+    pub const ATTRIBUTE_UV_1: MeshVertexAttribute =
+        MeshVertexAttribute::new("tex_coord_1", VertexFormat::Float32x2);
+    /// Linear RGBA, multiplied with the color of the material
+    pub const ATTRIBUTE_COLOR: MeshVertexAttribute =
+        MeshVertexAttribute::new("color", VertexFormat::Float32x4);
+    /// The direction of the `u` texture coordinate, `w` holds the sign of the bitangent
+    pub const ATTRIBUTE_TANGENT: MeshVertexAttribute =
+        MeshVertexAttribute::new("tangent", VertexFormat::Float32x4);
+    pub const ATTRIBUTE_JOINT_INDEX: MeshVertexAttribute =
+        MeshVertexAttribute::new("joint_indices", VertexFormat::Uint16x4);
+    pub const ATTRIBUTE_JOINT_WEIGHT: MeshVertexAttribute =
+        MeshVertexAttribute::new("joint_weights", VertexFormat::Float32x4);
+
+    /// An empty mesh assembling its indices into `primitive_topology`.
+    pub fn new(primitive_topology: PrimitiveTopology) -> Self {
+        Self {
+            primitive_topology,
+            ..Default::default()
+        }
+    }
+
+    /// A triangle list with the standard attributes of the `vertices`.
+    pub fn from_vertices(vertices: &[Vertex], indices: Vec<u32>) -> Self {
+        let mut mesh = Self {
+            indices,
+            ..Default::default()
+        };
+        mesh.insert_attribute(
+            Self::ATTRIBUTE_POSITION,
+            vertices
+                .iter()
+                .map(|vertex| vertex.position)
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Self::ATTRIBUTE_NORMAL,
+            vertices
+                .iter()
+                .map(|vertex| vertex.normal)
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Self::ATTRIBUTE_UV_0,
+            vertices
+                .iter()
+                .map(|vertex| vertex.tex_coord)
+                .collect::<Vec<_>>(),
+        );
+        mesh
+    }
+
+    pub fn primitive_topology(&self) -> PrimitiveTopology {
+        self.primitive_topology
+    }
+
+    /// Sets the values of `attribute`, replacing its previous values.
+    ///
+    /// # Panics
+    /// Panics if the format of the `values` isn't the one of the `attribute`.
+    pub fn insert_attribute(
+        &mut self,
+        attribute: MeshVertexAttribute,
+        values: impl Into<VertexAttributeValues>,
+    ) {
+        let values = values.into();
+        assert_eq!(
+            values.format(),
+            attribute.format,
+            "the values of the {} attribute have the wrong format",
+            attribute.name
+        );
+        self.attributes.insert(attribute.name, values);
+    }
+
+    pub fn with_inserted_attribute(
+        mut self,
+        attribute: MeshVertexAttribute,
+        values: impl Into<VertexAttributeValues>,
+    ) -> Self {
+        self.insert_attribute(attribute, values);
+        self
+    }
+
+    pub fn attribute(&self, attribute: MeshVertexAttribute) -> Option<&VertexAttributeValues> {
+        self.attributes.get(attribute.name)
+    }
+
+    pub fn attribute_mut(
+        &mut self,
+        attribute: MeshVertexAttribute,
+    ) -> Option<&mut VertexAttributeValues> {
+        self.attributes.get_mut(attribute.name)
+    }
+
+    pub fn remove_attribute(
+        &mut self,
+        attribute: MeshVertexAttribute,
+    ) -> Option<VertexAttributeValues> {
+        self.attributes.remove(attribute.name)
+    }
+
+    pub fn contains_attribute(&self, attribute: MeshVertexAttribute) -> bool {
+        self.attributes.contains_key(attribute.name)
+    }
+
+    /// Every attribute of the mesh, sorted by name.
+    pub fn attributes(
+        &self,
+    ) -> impl Iterator<Item = (MeshVertexAttribute, &VertexAttributeValues)> {
+        self.attributes
+            .iter()
+            .map(|(name, values)| (MeshVertexAttribute::new(name, values.format()), values))
+    }
+
+    /// The amount of vertices, `0` if the mesh has no attributes.
+    pub fn count_vertices(&self) -> usize {
+        self.attributes
+            .values()
+            .map(|values| values.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn vertex_layout(&self) -> MeshVertexLayout {
+        MeshVertexLayout::new(self.attributes().map(|(attribute, _)| attribute))
+    }
+
+    /// The attributes of every vertex one after the other, following the
+    /// [`Mesh::vertex_layout`].
+    ///
+    /// # Panics
+    /// Panics if the attributes don't have the same amount of values.
+    pub fn interleaved_vertices(&self) -> Vec<u8> {
+        let count = self.count_vertices();
+        assert!(
+            self.attributes.values().all(|values| values.len() == count),
+            "the attributes of a mesh must have a value for every vertex"
+        );
+        let mut bytes = Vec::with_capacity(count * self.vertex_layout().stride());
+        for index in 0..count {
+            for values in self.attributes.values() {
+                values.write_bytes(index, &mut bytes);
+            }
+        }
+        bytes
+    }
+
+    fn positions(&self) -> Option<&[[f32; 3]]> {
+        self.attribute(Self::ATTRIBUTE_POSITION)?.as_float3()
+    }
+
+    /// The indices of every triangle, an empty iterator if the primitives aren't triangles.
+    fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        let (list, strip) = match self.primitive_topology {
+            PrimitiveTopology::TriangleList => (Some(&self.indices), None),
+            PrimitiveTopology::TriangleStrip => (None, Some(&self.indices)),
+            _ => (None, None),
+        };
+        let list = list
+            .into_iter()
+            .flat_map(|indices| indices.chunks_exact(3))
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]]);
+        // every other triangle of a strip is swapped to keep the winding of the first one
+        let strip = strip
+            .into_iter()
+            .flat_map(|indices| indices.windows(3).enumerate())
+            .map(|(i, triangle)| match i % 2 {
+                0 => [triangle[0], triangle[1], triangle[2]],
+                _ => [triangle[1], triangle[0], triangle[2]],
+            });
+        list.chain(strip)
+    }
+
+    /// Gives every primitive its own vertices, so they don't share their attributes anymore.
+    ///
+    /// Strips become lists, since their primitives share their indices.
+    pub fn duplicate_vertices(&mut self) {
+        match self.primitive_topology {
+            PrimitiveTopology::TriangleStrip => {
+                self.indices = self.triangles().flatten().collect();
+                self.primitive_topology = PrimitiveTopology::TriangleList;
+            }
+            PrimitiveTopology::LineStrip => {
+                self.indices = self
+                    .indices
+                    .windows(2)
+                    .flat_map(|line| [line[0], line[1]])
+                    .collect();
+                self.primitive_topology = PrimitiveTopology::LineList;
+            }
+            _ => {}
+        }
+        for values in self.attributes.values_mut() {
+            *values = values.gather(&self.indices);
+        }
+        self.indices = (0..self.indices.len() as u32).collect();
+    }
+
+    /// Sets the normals of every triangle to the normal of its face, the vertices are
+    /// duplicated so they aren't shared between the triangles.
+    ///
+    /// # Panics
+    /// Panics if the mesh has no [`Mesh::ATTRIBUTE_POSITION`] or its primitives aren't
+    /// triangles.
+    pub fn compute_flat_normals(&mut self) {
+        assert!(
+            self.primitive_topology.is_triangles(),
+            "flat normals need triangles"
+        );
+        self.duplicate_vertices();
+        let positions = self.positions().expect("flat normals need positions");
+        let normals: Vec<[f32; 3]> = positions
+            .chunks_exact(3)
+            .flat_map(|triangle| {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(Vec3::from);
+                [(b - a).cross(c - a).normalize_or_zero().to_array(); 3]
+            })
+            .collect();
+        self.insert_attribute(Self::ATTRIBUTE_NORMAL, normals);
+    }
+
+    /// Sets the normal of every vertex to the average of the normals of the triangles sharing
+    /// it, weighted by their area.
+    ///
+    /// # Panics
+    /// Panics if the mesh has no [`Mesh::ATTRIBUTE_POSITION`] or its primitives aren't
+    /// triangles.
+    pub fn compute_smooth_normals(&mut self) {
+        assert!(
+            self.primitive_topology.is_triangles(),
+            "smooth normals need triangles"
+        );
+        let positions = self.positions().expect("smooth normals need positions");
+        let mut normals = vec![Vec3::ZERO; positions.len()];
+        for triangle in self.triangles() {
+            let [a, b, c] = triangle.map(|index| Vec3::from(positions[index as usize]));
+            // the length of the cross product is twice the area of the triangle
+            let normal = (b - a).cross(c - a);
+            for index in triangle {
+                normals[index as usize] += normal;
+            }
+        }
+        let normals: Vec<[f32; 3]> = normals
+            .into_iter()
+            .map(|normal| normal.normalize_or_zero().to_array())
+            .collect();
+        self.insert_attribute(Self::ATTRIBUTE_NORMAL, normals);
+    }
+
+    /// Generates the MikkTSpace [`Mesh::ATTRIBUTE_TANGENT`] from the positions, the normals
+    /// and the first texture coordinates, for normal mapping.
+    ///
+    /// A vertex shared by triangles whose tangent spaces MikkTSpace splits only keeps one of
+    /// them, [`Mesh::duplicate_vertices`] first to keep them all.
+    pub fn generate_tangents(&mut self) -> Result<(), GenerateTangentsError> {
+        if !self.primitive_topology.is_triangles() {
+            return Err(GenerateTangentsError::UnsupportedTopology(
+                self.primitive_topology,
+            ));
+        }
+        let missing = |attribute: MeshVertexAttribute| {
+            GenerateTangentsError::MissingAttribute(attribute.name)
+        };
+        let positions = self
+            .positions()
+            .ok_or_else(|| missing(Self::ATTRIBUTE_POSITION))?;
+        let normals = self
+            .attribute(Self::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .ok_or_else(|| missing(Self::ATTRIBUTE_NORMAL))?;
+        let uvs = self
+            .attribute(Self::ATTRIBUTE_UV_0)
+            .and_then(VertexAttributeValues::as_float2)
+            .ok_or_else(|| missing(Self::ATTRIBUTE_UV_0))?;
+
+        let mut geometry = MikktspaceGeometry {
+            triangles: self.triangles().collect(),
+            positions,
+            normals,
+            uvs,
+            tangents: vec![[0.0; 4]; positions.len()],
+        };
+        if !mikktspace::generate_tangents(&mut geometry) {
+            return Err(GenerateTangentsError::MikktspaceFailed);
+        }
+        let tangents = geometry.tangents;
+        self.insert_attribute(Self::ATTRIBUTE_TANGENT, tangents);
+        Ok(())
+    }
+
+    /// The smallest [`Aabb`] containing every vertex, `None` if the mesh has no vertices.
+    pub fn compute_aabb(&self) -> Option<Aabb> {
+        let mut positions = self
+            .positions()?
+            .iter()
+            .map(|&position| Vec3::from(position));
+        let first = positions.next()?;
+        let (min, max) = positions.fold((first, first), |(min, max), position| {
+            (min.min(position), max.max(position))
+        });
+        Some(Aabb::from_min_max(min, max))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GenerateTangentsError {
+    #[error("the {0} attribute is missing")]
+    MissingAttribute(&'static str),
+    #[error("the primitives are {0:?}, tangents need triangles")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error("MikkTSpace couldn't generate the tangents")]
+    MikktspaceFailed,
+}
+
+/// The triangles of a [`Mesh`] as MikkTSpace sees them, with the tangents it generates.
+struct MikktspaceGeometry<'a> {
+    triangles: Vec<[u32; 3]>,
+    positions: &'a [[f32; 3]],
+    normals: &'a [[f32; 3]],
+    uvs: &'a [[f32; 2]],
+    tangents: Vec<[f32; 4]>,
+}
+
+impl MikktspaceGeometry<'_> {
+    fn index(&self, face: usize, vert: usize) -> usize {
+        self.triangles[face][vert] as usize
+    }
+}
+
+impl mikktspace::Geometry for MikktspaceGeometry<'_> {
+    fn num_faces(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.positions[self.index(face, vert)]
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.normals[self.index(face, vert)]
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.uvs[self.index(face, vert)]
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        let index = self.index(face, vert);
+        self.tangents[index] = tangent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec4;
+
+    use super::*;
+
+    fn quad() -> Mesh {
+        // a square in the XY plane, its texture coordinates follow X and Y
+        let vertices: Vec<Vertex> = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+            .into_iter()
+            .map(|[x, y]| Vertex {
+                position: [x, y, 0.0],
+                normal: [0.0, 0.0, 0.0],
+                tex_coord: [x, y],
+            })
+            .collect();
+        Mesh::from_vertices(&vertices, vec![0, 1, 2, 0, 2, 3])
+    }
+
+    #[test]
+    fn normals_and_tangents_follow_the_faces() {
+        let mut mesh = quad();
+        mesh.compute_smooth_normals();
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+            Some(&VertexAttributeValues::Float32x3(vec![[0.0, 0.0, 1.0]; 4]))
+        );
+        mesh.generate_tangents().unwrap();
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT),
+            Some(&VertexAttributeValues::Float32x4(vec![
+                [1.0, 0.0, 0.0, 1.0];
+                4
+            ]))
+        );
+
+        mesh.compute_flat_normals();
+        assert_eq!(mesh.count_vertices(), 6);
+        assert_eq!(mesh.indices, [0, 1, 2, 3, 4, 5]);
+        // the other attributes are duplicated too
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+                .map(VertexAttributeValues::len),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn tangents_match_the_mikktspace_reference() {
+        // the cube of the regression test of the mikktspace crate, every side is a fan of 4
+        // triangles around its center, with the normals pointing away from the cube's center
+        let sides: [[([f32; 2], [f32; 3]); 5]; 6] = [
+            [
+                ([0.0, 0.0], [1.0, -1.0, 1.0]),
+                ([0.0, 1.0], [1.0, -1.0, -1.0]),
+                ([1.0, 1.0], [1.0, 1.0, -1.0]),
+                ([1.0, 0.0], [1.0, 1.0, 1.0]),
+                ([0.5, 0.5], [1.0, 0.0, 0.0]),
+            ],
+            [
+                ([1.0, 0.0], [-1.0, 1.0, 1.0]),
+                ([1.0, 1.0], [-1.0, 1.0, -1.0]),
+                ([0.0, 1.0], [-1.0, -1.0, -1.0]),
+                ([0.0, 0.0], [-1.0, -1.0, 1.0]),
+                ([0.5, 0.5], [-1.0, 0.0, 0.0]),
+            ],
+            [
+                ([0.0, 0.0], [1.0, 1.0, 1.0]),
+                ([0.0, 1.0], [1.0, 1.0, -1.0]),
+                ([0.0, 1.0], [-1.0, 1.0, -1.0]),
+                ([0.0, 0.0], [-1.0, 1.0, 1.0]),
+                ([0.0, 0.5], [0.0, 1.0, 0.0]),
+            ],
+            [
+                ([0.0, 0.0], [-1.0, -1.0, 1.0]),
+                ([0.0, 1.0], [-1.0, -1.0, -1.0]),
+                ([0.0, 1.0], [1.0, -1.0, -1.0]),
+                ([0.0, 0.0], [1.0, -1.0, 1.0]),
+                ([0.0, 0.5], [0.0, -1.0, 0.0]),
+            ],
+            [
+                ([0.0, 0.0], [-1.0, 1.0, 1.0]),
+                ([0.0, 1.0], [-1.0, -1.0, 1.0]),
+                ([1.0, 1.0], [1.0, -1.0, 1.0]),
+                ([1.0, 0.0], [1.0, 1.0, 1.0]),
+                ([0.5, 0.5], [0.0, 0.0, 1.0]),
+            ],
+            [
+                ([1.0, 0.0], [1.0, 1.0, -1.0]),
+                ([1.0, 1.0], [1.0, -1.0, -1.0]),
+                ([0.0, 1.0], [-1.0, -1.0, -1.0]),
+                ([0.0, 0.0], [-1.0, 1.0, -1.0]),
+                ([0.5, 0.5], [0.0, 0.0, -1.0]),
+            ],
+        ];
+        let vertices: Vec<Vertex> = sides
+            .iter()
+            .flatten()
+            .map(|&(tex_coord, direction)| Vertex {
+                position: (Vec3::from(direction) / 2.0).to_array(),
+                normal: Vec3::from(direction).normalize().to_array(),
+                tex_coord,
+            })
+            .collect();
+        let indices = (0..6)
+            .flat_map(|side| {
+                let base = side * 5;
+                [0, 1, 4, 1, 2, 4, 2, 3, 4, 3, 0, 4].map(|index| base + index)
+            })
+            .collect();
+        let mut mesh = Mesh::from_vertices(&vertices, indices);
+        mesh.generate_tangents().unwrap();
+
+        // the tangents of the +X side computed by the reference implementation
+        let a = 0.408_248_25;
+        let b = 0.816_496_55;
+        let expected = [
+            [a, b, a, -1.0],
+            [a, b, -a, -1.0],
+            [-a, b, a, -1.0],
+            [-a, b, -a, -1.0],
+            [0.0, 1.0, 0.0, -1.0],
+        ];
+        let tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+            Some(VertexAttributeValues::Float32x4(tangents)) => tangents,
+            other => panic!("the tangents are {:?}", other),
+        };
+        for (tangent, expected) in tangents.iter().zip(expected) {
+            assert!(
+                Vec4::from(*tangent).abs_diff_eq(Vec4::from(expected), 1e-6),
+                "{:?} != {:?}",
+                tangent,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn strips_keep_the_winding_of_their_first_triangle() {
+        let mut mesh = quad();
+        mesh.primitive_topology = PrimitiveTopology::TriangleStrip;
+        mesh.indices = vec![0, 1, 3, 2];
+        mesh.compute_smooth_normals();
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+            Some(&VertexAttributeValues::Float32x3(vec![[0.0, 0.0, 1.0]; 4]))
+        );
+
+        mesh.duplicate_vertices();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::TriangleList);
+        assert_eq!(mesh.count_vertices(), 6);
+    }
+
+    #[test]
+    fn attributes_are_interleaved_by_name() {
+        let mesh = quad().with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.0; 4]; 4]);
+        let layout = mesh.vertex_layout();
+        assert_eq!(
+            layout.attributes(),
+            [
+                Mesh::ATTRIBUTE_COLOR,
+                Mesh::ATTRIBUTE_NORMAL,
+                Mesh::ATTRIBUTE_POSITION,
+                Mesh::ATTRIBUTE_UV_0
+            ]
+        );
+        assert_eq!(layout.stride(), 16 + 12 + 12 + 8);
+        assert_eq!(mesh.interleaved_vertices().len(), 4 * layout.stride());
+    }
+}
@@ -1,17 +1,33 @@
+mod aabb;
+mod gpu_mesh;
 mod mesh;
 mod shape;
+mod vertex_attribute;
 
+pub use aabb::*;
+pub use gpu_mesh::*;
 pub use mesh::*;
 pub use shape::*;
+pub use vertex_attribute::*;
 
 use arara_app::{App, Plugin};
 use arara_asset::AddAsset;
+use arara_ecs::schedule::ParallelSystemDescriptorCoercion;
+
+use crate::{RenderStage, ViewSystem};
 
 /// Adds the [`Mesh`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct MeshPlugin;
 
 impl Plugin for MeshPlugin {
     fn build(&self, app: &mut App) {
-        app.add_asset::<Mesh>();
+        app.add_asset::<Mesh>()
+            .init_resource::<MeshAabbs>()
+            .init_non_send_resource::<GpuMeshes>()
+            .add_system_to_stage(RenderStage::Extract, extract_meshes)
+            .add_system_to_stage(
+                RenderStage::Extract,
+                update_mesh_aabbs.before(ViewSystem::CheckVisibility),
+            );
     }
 }
@@ -11,10 +11,11 @@ pub struct Capsule {
     uv_profile: CapsuleUvProfile,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 /// Manner in which UV coordinates are distributed vertically.
 pub enum CapsuleUvProfile {
     /// UV space is distributed by how much of the capsule consists of the hemispheres.
+    #[default]
     Aspect,
     /// Hemispheres get UV space according to the ratio of latitudes to rings.
     Uniform,
@@ -23,12 +24,6 @@ pub enum CapsuleUvProfile {
     Fixed,
 }
 
-impl Default for CapsuleUvProfile {
-    fn default() -> Self {
-        CapsuleUvProfile::Aspect
-    }
-}
-
 impl Capsule {
     pub fn new(
         radius: f32,
@@ -50,6 +45,7 @@ impl Capsule {
 }
 
 impl From<Capsule> for Mesh {
+    #[allow(clippy::needless_range_loop)]
     fn from(capsule: Capsule) -> Mesh {
         let Capsule {
             radius,
@@ -377,6 +373,6 @@ impl From<Capsule> for Mesh {
             i += 1;
         }
 
-        Mesh { vertices, indices }
+        Mesh::from_vertices(&vertices, indices)
     }
 }
@@ -54,7 +54,7 @@ impl From<Circle> for Mesh {
             tex_coord: [0.5, 0.5],
         });
 
-        Self { vertices, indices }
+        Self::from_vertices(&vertices, indices)
     }
 }
 
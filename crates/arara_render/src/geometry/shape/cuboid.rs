@@ -181,6 +181,6 @@ impl From<Cuboid> for Mesh {
             20, 21, 22, 22, 23, 20, // back
         ];
 
-        Self { vertices, indices }
+        Self::from_vertices(&vertices, indices)
     }
 }
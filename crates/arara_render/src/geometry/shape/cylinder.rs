@@ -60,10 +60,8 @@ impl From<Cylinder> for Mesh {
             });
         }
 
-        let mut k1 = 0;
-        let mut k2 = sector_count + 1;
-
-        for _ in 0..sector_count {
+        for k1 in 0..sector_count {
+            let k2 = k1 + sector_count + 1;
             indices.push(k1);
             indices.push(k1 + 1);
             indices.push(k2);
@@ -71,9 +69,6 @@ impl From<Cylinder> for Mesh {
             indices.push(k2);
             indices.push(k1 + 1);
             indices.push(k2 + 1);
-
-            k1 += 1;
-            k2 += 1;
         }
 
         // base circle
@@ -138,6 +133,6 @@ impl From<Cylinder> for Mesh {
             indices.push(top_center_index);
         }
 
-        Mesh { vertices, indices }
+        Mesh::from_vertices(&vertices, indices)
     }
 }
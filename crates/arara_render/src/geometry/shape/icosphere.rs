@@ -88,9 +88,9 @@ impl From<Icosphere> for Mesh {
         let mut vertices = Vec::with_capacity(points.len());
         for i in 0..points.len() {
             vertices.push(Vertex {
-                position: points[i].into(),
-                normal: normals[i].into(),
-                tex_coord: uvs[i].into(),
+                position: points[i],
+                normal: normals[i],
+                tex_coord: uvs[i],
             });
         }
 
@@ -99,6 +99,6 @@ impl From<Icosphere> for Mesh {
             generated.get_indices(i, &mut indices);
         }
 
-        Self { vertices, indices }
+        Self::from_vertices(&vertices, indices)
     }
 }
@@ -65,10 +65,10 @@ impl From<Sphere> for Mesh {
 
         let mut indices: Vec<u32> = Vec::new();
         for i in 0..stack_count {
-            let mut k1 = i * (sector_count + 1);
-            let mut k2 = k1 + sector_count + 1;
+            let first = i * (sector_count + 1);
 
-            for _ in 0..sector_count {
+            for k1 in first..first + sector_count {
+                let k2 = k1 + sector_count + 1;
                 if i != 0 {
                     indices.push(k1);
                     indices.push(k2);
@@ -79,12 +79,9 @@ impl From<Sphere> for Mesh {
                     indices.push(k2);
                     indices.push(k2 + 1);
                 }
-
-                k1 += 1;
-                k2 += 1;
             }
         }
 
-        Self { indices, vertices }
+        Self::from_vertices(&vertices, indices)
     }
 }
@@ -48,6 +48,6 @@ impl From<Square> for Mesh {
 
         let indices = vec![0, 1, 2, 0, 2, 3];
 
-        Self { vertices, indices }
+        Self::from_vertices(&vertices, indices)
     }
 }
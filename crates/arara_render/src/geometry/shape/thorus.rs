@@ -88,16 +88,16 @@ impl From<Thorus> for Mesh {
                 let lb = side + (segment + 1) * n_vertices_per_row;
                 let rb = (side + 1) + (segment + 1) * n_vertices_per_row;
 
-                indices.push(lt as u32);
-                indices.push(rt as u32);
-                indices.push(lb as u32);
+                indices.push(lt);
+                indices.push(rt);
+                indices.push(lb);
 
-                indices.push(rt as u32);
-                indices.push(rb as u32);
-                indices.push(lb as u32);
+                indices.push(rt);
+                indices.push(rb);
+                indices.push(lb);
             }
         }
 
-        Self { vertices, indices }
+        Self::from_vertices(&vertices, indices)
     }
 }
@@ -1,3 +1,4 @@
+/// The standard attributes of a vertex, see [`Mesh::from_vertices`](crate::Mesh::from_vertices).
 #[derive(Copy, Clone, Debug)]
 pub struct Vertex {
     pub position: [f32; 3],
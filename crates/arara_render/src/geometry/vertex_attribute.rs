@@ -0,0 +1,274 @@
+use std::{borrow::Cow, sync::Arc};
+
+use glium::vertex::AttributeType;
+
+/// The format of the values of a [`MeshVertexAttribute`], every format is a multiple of 4 bytes.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum VertexFormat {
+    Float32,
+    Float32x2,
+    Float32x3,
+    Float32x4,
+    Uint32,
+    Uint16x4,
+    /// Read as a `vec4` in `[0, 1]` by the shaders
+    Unorm8x4,
+}
+
+impl VertexFormat {
+    /// The size in bytes of a value.
+    pub fn size(&self) -> usize {
+        match self {
+            VertexFormat::Float32 | VertexFormat::Uint32 | VertexFormat::Unorm8x4 => 4,
+            VertexFormat::Float32x2 | VertexFormat::Uint16x4 => 8,
+            VertexFormat::Float32x3 => 12,
+            VertexFormat::Float32x4 => 16,
+        }
+    }
+
+    /// The glium type of the attribute and whether it is normalized.
+    fn attribute_type(&self) -> (AttributeType, bool) {
+        match self {
+            VertexFormat::Float32 => (AttributeType::F32, false),
+            VertexFormat::Float32x2 => (AttributeType::F32F32, false),
+            VertexFormat::Float32x3 => (AttributeType::F32F32F32, false),
+            VertexFormat::Float32x4 => (AttributeType::F32F32F32F32, false),
+            VertexFormat::Uint32 => (AttributeType::U32, false),
+            VertexFormat::Uint16x4 => (AttributeType::U16U16U16U16, false),
+            VertexFormat::Unorm8x4 => (AttributeType::U8U8U8U8, true),
+        }
+    }
+}
+
+/// A named attribute of the vertices of a [`Mesh`](crate::Mesh), the shaders read it as the
+/// `in` variable of the same name.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct MeshVertexAttribute {
+    pub name: &'static str,
+    pub format: VertexFormat,
+}
+
+impl MeshVertexAttribute {
+    pub const fn new(name: &'static str, format: VertexFormat) -> Self {
+        Self { name, format }
+    }
+
+    /// The shader define set by the pipelines when a mesh has the attribute, `VERTEX_` followed
+    /// by its name in uppercase, for instance `VERTEX_TEX_COORD_1`.
+    pub fn shader_def(&self) -> String {
+        format!("VERTEX_{}", self.name.to_uppercase())
+    }
+}
+
+/// The values of a [`MeshVertexAttribute`], one per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VertexAttributeValues {
+    Float32(Vec<f32>),
+    Float32x2(Vec<[f32; 2]>),
+    Float32x3(Vec<[f32; 3]>),
+    Float32x4(Vec<[f32; 4]>),
+    Uint32(Vec<u32>),
+    Uint16x4(Vec<[u16; 4]>),
+    Unorm8x4(Vec<[u8; 4]>),
+}
+
+impl VertexAttributeValues {
+    pub fn len(&self) -> usize {
+        match self {
+            VertexAttributeValues::Float32(values) => values.len(),
+            VertexAttributeValues::Float32x2(values) => values.len(),
+            VertexAttributeValues::Float32x3(values) => values.len(),
+            VertexAttributeValues::Float32x4(values) => values.len(),
+            VertexAttributeValues::Uint32(values) => values.len(),
+            VertexAttributeValues::Uint16x4(values) => values.len(),
+            VertexAttributeValues::Unorm8x4(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn format(&self) -> VertexFormat {
+        match self {
+            VertexAttributeValues::Float32(_) => VertexFormat::Float32,
+            VertexAttributeValues::Float32x2(_) => VertexFormat::Float32x2,
+            VertexAttributeValues::Float32x3(_) => VertexFormat::Float32x3,
+            VertexAttributeValues::Float32x4(_) => VertexFormat::Float32x4,
+            VertexAttributeValues::Uint32(_) => VertexFormat::Uint32,
+            VertexAttributeValues::Uint16x4(_) => VertexFormat::Uint16x4,
+            VertexAttributeValues::Unorm8x4(_) => VertexFormat::Unorm8x4,
+        }
+    }
+
+    /// The values of the [`VertexFormat::Float32x2`] attributes, like texture coordinates.
+    pub fn as_float2(&self) -> Option<&[[f32; 2]]> {
+        match self {
+            VertexAttributeValues::Float32x2(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The values of the [`VertexFormat::Float32x3`] attributes, like positions and normals.
+    pub fn as_float3(&self) -> Option<&[[f32; 3]]> {
+        match self {
+            VertexAttributeValues::Float32x3(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Appends the value of the vertex at `index` to `bytes`, in native endianness.
+    pub fn write_bytes(&self, index: usize, bytes: &mut Vec<u8>) {
+        match self {
+            VertexAttributeValues::Float32(values) => bytes.extend(values[index].to_ne_bytes()),
+            VertexAttributeValues::Float32x2(values) => {
+                bytes.extend(values[index].iter().flat_map(|value| value.to_ne_bytes()))
+            }
+            VertexAttributeValues::Float32x3(values) => {
+                bytes.extend(values[index].iter().flat_map(|value| value.to_ne_bytes()))
+            }
+            VertexAttributeValues::Float32x4(values) => {
+                bytes.extend(values[index].iter().flat_map(|value| value.to_ne_bytes()))
+            }
+            VertexAttributeValues::Uint32(values) => bytes.extend(values[index].to_ne_bytes()),
+            VertexAttributeValues::Uint16x4(values) => {
+                bytes.extend(values[index].iter().flat_map(|value| value.to_ne_bytes()))
+            }
+            VertexAttributeValues::Unorm8x4(values) => bytes.extend(values[index]),
+        }
+    }
+
+    /// The values of the vertices at `indices`, in order.
+    pub(crate) fn gather(&self, indices: &[u32]) -> Self {
+        fn gather<T: Copy>(values: &[T], indices: &[u32]) -> Vec<T> {
+            indices
+                .iter()
+                .map(|&index| values[index as usize])
+                .collect()
+        }
+        match self {
+            VertexAttributeValues::Float32(values) => {
+                VertexAttributeValues::Float32(gather(values, indices))
+            }
+            VertexAttributeValues::Float32x2(values) => {
+                VertexAttributeValues::Float32x2(gather(values, indices))
+            }
+            VertexAttributeValues::Float32x3(values) => {
+                VertexAttributeValues::Float32x3(gather(values, indices))
+            }
+            VertexAttributeValues::Float32x4(values) => {
+                VertexAttributeValues::Float32x4(gather(values, indices))
+            }
+            VertexAttributeValues::Uint32(values) => {
+                VertexAttributeValues::Uint32(gather(values, indices))
+            }
+            VertexAttributeValues::Uint16x4(values) => {
+                VertexAttributeValues::Uint16x4(gather(values, indices))
+            }
+            VertexAttributeValues::Unorm8x4(values) => {
+                VertexAttributeValues::Unorm8x4(gather(values, indices))
+            }
+        }
+    }
+}
+
+impl From<Vec<f32>> for VertexAttributeValues {
+    fn from(values: Vec<f32>) -> Self {
+        VertexAttributeValues::Float32(values)
+    }
+}
+
+impl From<Vec<[f32; 2]>> for VertexAttributeValues {
+    fn from(values: Vec<[f32; 2]>) -> Self {
+        VertexAttributeValues::Float32x2(values)
+    }
+}
+
+impl From<Vec<[f32; 3]>> for VertexAttributeValues {
+    fn from(values: Vec<[f32; 3]>) -> Self {
+        VertexAttributeValues::Float32x3(values)
+    }
+}
+
+impl From<Vec<[f32; 4]>> for VertexAttributeValues {
+    fn from(values: Vec<[f32; 4]>) -> Self {
+        VertexAttributeValues::Float32x4(values)
+    }
+}
+
+impl From<Vec<u32>> for VertexAttributeValues {
+    fn from(values: Vec<u32>) -> Self {
+        VertexAttributeValues::Uint32(values)
+    }
+}
+
+impl From<Vec<[u16; 4]>> for VertexAttributeValues {
+    fn from(values: Vec<[u16; 4]>) -> Self {
+        VertexAttributeValues::Uint16x4(values)
+    }
+}
+
+impl From<Vec<[u8; 4]>> for VertexAttributeValues {
+    fn from(values: Vec<[u8; 4]>) -> Self {
+        VertexAttributeValues::Unorm8x4(values)
+    }
+}
+
+/// The attributes of the vertices of a [`Mesh`](crate::Mesh), interleaved in this order.
+///
+/// Part of the pipeline keys of the meshes, so the shaders can be specialized on the
+/// [`MeshVertexAttribute::shader_def`]s.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub struct MeshVertexLayout {
+    attributes: Arc<[MeshVertexAttribute]>,
+}
+
+impl MeshVertexLayout {
+    pub fn new(attributes: impl IntoIterator<Item = MeshVertexAttribute>) -> Self {
+        Self {
+            attributes: attributes.into_iter().collect(),
+        }
+    }
+
+    pub fn attributes(&self) -> &[MeshVertexAttribute] {
+        &self.attributes
+    }
+
+    pub fn contains(&self, attribute: MeshVertexAttribute) -> bool {
+        self.attributes.contains(&attribute)
+    }
+
+    /// The size in bytes of a vertex.
+    pub fn stride(&self) -> usize {
+        self.attributes
+            .iter()
+            .map(|attribute| attribute.format.size())
+            .sum()
+    }
+
+    /// The shader defines of every attribute.
+    pub fn shader_defs(&self) -> impl Iterator<Item = String> + '_ {
+        self.attributes.iter().map(MeshVertexAttribute::shader_def)
+    }
+
+    /// The glium bindings of the interleaved attributes.
+    pub(crate) fn bindings(&self) -> glium::vertex::VertexFormat {
+        let mut offset = 0;
+        let bindings: Vec<_> = self
+            .attributes
+            .iter()
+            .map(|attribute| {
+                let (attribute_type, normalize) = attribute.format.attribute_type();
+                let binding = (
+                    Cow::Borrowed(attribute.name),
+                    offset,
+                    attribute_type,
+                    normalize,
+                );
+                offset += attribute.format.size();
+                binding
+            })
+            .collect();
+        Cow::Owned(bindings)
+    }
+}
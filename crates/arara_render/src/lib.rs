@@ -1,29 +1,50 @@
+#![allow(clippy::module_inception)]
+
 mod billboard;
+mod capabilities;
 mod clear_color;
 mod color;
+mod debug_lines;
 mod frame_executor;
 mod geometry;
+mod post_process;
 mod render_phase;
 mod render_resource;
+mod screenshot;
 mod shader;
 mod texture;
 mod view;
 
 pub use billboard::*;
+pub use capabilities::*;
 pub use clear_color::*;
 pub use color::*;
+pub use debug_lines::*;
 use frame_executor::draw_frame;
 pub use geometry::*;
+pub use post_process::*;
 pub use render_phase::*;
 pub use render_resource::*;
+pub use screenshot::*;
 pub use shader::*;
 pub use texture::*;
 pub use view::*;
 
 pub mod prelude {
     pub use crate::{
-        billboard::Billboard, clear_color::ClearColor, color::*, geometry::*, texture::Image,
-        view::Visibility, RenderPlugin,
+        billboard::Billboard,
+        clear_color::ClearColor,
+        color::*,
+        debug_lines::{DebugLines, DebugLinesPlugin},
+        geometry::*,
+        post_process::{Bloom, CustomPass, PostProcessPass, PostProcessing, Tonemapping},
+        screenshot::{ScreenshotCaptured, ScreenshotFailed, ScreenshotManager},
+        texture::{
+            Cubemap, DefaultImageSampler, FilterMode, Image, ImageSampler, PixelFormat,
+            SamplerDescriptor, WrapMode,
+        },
+        view::{NoFrustumCulling, RenderLayers, RenderTarget, Visibility},
+        RenderPlugin,
     };
 }
 
@@ -61,22 +82,50 @@ pub struct RenderPlugin;
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ClearColor>()
+        app.init_resource::<RenderCapabilities>()
+            .init_resource::<ClearColor>()
             .init_resource::<RenderPhases>()
-            .init_non_send_resource::<RenderPipelineCache>();
+            .init_resource::<ScreenshotManager>()
+            .add_event::<ScreenshotCaptured>()
+            .add_event::<ScreenshotFailed>()
+            .init_non_send_resource::<RenderPipelineCache>()
+            .init_non_send_resource::<RenderTargets>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_camera_target_sizes);
+
+        let capabilities = app
+            .world
+            .get_resource::<RenderCapabilities>()
+            .unwrap()
+            .clone();
+        let mut pipeline_cache = app
+            .world
+            .get_non_send_resource_mut::<RenderPipelineCache>()
+            .unwrap();
+        if capabilities.bindless_textures {
+            pipeline_cache.set_global_define("BINDLESS_TEXTURES", 1);
+        }
+        pipeline_cache.set_max_glsl_version(capabilities.glsl_version, capabilities.gles);
 
         app.schedule
             .add_stage_before(
                 CoreStage::PreUpdate,
                 RenderStage::Extract,
                 SystemStage::parallel()
-                    .with_system(extract_cameras)
+                    // exclusive so the views of the new cameras are inserted before the
+                    // visibility checks of their first frame
+                    .with_system(
+                        extract_cameras
+                            .exclusive_system()
+                            .at_start()
+                            .label(ViewSystem::ExtractCameras),
+                    )
+                    .with_system(check_visibility.label(ViewSystem::CheckVisibility))
                     .with_system(extract_shaders),
             )
             .add_stage_after(
                 RenderStage::Extract,
                 RenderStage::Prepare,
-                SystemStage::parallel(),
+                SystemStage::parallel().with_system(prepare_render_targets),
             )
             .add_stage_after(
                 RenderStage::Prepare,
@@ -104,6 +153,7 @@ impl Plugin for RenderPlugin {
         app.add_plugin(shader::ShaderPlugin)
             .add_plugin(geometry::MeshPlugin)
             .add_plugin(texture::ImagePlugin)
+            .add_plugin(post_process::PostProcessPlugin)
             .add_plugin(billboard::BillboardPlugin);
     }
 }
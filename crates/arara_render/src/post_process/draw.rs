@@ -0,0 +1,276 @@
+use arara_ecs::{entity::Entity, world::World};
+use arara_utils::tracing::error;
+use glam::Vec2;
+use glium::{
+    backend::Facade,
+    framebuffer::SimpleFrameBuffer,
+    index::{NoIndices, PrimitiveType},
+    uniforms::{
+        MagnifySamplerFilter, MinifySamplerFilter, SamplerBehavior, SamplerWrapFunction,
+        UniformValue, Uniforms,
+    },
+    vertex::EmptyVertexAttributes,
+    Texture2d,
+};
+
+use crate::{
+    Bloom, ClearColor, FrameError, PostProcessPass, PostProcessPipeline, PostProcessTextures,
+    PostProcessUniform, PostProcessing, RenderPhases, RenderPipeline, RenderPipelineCache,
+    SpecializedPipelines, TrackedFrame, ViewPostProcessTextures,
+};
+
+/// A [`PostProcessPass`] whose pipelines are compiled.
+enum Step<'a> {
+    Fullscreen {
+        pipeline: &'a RenderPipeline,
+        uniforms: Vec<(String, PostProcessUniform)>,
+    },
+    Bloom {
+        threshold: &'a RenderPipeline,
+        blur: &'a RenderPipeline,
+        composite: &'a RenderPipeline,
+        bloom: Bloom,
+    },
+}
+
+/// Draws `view` into its HDR texture then runs its passes, the last one writing into `frame`.
+///
+/// Returns `false` if the view has no [`PostProcessing`], it should then be drawn as usual.
+pub(crate) fn draw_post_processed_view(
+    world: &World,
+    phases: &RenderPhases,
+    view: Entity,
+    frame: &mut TrackedFrame,
+) -> bool {
+    let post_processing = match world.get::<PostProcessing>(view) {
+        Some(post_processing) if !post_processing.passes.is_empty() => post_processing,
+        _ => return false,
+    };
+    let textures = match world
+        .get_non_send_resource::<PostProcessTextures>()
+        .unwrap()
+        .get(view)
+    {
+        Some(textures) => textures,
+        None => return false,
+    };
+
+    let clear_color = world.get_resource::<ClearColor>().unwrap();
+    let facade = frame.facade();
+    let result = SimpleFrameBuffer::with_depth_buffer(facade, &textures.main, &textures.depth)
+        .map_err(FrameError::from)
+        .and_then(|framebuffer| {
+            let mut main_frame = TrackedFrame::offscreen(facade, framebuffer);
+            main_frame.clear_color_and_depth(clear_color.0);
+            phases.run(world, view, &mut main_frame);
+
+            let steps = ready_steps(world, post_processing);
+            if steps.is_empty() {
+                frame.blit_color_from(&textures.main);
+                return Ok(());
+            }
+            run_steps(facade, &steps, textures, frame)
+        });
+    if let Err(err) = result {
+        error!("Failed to post process {:?}: {}", view, err);
+    }
+    true
+}
+
+/// The passes of `post_processing` ready to be drawn, the others are skipped.
+fn ready_steps<'w>(world: &'w World, post_processing: &PostProcessing) -> Vec<Step<'w>> {
+    let pipeline = world.get_resource::<PostProcessPipeline>().unwrap();
+    let pipelines = world
+        .get_resource::<SpecializedPipelines<PostProcessPipeline>>()
+        .unwrap();
+    let pipeline_cache = world
+        .get_non_send_resource::<RenderPipelineCache>()
+        .unwrap();
+    let get = |shader| pipelines.get(shader).and_then(|id| pipeline_cache.get(id));
+
+    let mut steps = Vec::new();
+    for pass in post_processing.passes.iter() {
+        let step = match pass {
+            PostProcessPass::Bloom(bloom) => match (
+                get(&pipeline.bloom_threshold_shader),
+                get(&pipeline.bloom_blur_shader),
+                get(&pipeline.bloom_composite_shader),
+            ) {
+                (Some(threshold), Some(blur), Some(composite)) => Some(Step::Bloom {
+                    threshold,
+                    blur,
+                    composite,
+                    bloom: *bloom,
+                }),
+                _ => None,
+            },
+            PostProcessPass::Tonemapping(tonemapping) => {
+                get(&pipeline.tonemapping_shader).map(|pipeline| Step::Fullscreen {
+                    pipeline,
+                    uniforms: vec![("u_exposure".to_string(), tonemapping.exposure.into())],
+                })
+            }
+            PostProcessPass::Fxaa => get(&pipeline.fxaa_shader).map(|pipeline| Step::Fullscreen {
+                pipeline,
+                uniforms: Vec::new(),
+            }),
+            PostProcessPass::Custom(pass) => {
+                get(&pass.fragment_shader).map(|pipeline| Step::Fullscreen {
+                    pipeline,
+                    uniforms: pass.uniforms.clone(),
+                })
+            }
+        };
+        if let Some(step) = step {
+            steps.push(step);
+        }
+    }
+    steps
+}
+
+fn run_steps(
+    facade: &dyn Facade,
+    steps: &[Step],
+    textures: &ViewPostProcessTextures,
+    frame: &mut TrackedFrame,
+) -> Result<(), FrameError> {
+    let mut input = &textures.main;
+    for (i, step) in steps.iter().enumerate() {
+        // the last step writes into the frame, the others alternate between the swap textures
+        let output = if i + 1 == steps.len() {
+            None
+        } else {
+            Some(&textures.swap[i % 2])
+        };
+
+        match step {
+            Step::Fullscreen { pipeline, uniforms } => {
+                let uniforms = PassUniforms::new(input, uniforms);
+                draw_to(facade, output, frame, pipeline, &uniforms)?;
+            }
+            Step::Bloom {
+                threshold,
+                blur,
+                composite,
+                bloom,
+            } => {
+                let [bloom_texture, blur_texture] = &textures.bloom;
+                let uniforms = [("u_threshold".to_string(), bloom.threshold.into())];
+                let mut bloom_frame = offscreen(facade, bloom_texture)?;
+                draw(
+                    &mut bloom_frame,
+                    threshold,
+                    &PassUniforms::new(input, &uniforms),
+                )?;
+
+                let horizontal = [("u_direction".to_string(), Vec2::X.into())];
+                let vertical = [("u_direction".to_string(), Vec2::Y.into())];
+                for _ in 0..bloom.iterations {
+                    let mut blur_frame = offscreen(facade, blur_texture)?;
+                    let uniforms = PassUniforms::new(bloom_texture, &horizontal);
+                    draw(&mut blur_frame, blur, &uniforms)?;
+                    let uniforms = PassUniforms::new(blur_texture, &vertical);
+                    draw(&mut bloom_frame, blur, &uniforms)?;
+                }
+
+                let uniforms = [("u_intensity".to_string(), bloom.intensity.into())];
+                let uniforms = PassUniforms {
+                    bloom: Some(bloom_texture),
+                    ..PassUniforms::new(input, &uniforms)
+                };
+                draw_to(facade, output, frame, composite, &uniforms)?;
+            }
+        }
+
+        if let Some(output) = output {
+            input = output;
+        }
+    }
+    Ok(())
+}
+
+fn offscreen<'a>(
+    facade: &'a dyn Facade,
+    texture: &'a Texture2d,
+) -> Result<TrackedFrame<'a>, FrameError> {
+    Ok(TrackedFrame::offscreen(
+        facade,
+        SimpleFrameBuffer::new(facade, texture)?,
+    ))
+}
+
+/// Draws into `output`, or into `frame` if there is none.
+fn draw_to(
+    facade: &dyn Facade,
+    output: Option<&Texture2d>,
+    frame: &mut TrackedFrame,
+    pipeline: &RenderPipeline,
+    uniforms: &PassUniforms,
+) -> Result<(), FrameError> {
+    match output {
+        Some(texture) => draw(&mut offscreen(facade, texture)?, pipeline, uniforms),
+        None => draw(frame, pipeline, uniforms),
+    }
+}
+
+/// Draws a triangle covering the whole frame, the vertex shader computes its vertices.
+fn draw(
+    frame: &mut TrackedFrame,
+    pipeline: &RenderPipeline,
+    uniforms: &PassUniforms,
+) -> Result<(), FrameError> {
+    frame.draw(
+        EmptyVertexAttributes { len: 3 },
+        NoIndices(PrimitiveType::TrianglesList),
+        &pipeline.program,
+        uniforms,
+        &pipeline.parameters,
+    )?;
+    Ok(())
+}
+
+struct PassUniforms<'a> {
+    input: &'a Texture2d,
+    bloom: Option<&'a Texture2d>,
+    values: &'a [(String, PostProcessUniform)],
+}
+
+impl<'a> PassUniforms<'a> {
+    fn new(input: &'a Texture2d, values: &'a [(String, PostProcessUniform)]) -> Self {
+        Self {
+            input,
+            bloom: None,
+            values,
+        }
+    }
+}
+
+impl Uniforms for PassUniforms<'_> {
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut output: F) {
+        let sampler = SamplerBehavior {
+            wrap_function: (
+                SamplerWrapFunction::Clamp,
+                SamplerWrapFunction::Clamp,
+                SamplerWrapFunction::Clamp,
+            ),
+            minify_filter: MinifySamplerFilter::Linear,
+            magnify_filter: MagnifySamplerFilter::Linear,
+            ..Default::default()
+        };
+        let (width, height) = self.input.dimensions();
+        output(
+            "u_color",
+            UniformValue::Texture2d(self.input, Some(sampler)),
+        );
+        output(
+            "u_texel_size",
+            UniformValue::Vec2([1.0 / width as f32, 1.0 / height as f32]),
+        );
+        if let Some(bloom) = self.bloom {
+            output("u_bloom", UniformValue::Texture2d(bloom, Some(sampler)));
+        }
+        for (name, value) in self.values.iter() {
+            output(name, value.as_uniform_value());
+        }
+    }
+}
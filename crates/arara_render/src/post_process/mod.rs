@@ -0,0 +1,184 @@
+mod draw;
+mod pipeline;
+mod textures;
+
+pub(crate) use draw::draw_post_processed_view;
+pub use pipeline::*;
+pub use textures::*;
+
+use arara_app::{App, Plugin};
+use arara_asset::Handle;
+use arara_ecs::prelude::*;
+use glam::{Vec2, Vec3, Vec4};
+use glium::uniforms::UniformValue;
+
+use crate::{Color, RenderStage, Shader, SpecializedPipelines};
+
+/// Fullscreen passes applied in order to the image of a camera.
+///
+/// The camera is drawn into an HDR texture first, then every pass reads the output of the
+/// previous one and the last pass writes into the camera viewport of the render target.
+///
+/// ```ignore
+/// commands
+///     .spawn_bundle(PerspectiveCameraBundle::default())
+///     .insert(PostProcessing::new(vec![
+///         PostProcessPass::Bloom(Bloom::default()),
+///         PostProcessPass::Tonemapping(Tonemapping::default()),
+///         PostProcessPass::Fxaa,
+///     ]));
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct PostProcessing {
+    pub passes: Vec<PostProcessPass>,
+}
+
+impl PostProcessing {
+    pub fn new(passes: Vec<PostProcessPass>) -> Self {
+        Self { passes }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PostProcessPass {
+    /// Adds a blurred copy of the brightest pixels.
+    Bloom(Bloom),
+    /// Maps HDR colors to the displayable range.
+    Tonemapping(Tonemapping),
+    /// Fast approximate anti-aliasing, best applied after the tonemapping.
+    Fxaa,
+    Custom(CustomPass),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Bloom {
+    /// Pixels brighter than this start to bloom.
+    pub threshold: f32,
+    /// Strength of the added blur.
+    pub intensity: f32,
+    /// Amount of times the blur is applied, more iterations spread the glow further.
+    pub iterations: u32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            intensity: 0.3,
+            iterations: 2,
+        }
+    }
+}
+
+/// The ACES filmic curve.
+#[derive(Debug, Clone, Copy)]
+pub struct Tonemapping {
+    /// Multiplied with the colors before the curve is applied.
+    pub exposure: f32,
+}
+
+impl Default for Tonemapping {
+    fn default() -> Self {
+        Self { exposure: 1.0 }
+    }
+}
+
+/// A user defined pass.
+///
+/// The fragment shader receives the texture coordinates as `in vec2 v_uv`, the output of the
+/// previous pass as `uniform sampler2D u_color` and the size of one of its pixels as
+/// `uniform vec2 u_texel_size`, along with the `uniforms` of the pass.
+#[derive(Debug, Clone)]
+pub struct CustomPass {
+    pub fragment_shader: Handle<Shader>,
+    pub uniforms: Vec<(String, PostProcessUniform)>,
+}
+
+impl CustomPass {
+    pub fn new(fragment_shader: Handle<Shader>) -> Self {
+        Self {
+            fragment_shader,
+            uniforms: Vec::new(),
+        }
+    }
+
+    pub fn with_uniform(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<PostProcessUniform>,
+    ) -> Self {
+        self.uniforms.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// The value of a uniform of a [`CustomPass`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostProcessUniform {
+    Float(f32),
+    Int(i32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+}
+
+impl PostProcessUniform {
+    pub fn as_uniform_value(&self) -> UniformValue<'static> {
+        match *self {
+            PostProcessUniform::Float(value) => UniformValue::Float(value),
+            PostProcessUniform::Int(value) => UniformValue::SignedInt(value),
+            PostProcessUniform::Vec2(value) => UniformValue::Vec2(value.into()),
+            PostProcessUniform::Vec3(value) => UniformValue::Vec3(value.into()),
+            PostProcessUniform::Vec4(value) => UniformValue::Vec4(value.into()),
+        }
+    }
+}
+
+impl From<f32> for PostProcessUniform {
+    fn from(value: f32) -> Self {
+        PostProcessUniform::Float(value)
+    }
+}
+
+impl From<i32> for PostProcessUniform {
+    fn from(value: i32) -> Self {
+        PostProcessUniform::Int(value)
+    }
+}
+
+impl From<Vec2> for PostProcessUniform {
+    fn from(value: Vec2) -> Self {
+        PostProcessUniform::Vec2(value)
+    }
+}
+
+impl From<Vec3> for PostProcessUniform {
+    fn from(value: Vec3) -> Self {
+        PostProcessUniform::Vec3(value)
+    }
+}
+
+impl From<Vec4> for PostProcessUniform {
+    fn from(value: Vec4) -> Self {
+        PostProcessUniform::Vec4(value)
+    }
+}
+
+impl From<Color> for PostProcessUniform {
+    fn from(color: Color) -> Self {
+        PostProcessUniform::Vec4(Vec4::from(color.as_rgba_f32()))
+    }
+}
+
+/// Adds the [`PostProcessing`] of the cameras.
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PostProcessPipeline>()
+            .init_resource::<SpecializedPipelines<PostProcessPipeline>>()
+            .init_non_send_resource::<PostProcessTextures>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_post_process_textures)
+            .add_system_to_stage(RenderStage::Queue, queue_post_process_pipelines);
+    }
+}
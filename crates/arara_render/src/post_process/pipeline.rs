@@ -0,0 +1,79 @@
+use arara_asset::{AssetServer, Handle};
+use arara_ecs::prelude::*;
+
+use crate::{
+    ExtractedView, PostProcessPass, PostProcessing, RenderPipelineCache, RenderPipelineDescriptor,
+    Shader, SpecializedPipeline, SpecializedPipelines,
+};
+
+/// The shaders of the built-in post process passes, all drawn with the same fullscreen
+/// triangle vertex shader.
+pub struct PostProcessPipeline {
+    pub vertex_shader: Handle<Shader>,
+    pub tonemapping_shader: Handle<Shader>,
+    pub fxaa_shader: Handle<Shader>,
+    pub bloom_threshold_shader: Handle<Shader>,
+    pub bloom_blur_shader: Handle<Shader>,
+    pub bloom_composite_shader: Handle<Shader>,
+}
+
+impl FromWorld for PostProcessPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        Self {
+            vertex_shader: asset_server.load("shaders/fullscreen.vert"),
+            tonemapping_shader: asset_server.load("shaders/tonemapping.frag"),
+            fxaa_shader: asset_server.load("shaders/fxaa.frag"),
+            bloom_threshold_shader: asset_server.load("shaders/bloom_threshold.frag"),
+            bloom_blur_shader: asset_server.load("shaders/bloom_blur.frag"),
+            bloom_composite_shader: asset_server.load("shaders/bloom_composite.frag"),
+        }
+    }
+}
+
+impl PostProcessPipeline {
+    /// The fragment shaders drawn by `pass`.
+    pub fn fragment_shaders(&self, pass: &PostProcessPass) -> Vec<Handle<Shader>> {
+        match pass {
+            PostProcessPass::Bloom(_) => vec![
+                self.bloom_threshold_shader.clone_weak(),
+                self.bloom_blur_shader.clone_weak(),
+                self.bloom_composite_shader.clone_weak(),
+            ],
+            PostProcessPass::Tonemapping(_) => vec![self.tonemapping_shader.clone_weak()],
+            PostProcessPass::Fxaa => vec![self.fxaa_shader.clone_weak()],
+            PostProcessPass::Custom(pass) => vec![pass.fragment_shader.clone_weak()],
+        }
+    }
+}
+
+impl SpecializedPipeline for PostProcessPipeline {
+    /// The fragment shader of the pass
+    type Key = Handle<Shader>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex_shader: self.vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
+            fragment_shader: key,
+            shader_defs: Vec::new(),
+            draw_parameters: Default::default(),
+        }
+    }
+}
+
+pub(crate) fn queue_post_process_pipelines(
+    views: Query<&PostProcessing, With<ExtractedView>>,
+    mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
+    pipeline: Res<PostProcessPipeline>,
+    mut pipelines: ResMut<SpecializedPipelines<PostProcessPipeline>>,
+) {
+    for post_processing in views.iter() {
+        for pass in post_processing.passes.iter() {
+            for shader in pipeline.fragment_shaders(pass) {
+                pipelines.specialize(&mut render_pipeline_cache, &pipeline, shader);
+            }
+        }
+    }
+}
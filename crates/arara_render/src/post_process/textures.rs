@@ -0,0 +1,107 @@
+use arara_asset::Assets;
+use arara_ecs::prelude::*;
+use arara_utils::{
+    tracing::{error, trace},
+    HashMap, HashSet,
+};
+use arara_window::Window;
+use glam::UVec2;
+use glium::{
+    backend::Facade,
+    texture::{DepthTexture2d, MipmapsOption, TextureCreationError, UncompressedFloatFormat},
+    Texture2d,
+};
+
+use crate::{ExtractedView, Image, PostProcessing};
+
+/// The HDR textures a post processed camera is drawn into, with the size of its viewport.
+pub struct ViewPostProcessTextures {
+    pub main: Texture2d,
+    pub depth: DepthTexture2d,
+    /// Written alternately by the passes between the main texture and the render target
+    pub swap: [Texture2d; 2],
+    /// Half resolution textures used by the [`Bloom`](crate::Bloom)
+    pub bloom: [Texture2d; 2],
+}
+
+impl ViewPostProcessTextures {
+    pub fn new(facade: &dyn Facade, size: UVec2) -> Result<Self, TextureCreationError> {
+        let bloom_size = (size / 2).max(UVec2::ONE);
+        Ok(Self {
+            main: hdr_texture(facade, size)?,
+            depth: DepthTexture2d::empty(facade, size.x, size.y)?,
+            swap: [hdr_texture(facade, size)?, hdr_texture(facade, size)?],
+            bloom: [
+                hdr_texture(facade, bloom_size)?,
+                hdr_texture(facade, bloom_size)?,
+            ],
+        })
+    }
+
+    pub fn size(&self) -> UVec2 {
+        UVec2::from(self.main.dimensions())
+    }
+}
+
+fn hdr_texture(facade: &dyn Facade, size: UVec2) -> Result<Texture2d, TextureCreationError> {
+    Texture2d::empty_with_format(
+        facade,
+        UncompressedFloatFormat::F16F16F16F16,
+        MipmapsOption::NoMipmap,
+        size.x,
+        size.y,
+    )
+}
+
+/// The [`ViewPostProcessTextures`] of every active camera with [`PostProcessing`].
+#[derive(Default)]
+pub struct PostProcessTextures {
+    views: HashMap<Entity, ViewPostProcessTextures>,
+}
+
+impl PostProcessTextures {
+    pub fn get(&self, view: Entity) -> Option<&ViewPostProcessTextures> {
+        self.views.get(&view)
+    }
+}
+
+/// Allocates the textures of the post processed cameras, reallocating them when the viewport
+/// is resized.
+pub(crate) fn prepare_post_process_textures(
+    window: NonSend<Window>,
+    images: Res<Assets<Image>>,
+    views: Query<(Entity, &ExtractedView, &PostProcessing)>,
+    mut textures: NonSendMut<PostProcessTextures>,
+) {
+    let mut used = HashSet::default();
+    for (entity, view, post_processing) in views.iter() {
+        if post_processing.passes.is_empty() {
+            continue;
+        }
+        let target_size = match view.target.physical_size(&window, &images) {
+            Some(size) => size,
+            None => continue,
+        };
+        let size = match view.viewport {
+            Some(viewport) => viewport.physical_rect(target_size).1,
+            None => target_size.max(UVec2::ONE),
+        };
+        used.insert(entity);
+
+        if matches!(textures.get(entity), Some(view_textures) if view_textures.size() == size) {
+            continue;
+        }
+        trace!(
+            "allocating post process textures of {:?} of size {}",
+            entity,
+            size
+        );
+        match ViewPostProcessTextures::new(window.facade(), size) {
+            Ok(view_textures) => {
+                textures.views.insert(entity, view_textures);
+            }
+            Err(err) => error!("Failed to create the post process textures: {}", err),
+        }
+    }
+    textures.views.retain(|entity, _| used.contains(entity));
+}
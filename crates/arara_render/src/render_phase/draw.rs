@@ -1,6 +1,6 @@
 use std::any::TypeId;
 
-use arara_ecs::world::World;
+use arara_ecs::{entity::Entity, world::World};
 use arara_utils::HashMap;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
@@ -12,8 +12,8 @@ pub struct DrawFunctionId(usize);
 
 /// A draw function which is used to draw a specific [`PhaseItem`].
 pub trait Draw<P: PhaseItem>: Send + Sync + 'static {
-    /// Draws the [`PhaseItem`] by issuing draw calls via the [`TrackedFrame`].
-    fn draw<'w>(&mut self, world: &'w World, frame: &mut TrackedFrame, item: &P);
+    /// Draws the [`PhaseItem`] of the `view` by issuing draw calls via the [`TrackedFrame`].
+    fn draw(&mut self, world: &World, frame: &mut TrackedFrame, view: Entity, item: &P);
 }
 
 /// Stores all draw functions for the [`PhaseItem`] type.
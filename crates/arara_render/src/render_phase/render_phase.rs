@@ -1,4 +1,9 @@
-use arara_ecs::{entity::Entity, prelude::ResMut, system::Commands};
+use arara_camera::Camera;
+use arara_ecs::{
+    entity::Entity,
+    prelude::{Component, With, Without},
+    system::{Commands, Query},
+};
 
 use crate::{render_phase::DrawFunctionId, CachedPipelineId};
 
@@ -24,7 +29,9 @@ pub trait CachedPipelinePhaseItem: PhaseItem {
     fn cached_pipeline(&self) -> CachedPipelineId;
 }
 
-/// A resource to collect and sort draw requests for specific [`PhaseItems`](PhaseItem).
+/// A component of the camera entities collecting and sorting the draw requests of specific
+/// [`PhaseItems`](PhaseItem) for that view.
+#[derive(Component)]
 pub struct RenderPhase<I: PhaseItem> {
     pub items: Vec<I>,
 }
@@ -48,17 +55,37 @@ impl<I: PhaseItem> RenderPhase<I> {
     }
 }
 
+/// This system adds a [`RenderPhase`] of the [`PhaseItem`] type to every [`Camera`].
+pub fn extract_camera_phases<I: PhaseItem>(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<Camera>, Without<RenderPhase<I>>)>,
+) {
+    for entity in cameras.iter() {
+        commands.entity(entity).insert(RenderPhase::<I>::default());
+    }
+}
+
 /// This system sorts all [`RenderPhases`](RenderPhase) for the [`PhaseItem`] type.
-pub fn sort_phase_system<I: PhaseItem>(mut render_phase: ResMut<RenderPhase<I>>) {
-    render_phase.sort();
+pub fn sort_phase_system<I: PhaseItem>(mut render_phases: Query<&mut RenderPhase<I>>) {
+    for mut phase in render_phases.iter_mut() {
+        phase.sort();
+    }
+}
+
+/// This system removes the items of every [`RenderPhase`] of the [`PhaseItem`] type.
+pub fn clear_phase_system<I: PhaseItem>(mut render_phases: Query<&mut RenderPhase<I>>) {
+    for mut phase in render_phases.iter_mut() {
+        phase.items.clear();
+    }
 }
 
-pub fn clear_phase_system<I: EntityPhaseItem>(
+/// Despawns the entities spawned every frame by the render stages, like the batches referenced by
+/// the [`PhaseItems`](EntityPhaseItem) of every view.
+pub fn despawn_render_entities<C: Component>(
     mut commands: Commands,
-    mut phase: ResMut<RenderPhase<I>>,
+    query: Query<Entity, With<C>>,
 ) {
-    for item in phase.items.iter() {
-        commands.entity(item.entity()).despawn();
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
     }
-    phase.items.clear();
 }
@@ -1,6 +1,6 @@
 use std::any::TypeId;
 
-use arara_ecs::world::World;
+use arara_ecs::{entity::Entity, world::World};
 use arara_utils::HashMap;
 
 use crate::{DrawFunctions, PhaseItem, RenderPhase, TrackedFrame};
@@ -9,7 +9,7 @@ arara_utils::define_label!(RenderPhaseLabel);
 
 #[derive(Default)]
 pub struct RenderPhases {
-    functions: HashMap<TypeId, Box<fn(&World, &mut TrackedFrame)>>,
+    functions: HashMap<TypeId, Box<fn(&World, Entity, &mut TrackedFrame)>>,
     order: Vec<TypeId>,
 }
 
@@ -59,20 +59,29 @@ impl RenderPhases {
         self
     }
 
-    pub fn run(&self, world: &World, tracked_frame: &mut TrackedFrame) {
+    /// Draws every phase of the `view` in order.
+    pub fn run(&self, world: &World, view: Entity, tracked_frame: &mut TrackedFrame) {
         self.order.iter().for_each(|id| {
             let f = self.functions.get(id).unwrap();
-            f(world, tracked_frame);
+            f(world, view, tracked_frame);
         });
     }
 }
 
-pub fn draw_render_phase<I: PhaseItem>(world: &World, tracked_frame: &mut TrackedFrame) {
+/// Draws the [`RenderPhase`] of the `view`, if it has one.
+pub fn draw_render_phase<I: PhaseItem>(
+    world: &World,
+    view: Entity,
+    tracked_frame: &mut TrackedFrame,
+) {
+    let phase = match world.get::<RenderPhase<I>>(view) {
+        Some(phase) => phase,
+        None => return,
+    };
     let draw_functions = world.get_resource::<DrawFunctions<I>>().unwrap();
-    let phase = world.get_resource::<RenderPhase<I>>().unwrap();
     let mut draw_functions = draw_functions.write();
     for item in phase.items.iter() {
         let draw_function = draw_functions.get_mut(item.draw_function()).unwrap();
-        draw_function.draw(world, tracked_frame, item);
+        draw_function.draw(world, tracked_frame, view, item);
     }
 }
@@ -2,8 +2,10 @@ mod pipeline;
 mod pipeline_cache;
 mod specialized_pipeline;
 mod tracked_frame;
+mod uniforms;
 
 pub use pipeline::*;
 pub use pipeline_cache::*;
 pub use specialized_pipeline::*;
 pub use tracked_frame::*;
+pub use uniforms::*;
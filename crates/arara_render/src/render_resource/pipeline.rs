@@ -2,7 +2,7 @@ use std::fmt;
 
 use arara_asset::Handle;
 use bevy_reflect::TypeUuid;
-use glium::{DrawParameters, Program};
+use glium::{program::ComputeShader, DrawParameters, Program};
 
 use crate::Shader;
 
@@ -25,6 +25,35 @@ impl fmt::Debug for RenderPipeline {
 #[derive(Clone, Debug)]
 pub struct RenderPipelineDescriptor {
     pub vertex_shader: Handle<Shader>,
+    /// The tessellation control and evaluation shaders, both are needed to tessellate the
+    /// primitives, which must then be drawn as `PrimitiveType::Patches`
+    pub tessellation_shaders: Option<TessellationShaders>,
+    pub geometry_shader: Option<Handle<Shader>>,
     pub fragment_shader: Handle<Shader>,
+    /// Names tested by the `#ifdef` and `#ifndef` blocks of the shaders,
+    /// see [`ShaderProcessor`](crate::ShaderProcessor)
+    pub shader_defs: Vec<String>,
     pub draw_parameters: DrawParameters<'static>,
 }
+
+#[derive(Clone, Debug)]
+pub struct TessellationShaders {
+    pub control_shader: Handle<Shader>,
+    pub evaluation_shader: Handle<Shader>,
+}
+
+/// A compiled compute shader, dispatched with [`ComputeShader::execute`].
+#[derive(Debug, TypeUuid)]
+#[uuid = "6b1ee6b6-1c3f-4d9c-a1f5-0d8d2cf6e3a4"]
+pub struct ComputePipeline {
+    pub shader: ComputeShader,
+}
+
+/// Describes a compute pipeline.
+#[derive(Clone, Debug)]
+pub struct ComputePipelineDescriptor {
+    pub shader: Handle<Shader>,
+    /// Names tested by the `#ifdef` and `#ifndef` blocks of the shader,
+    /// see [`ShaderProcessor`](crate::ShaderProcessor)
+    pub shader_defs: Vec<String>,
+}
@@ -8,18 +8,24 @@ use arara_utils::{
     HashMap, HashSet,
 };
 use arara_window::Window;
-use glium::Display;
+use glium::{
+    backend::Facade,
+    program::{ComputeShader, SourceCode},
+};
 use thiserror::Error;
 
 use crate::{
-    render_resource::{RenderPipeline, RenderPipelineDescriptor},
-    Shader,
+    render_resource::{
+        ComputePipeline, ComputePipelineDescriptor, RenderPipeline, RenderPipelineDescriptor,
+    },
+    ProcessShaderError, Shader, ShaderProcessor,
 };
 
 #[derive(Default)]
 struct ShaderCache {
     data: HashMap<Handle<Shader>, ShaderMetaData>,
     shaders: HashMap<Handle<Shader>, Shader>,
+    processor: ShaderProcessor,
 }
 
 #[derive(Default)]
@@ -28,16 +34,40 @@ pub struct ShaderMetaData {
 }
 
 impl ShaderCache {
-    fn get(&mut self, handle: &Handle<Shader>) -> Result<Shader, RenderPipelineError> {
+    /// The processed source of the shader, `pipeline` is queued again when the shader or one
+    /// of its imports changes.
+    fn get(
+        &mut self,
+        pipeline: CachedPipelineId,
+        handle: &Handle<Shader>,
+        shader_defs: &HashSet<String>,
+    ) -> Result<String, RenderPipelineError> {
+        self.data
+            .entry(handle.clone_weak())
+            .or_default()
+            .pipelines
+            .insert(pipeline);
         let shader = self
             .shaders
             .get(handle)
             .ok_or_else(|| RenderPipelineError::ShaderNotLoaded(handle.clone_weak()))?;
-        Ok(shader.clone())
+
+        let mut imports = HashSet::default();
+        let processed = self
+            .processor
+            .process(shader, shader_defs, &self.shaders, &mut imports);
+        for import in imports {
+            self.data
+                .entry(import.handle())
+                .or_default()
+                .pipelines
+                .insert(pipeline);
+        }
+        Ok(processed?)
     }
 
     fn get_dependent_pipelines(&mut self, handle: &Handle<Shader>) -> Vec<CachedPipelineId> {
-        match self.data.get_mut(&handle) {
+        match self.data.get_mut(handle) {
             Some(data) => data.pipelines.iter().cloned().collect(),
             None => Vec::new(),
         }
@@ -59,6 +89,9 @@ impl ShaderCache {
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct CachedPipelineId(usize);
 
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct CachedComputePipelineId(usize);
+
 // TODO: make arara::Window hava an Arc<Display>
 #[derive(Default)]
 pub struct RenderPipelineCache {
@@ -67,6 +100,15 @@ pub struct RenderPipelineCache {
     shader_cache: ShaderCache,
     pipelines: Vec<CachedPipeline>,
     waiting_pipelines: HashSet<CachedPipelineId>,
+    global_defines: HashMap<String, String>,
+    max_glsl_version: Option<GlslVersion>,
+}
+
+/// The shading language of the context, see [`RenderPipelineCache::set_max_glsl_version`].
+#[derive(Debug, Clone, Copy)]
+struct GlslVersion {
+    version: u32,
+    es: bool,
 }
 
 impl RenderPipelineCache {
@@ -75,9 +117,27 @@ impl RenderPipelineCache {
         &self.pipelines[id.0].state
     }
 
+    #[inline]
+    pub fn get_compute_state(&self, id: CachedComputePipelineId) -> &CachedPipelineState {
+        &self.pipelines[id.0].state
+    }
+
     #[inline]
     pub fn get(&self, id: CachedPipelineId) -> Option<&RenderPipeline> {
-        if let CachedPipelineState::Ok(pipeline) = &self.pipelines[id.0].state {
+        if let CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) =
+            &self.pipelines[id.0].state
+        {
+            Some(pipeline)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get_compute(&self, id: CachedComputePipelineId) -> Option<&ComputePipeline> {
+        if let CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) =
+            &self.pipelines[id.0].state
+        {
             Some(pipeline)
         } else {
             None
@@ -85,93 +145,296 @@ impl RenderPipelineCache {
     }
 
     pub fn queue(&mut self, descriptor: RenderPipelineDescriptor) -> CachedPipelineId {
-        let id = CachedPipelineId(self.pipelines.len());
+        CachedPipelineId(
+            self.push(PipelineDescriptor::RenderPipelineDescriptor(Box::new(
+                descriptor,
+            ))),
+        )
+    }
+
+    /// Queues a compute pipeline, compiled like the render pipelines once its shader is
+    /// loaded.
+    pub fn queue_compute(
+        &mut self,
+        descriptor: ComputePipelineDescriptor,
+    ) -> CachedComputePipelineId {
+        CachedComputePipelineId(
+            self.push(PipelineDescriptor::ComputePipelineDescriptor(Box::new(
+                descriptor,
+            ))),
+        )
+    }
+
+    fn push(&mut self, descriptor: PipelineDescriptor) -> usize {
+        let index = self.pipelines.len();
         self.pipelines.push(CachedPipeline {
             descriptor,
             state: CachedPipelineState::Queued,
         });
-        self.waiting_pipelines.insert(id);
-        id
+        self.waiting_pipelines.insert(CachedPipelineId(index));
+        index
+    }
+
+    /// Adds `#define name value` to every shader, recompiling all the pipelines if the value
+    /// changed.
+    pub fn set_global_define(&mut self, name: impl Into<String>, value: impl ToString) {
+        let value = value.to_string();
+        let previous = self.global_defines.insert(name.into(), value.clone());
+        if previous.as_ref() == Some(&value) {
+            return;
+        }
+        for (id, cached_pipeline) in self.pipelines.iter_mut().enumerate() {
+            cached_pipeline.state = CachedPipelineState::Queued;
+            self.waiting_pipelines.insert(CachedPipelineId(id));
+        }
     }
 
+    /// Lowers the `#version` of the shaders requiring more than `version`, so that they can
+    /// compile on contexts supporting the features they use under an older version.
+    ///
+    /// With `es` the desktop shaders are compiled as OpenGL ES shaders of `version`, the
+    /// directive becomes `#version <version> es` followed by the default precisions ES requires.
+    pub fn set_max_glsl_version(&mut self, version: u32, es: bool) {
+        self.max_glsl_version = Some(GlslVersion { version, es });
+    }
+
+    /// Queues the pipelines using the shader, they keep their current program until the new
+    /// one is compiled.
     fn set_shader(&mut self, handle: &Handle<Shader>, shader: &Shader) {
         let pipelines_to_queue = self.shader_cache.set_shader(handle, shader.clone());
-        for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline.0].state = CachedPipelineState::Queued;
-            self.waiting_pipelines.insert(cached_pipeline);
-        }
+        self.requeue(pipelines_to_queue);
     }
 
     fn remove_shader(&mut self, shader: &Handle<Shader>) {
         let pipelines_to_queue = self.shader_cache.remove(shader);
-        for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline.0].state = CachedPipelineState::Queued;
-            self.waiting_pipelines.insert(cached_pipeline);
+        self.requeue(pipelines_to_queue);
+    }
+
+    fn requeue(&mut self, pipelines: Vec<CachedPipelineId>) {
+        for id in pipelines {
+            let state = &mut self.pipelines[id.0].state;
+            // failed pipelines are given another chance with the new shaders
+            if !matches!(state, CachedPipelineState::Ok(_)) {
+                *state = CachedPipelineState::Queued;
+            }
+            self.waiting_pipelines.insert(id);
         }
     }
 
-    pub fn process_queue(&mut self, display: &Display) {
+    /// Compiles the queued pipelines.
+    ///
+    /// A compiled pipeline queued again because one of its shaders changed keeps its program
+    /// if the new shaders fail to compile, the error is logged instead.
+    pub fn process_queue(&mut self, facade: &dyn Facade) {
         let pipelines = std::mem::take(&mut self.waiting_pipelines);
         for id in pipelines {
             // debug!("trying id {:?}", id);
-            let state = &mut self.pipelines[id.0];
-            match &state.state {
-                CachedPipelineState::Ok(_) => continue,
-                CachedPipelineState::Queued => {}
-                CachedPipelineState::Err(err) => {
-                    match err {
-                        RenderPipelineError::ShaderNotLoaded(_) => { /* retry */ }
-                        // shader could not be processed ... retrying won't help
-                        RenderPipelineError::ProgramCreationError(err) => {
-                            error!("failed to process shaders: {}", err);
-                            continue;
-                        }
+            if let CachedPipelineState::Err(err) = &self.pipelines[id.0].state {
+                match err {
+                    RenderPipelineError::ShaderNotLoaded(_)
+                    | RenderPipelineError::ProcessShaderError(
+                        ProcessShaderError::UnresolvedImport(_),
+                    ) => { /* retry */ }
+                    // shader could not be processed ... retrying won't help
+                    RenderPipelineError::ProcessShaderError(err) => {
+                        error!("failed to process shaders: {}", err);
+                        continue;
+                    }
+                    RenderPipelineError::ProgramCreationError(err) => {
+                        error!("failed to process shaders: {}", err);
+                        continue;
                     }
                 }
             }
 
-            let descriptor = &state.descriptor;
-            let vertex_shader = match self.shader_cache.get(&descriptor.vertex_shader) {
-                Ok(shader) => shader,
-                Err(err) => {
-                    state.state = CachedPipelineState::Err(err);
+            let result = self.compile(facade, id);
+            let state = &mut self.pipelines[id.0].state;
+            match (result, &state) {
+                (Ok(pipeline), _) => *state = CachedPipelineState::Ok(pipeline),
+                (
+                    Err(
+                        RenderPipelineError::ShaderNotLoaded(_)
+                        | RenderPipelineError::ProcessShaderError(
+                            ProcessShaderError::UnresolvedImport(_),
+                        ),
+                    ),
+                    CachedPipelineState::Ok(_),
+                ) => {
                     self.waiting_pipelines.insert(id);
-                    continue;
                 }
-            };
-
-            let fragment_shader = match self.shader_cache.get(&descriptor.fragment_shader) {
-                Ok(shader) => shader,
-                Err(err) => {
-                    state.state = CachedPipelineState::Err(err);
-                    self.waiting_pipelines.insert(id);
-                    continue;
+                (Err(err), CachedPipelineState::Ok(_)) => {
+                    error!(
+                        "failed to reload shaders, keeping the previous program: {}",
+                        err
+                    );
                 }
-            };
-
-            let parameters = descriptor.draw_parameters.clone();
-            let program = match glium::Program::from_source(
-                display,
-                vertex_shader.source(),
-                fragment_shader.source(),
-                None,
-            ) {
-                Ok(program) => program,
-                Err(err) => {
-                    state.state =
-                        CachedPipelineState::Err(RenderPipelineError::ProgramCreationError(err));
+                (Err(err), _) => {
+                    *state = CachedPipelineState::Err(err);
                     self.waiting_pipelines.insert(id);
-                    continue;
                 }
-            };
+            }
+        }
+    }
 
-            let pipeline = RenderPipeline {
-                program,
-                parameters,
-            };
+    fn compile(
+        &mut self,
+        facade: &dyn Facade,
+        id: CachedPipelineId,
+    ) -> Result<Pipeline, RenderPipelineError> {
+        let Self {
+            shader_cache,
+            pipelines,
+            global_defines,
+            max_glsl_version,
+            ..
+        } = self;
+        let descriptor = &pipelines[id.0].descriptor;
+        // the global defines can be tested with `#ifdef` as well
+        let shader_defs: HashSet<String> = descriptor
+            .shader_defs()
+            .iter()
+            .chain(global_defines.keys())
+            .cloned()
+            .collect();
+        let mut source = |handle: &Handle<Shader>| -> Result<String, RenderPipelineError> {
+            let processed = shader_cache.get(id, handle, &shader_defs)?;
+            Ok(process_source(
+                &processed,
+                global_defines,
+                *max_glsl_version,
+            ))
+        };
 
-            state.state = CachedPipelineState::Ok(pipeline);
+        match descriptor {
+            PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
+                let vertex_shader = source(&descriptor.vertex_shader)?;
+                let tessellation_shaders = match &descriptor.tessellation_shaders {
+                    Some(shaders) => Some((
+                        source(&shaders.control_shader)?,
+                        source(&shaders.evaluation_shader)?,
+                    )),
+                    None => None,
+                };
+                let geometry_shader = match &descriptor.geometry_shader {
+                    Some(shader) => Some(source(shader)?),
+                    None => None,
+                };
+                let fragment_shader = source(&descriptor.fragment_shader)?;
+
+                let program = glium::Program::new(
+                    facade,
+                    SourceCode {
+                        vertex_shader: &vertex_shader,
+                        tessellation_control_shader: tessellation_shaders
+                            .as_ref()
+                            .map(|(control, _)| control.as_str()),
+                        tessellation_evaluation_shader: tessellation_shaders
+                            .as_ref()
+                            .map(|(_, evaluation)| evaluation.as_str()),
+                        geometry_shader: geometry_shader.as_deref(),
+                        fragment_shader: &fragment_shader,
+                    },
+                )?;
+                Ok(Pipeline::RenderPipeline(RenderPipeline {
+                    program,
+                    parameters: descriptor.draw_parameters.clone(),
+                }))
+            }
+            PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
+                let shader = ComputeShader::from_source(facade, &source(&descriptor.shader)?)?;
+                Ok(Pipeline::ComputePipeline(ComputePipeline { shader }))
+            }
+        }
+    }
+}
+
+fn process_source(
+    source: &str,
+    defines: &HashMap<String, String>,
+    max_glsl_version: Option<GlslVersion>,
+) -> String {
+    let source = with_defines(source, defines);
+    match max_glsl_version {
+        Some(GlslVersion { version, es }) => clamp_glsl_version(&source, version, es),
+        None => source,
+    }
+}
+
+/// Inserts the `defines` after the `#version` directive, which has to come first.
+fn with_defines(source: &str, defines: &HashMap<String, String>) -> String {
+    if defines.is_empty() {
+        return source.to_string();
+    }
+    let (version, body) = match source.find("#version") {
+        Some(start) => {
+            let end = source[start..]
+                .find('\n')
+                .map_or(source.len(), |end| start + end + 1);
+            source.split_at(end)
+        }
+        None => ("", source),
+    };
+    let mut defines: Vec<_> = defines.iter().collect();
+    defines.sort();
+
+    let mut processed = version.to_string();
+    if !processed.is_empty() && !processed.ends_with('\n') {
+        processed.push('\n');
+    }
+    for (name, value) in defines {
+        processed.push_str(&format!("#define {} {}\n", name, value));
+    }
+    processed.push_str(body);
+    processed
+}
+
+/// Default precisions of the ES shaders, floats and most samplers have none in fragment shaders.
+const ES_PRECISIONS: &str = "precision highp float;\nprecision highp int;\n";
+/// Sampler types added by GLSL ES 3.00 which have no default precision in any stage.
+const ES_300_SAMPLER_PRECISIONS: &str = "precision highp sampler3D;\n\
+    precision highp sampler2DArray;\n\
+    precision highp sampler2DShadow;\n\
+    precision highp samplerCubeShadow;\n\
+    precision highp sampler2DArrayShadow;\n";
+
+fn clamp_glsl_version(source: &str, max_version: u32, es: bool) -> String {
+    let directive = source
+        .lines()
+        .find(|line| line.trim_start().starts_with("#version"));
+    let version = directive.and_then(|line| {
+        line.trim_start()
+            .trim_start_matches("#version")
+            .split_whitespace()
+            .next()?
+            .parse::<u32>()
+            .ok()
+    });
+    match (directive, version) {
+        // desktop versions don't map to ES ones, the ES version of the context is always used
+        (Some(directive), Some(_)) if es && !directive.contains(" es") => {
+            let mut replacement = format!(
+                "{}\n{}",
+                glsl_version_directive(max_version, es),
+                ES_PRECISIONS
+            );
+            if max_version >= 300 {
+                replacement.push_str(ES_300_SAMPLER_PRECISIONS);
+            }
+            source.replacen(&format!("{}\n", directive), &replacement, 1)
+        }
+        (Some(directive), Some(version)) if version > max_version => {
+            source.replacen(directive, &glsl_version_directive(max_version, es), 1)
         }
+        _ => source.to_string(),
+    }
+}
+
+/// GLSL ES 1.00 predates the `es` profile of the directive.
+fn glsl_version_directive(version: u32, es: bool) -> String {
+    if es && version >= 300 {
+        format!("#version {} es", version)
+    } else {
+        format!("#version {}", version)
     }
 }
 
@@ -179,7 +442,7 @@ pub(crate) fn process_pipeline_queue(
     mut cache: NonSendMut<RenderPipelineCache>,
     window: NonSend<Window>,
 ) {
-    cache.process_queue(window.display());
+    cache.process_queue(window.facade());
 }
 
 pub(crate) fn extract_shaders(
@@ -201,19 +464,42 @@ pub(crate) fn extract_shaders(
 }
 
 struct CachedPipeline {
-    descriptor: RenderPipelineDescriptor,
+    descriptor: PipelineDescriptor,
     state: CachedPipelineState,
 }
 
+#[derive(Clone, Debug)]
+pub enum PipelineDescriptor {
+    RenderPipelineDescriptor(Box<RenderPipelineDescriptor>),
+    ComputePipelineDescriptor(Box<ComputePipelineDescriptor>),
+}
+
+impl PipelineDescriptor {
+    pub fn shader_defs(&self) -> &[String] {
+        match self {
+            PipelineDescriptor::RenderPipelineDescriptor(descriptor) => &descriptor.shader_defs,
+            PipelineDescriptor::ComputePipelineDescriptor(descriptor) => &descriptor.shader_defs,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Pipeline {
+    RenderPipeline(RenderPipeline),
+    ComputePipeline(ComputePipeline),
+}
+
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum CachedPipelineState {
     Queued,
-    Ok(RenderPipeline),
+    Ok(Pipeline),
     Err(RenderPipelineError),
 }
 
 impl CachedPipelineState {
-    pub fn unwrap(&self) -> &RenderPipeline {
+    pub fn unwrap(&self) -> &Pipeline {
         match self {
             CachedPipelineState::Ok(pipeline) => pipeline,
             CachedPipelineState::Queued => {
@@ -231,5 +517,54 @@ pub enum RenderPipelineError {
     )]
     ShaderNotLoaded(Handle<Shader>),
     #[error(transparent)]
+    ProcessShaderError(#[from] ProcessShaderError),
+    #[error(transparent)]
     ProgramCreationError(#[from] glium::ProgramCreationError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defines_follow_the_version() {
+        let mut defines = HashMap::default();
+        defines.insert("TEXTURE_BUFFER_SIZE".to_string(), "16".to_string());
+        assert_eq!(
+            with_defines("#version 460\nvoid main() {}\n", &defines),
+            "#version 460\n#define TEXTURE_BUFFER_SIZE 16\nvoid main() {}\n"
+        );
+        assert_eq!(
+            with_defines("void main() {}", &defines),
+            "#define TEXTURE_BUFFER_SIZE 16\nvoid main() {}"
+        );
+    }
+
+    #[test]
+    fn version_is_clamped() {
+        assert_eq!(
+            clamp_glsl_version("#version 460\nvoid main() {}", 450, false),
+            "#version 450\nvoid main() {}"
+        );
+        assert_eq!(
+            clamp_glsl_version("#version 330\nvoid main() {}", 450, false),
+            "#version 330\nvoid main() {}"
+        );
+    }
+
+    #[test]
+    fn desktop_shaders_become_es_shaders() {
+        assert_eq!(
+            clamp_glsl_version("#version 330 core\nvoid main() {}", 100, true),
+            "#version 100\nprecision highp float;\nprecision highp int;\nvoid main() {}"
+        );
+        let source = clamp_glsl_version("#version 460\nvoid main() {}", 300, true);
+        assert!(source.starts_with("#version 300 es\nprecision highp float;\n"));
+        assert!(source.contains("precision highp sampler2DArrayShadow;\n"));
+        assert!(source.ends_with(";\nvoid main() {}"));
+        assert_eq!(
+            clamp_glsl_version("#version 320 es\nvoid main() {}", 300, true),
+            "#version 300 es\nvoid main() {}"
+        );
+    }
+}
@@ -27,6 +27,11 @@ impl<S: SpecializedPipeline> SpecializedPipelines<S> {
             cache.queue(descriptor)
         })
     }
+
+    /// The pipeline of `key`, if it was already specialized.
+    pub fn get(&self, key: &S::Key) -> Option<CachedPipelineId> {
+        self.cache.get(key).copied()
+    }
 }
 
 pub trait SpecializedPipeline {
@@ -1,20 +1,59 @@
+use arara_window::{Window, WindowBackend};
 use glium::{
-    index, uniforms, vertex, Display, DrawError, DrawParameters, Frame, Program, Surface,
-    SwapBuffersError,
+    backend::Facade,
+    framebuffer::SimpleFrameBuffer,
+    index,
+    texture::{MipmapsOption, RawImage2d, UncompressedFloatFormat},
+    uniforms::{self, MagnifySamplerFilter},
+    vertex, BlitTarget, DrawError, DrawParameters, Frame, Program, Rect, Surface, SwapBuffersError,
+    Texture2d,
 };
 use thiserror::Error;
 
-use crate::Color;
+use crate::{Color, Image};
 
-pub struct TrackedFrame {
-    frame: Frame,
+/// The surface a [`TrackedFrame`] draws into.
+pub enum FrameTarget<'a> {
+    /// The back buffer of a window, presented on [`TrackedFrame::finish`].
+    Window(Frame),
+    /// An offscreen framebuffer, used by headless windows and image render targets.
+    Offscreen(SimpleFrameBuffer<'a>),
 }
 
-impl TrackedFrame {
-    /// Builds a new `Frame`.
-    pub fn new(display: &Display) -> Self {
+pub struct TrackedFrame<'a> {
+    facade: &'a dyn Facade,
+    target: FrameTarget<'a>,
+    viewport: Option<Rect>,
+}
+
+impl<'a> TrackedFrame<'a> {
+    /// Builds a new `Frame` targeting the `window` back buffer,
+    /// or its offscreen framebuffer if the window is headless.
+    pub fn new(window: &'a Window) -> Self {
+        let target = match window.backend() {
+            WindowBackend::Display(display) => FrameTarget::Window(display.draw()),
+            WindowBackend::Headless(headless) => FrameTarget::Offscreen(
+                SimpleFrameBuffer::with_depth_buffer(
+                    headless.renderer(),
+                    headless.color(),
+                    headless.depth(),
+                )
+                .unwrap(),
+            ),
+        };
         Self {
-            frame: display.draw(),
+            facade: window.facade(),
+            target,
+            viewport: None,
+        }
+    }
+
+    /// Builds a new `Frame` drawing into an offscreen `framebuffer`.
+    pub fn offscreen(facade: &'a dyn Facade, framebuffer: SimpleFrameBuffer<'a>) -> Self {
+        Self {
+            facade,
+            target: FrameTarget::Offscreen(framebuffer),
+            viewport: None,
         }
     }
 
@@ -23,15 +62,62 @@ impl TrackedFrame {
     /// See the documentation of `SwapBuffersError` about what is being returned.
     #[inline]
     pub fn finish(self) -> Result<(), SwapBuffersError> {
-        self.frame.finish()
+        match self.target {
+            FrameTarget::Window(frame) => frame.finish(),
+            FrameTarget::Offscreen(_) => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub fn target(&self) -> &FrameTarget<'a> {
+        &self.target
+    }
+
+    /// The OpenGL context the frame belongs to.
+    #[inline]
+    pub fn facade(&self) -> &'a dyn Facade {
+        self.facade
+    }
+
+    /// Returns the dimensions in pixels of the target.
+    pub fn dimensions(&self) -> (u32, u32) {
+        match &self.target {
+            FrameTarget::Window(frame) => frame.get_dimensions(),
+            FrameTarget::Offscreen(framebuffer) => framebuffer.get_dimensions(),
+        }
+    }
+
+    /// Restricts the following draws and clears to `viewport`, `None` uses the whole target.
+    pub fn set_viewport(&mut self, viewport: Option<Rect>) {
+        self.viewport = viewport;
+    }
+
+    #[inline]
+    pub fn viewport(&self) -> Option<Rect> {
+        self.viewport
+    }
+
+    pub fn clear_depth(&mut self) {
+        let rect = self.viewport.as_ref();
+        match &mut self.target {
+            FrameTarget::Window(frame) => frame.clear(rect, None, false, Some(1.0), None),
+            FrameTarget::Offscreen(framebuffer) => {
+                framebuffer.clear(rect, None, false, Some(1.0), None)
+            }
+        }
     }
 
     pub fn clear_color_and_depth(&mut self, color: Color) {
         let clear_color = (color.r(), color.g(), color.b(), color.a());
-        self.frame.clear_color_and_depth(clear_color, 1.0);
+        match &mut self.target {
+            FrameTarget::Window(frame) => frame.clear_color_and_depth(clear_color, 1.0),
+            FrameTarget::Offscreen(framebuffer) => {
+                framebuffer.clear_color_and_depth(clear_color, 1.0)
+            }
+        }
     }
 
-    pub fn draw<'a, 'b, V, I, U>(
+    pub fn draw<'b, 'c, V, I, U>(
         &mut self,
         vertex_buffer: V,
         index_buffer: I,
@@ -40,17 +126,106 @@ impl TrackedFrame {
         draw_parameters: &DrawParameters<'_>,
     ) -> Result<(), DrawError>
     where
-        I: Into<index::IndicesSource<'a>>,
+        I: Into<index::IndicesSource<'b>>,
         U: uniforms::Uniforms,
-        V: vertex::MultiVerticesSource<'b>,
+        V: vertex::MultiVerticesSource<'c>,
     {
-        self.frame.draw(
-            vertex_buffer,
-            index_buffer,
-            program,
-            uniforms,
-            draw_parameters,
-        )
+        let viewport_parameters;
+        let draw_parameters = match self.viewport {
+            Some(viewport) => {
+                viewport_parameters = DrawParameters {
+                    viewport: Some(viewport),
+                    ..draw_parameters.clone()
+                };
+                &viewport_parameters
+            }
+            None => draw_parameters,
+        };
+        match &mut self.target {
+            FrameTarget::Window(frame) => frame.draw(
+                vertex_buffer,
+                index_buffer,
+                program,
+                uniforms,
+                draw_parameters,
+            ),
+            FrameTarget::Offscreen(framebuffer) => framebuffer.draw(
+                vertex_buffer,
+                index_buffer,
+                program,
+                uniforms,
+                draw_parameters,
+            ),
+        }
+    }
+
+    /// Copies `texture` into the viewport, or the whole target.
+    pub fn blit_color_from(&self, texture: &Texture2d) {
+        let (width, height) = self.dimensions();
+        let rect = self.viewport.unwrap_or(Rect {
+            left: 0,
+            bottom: 0,
+            width,
+            height,
+        });
+        let blit_target = BlitTarget {
+            left: rect.left,
+            bottom: rect.bottom,
+            width: rect.width as i32,
+            height: rect.height as i32,
+        };
+        let source = texture.as_surface();
+        match &self.target {
+            FrameTarget::Window(frame) => {
+                source.blit_whole_color_to(frame, &blit_target, MagnifySamplerFilter::Linear)
+            }
+            FrameTarget::Offscreen(framebuffer) => {
+                source.blit_whole_color_to(framebuffer, &blit_target, MagnifySamplerFilter::Linear)
+            }
+        }
+    }
+
+    /// Reads back the color buffer of the target into an [`Image`].
+    ///
+    /// Should be called after all phases are drawn and before [`TrackedFrame::finish`].
+    pub fn read_pixels(&self) -> Result<Image, FrameError> {
+        let (width, height) = self.dimensions();
+        let texture = Texture2d::empty_with_format(
+            self.facade,
+            UncompressedFloatFormat::U8U8U8U8,
+            MipmapsOption::NoMipmap,
+            width,
+            height,
+        )?;
+        let framebuffer = SimpleFrameBuffer::new(self.facade, &texture)?;
+        let blit_target = BlitTarget {
+            left: 0,
+            bottom: 0,
+            width: width as i32,
+            height: height as i32,
+        };
+        match &self.target {
+            FrameTarget::Window(frame) => {
+                frame.blit_whole_color_to(&framebuffer, &blit_target, MagnifySamplerFilter::Nearest)
+            }
+            FrameTarget::Offscreen(target) => target.blit_whole_color_to(
+                &framebuffer,
+                &blit_target,
+                MagnifySamplerFilter::Nearest,
+            ),
+        }
+
+        // OpenGL rows go from bottom to top, images are stored from top to bottom
+        let raw: RawImage2d<u8> = texture.read();
+        let row_size = width as usize * 4;
+        let data = raw
+            .data
+            .chunks_exact(row_size)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        Ok(Image::new(data, (width, height), false))
     }
 }
 
@@ -60,4 +235,8 @@ pub enum FrameError {
     SwapBuffersError(#[from] glium::SwapBuffersError),
     #[error(transparent)]
     DrawError(#[from] glium::DrawError),
+    #[error(transparent)]
+    TextureCreationError(#[from] glium::texture::TextureCreationError),
+    #[error(transparent)]
+    ValidationError(#[from] glium::framebuffer::ValidationError),
 }
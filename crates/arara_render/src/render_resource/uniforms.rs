@@ -0,0 +1,108 @@
+use glium::uniforms::{UniformValue, Uniforms};
+
+/// The uniforms of both `A` and `B`, for instance the ones of a `glium::uniform!` along with
+/// [`TextureBindings`](crate::TextureBindings).
+pub struct ChainedUniforms<A, B>(pub A, pub B);
+
+impl<A: Uniforms, B: Uniforms> Uniforms for ChainedUniforms<A, B> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        self.0.visit_values(&mut output);
+        self.1.visit_values(output);
+    }
+}
+
+/// Implements `glium::uniforms::UniformBlock` for a `#[repr(C)]` struct, from its name, with
+/// its lifetime if it has one, and the names of its fields.
+///
+/// A replacement of `glium::implement_uniform_block!`, which dereferences a null pointer to find
+/// the offsets of the fields and doesn't add the offset of the block to them, so structs nested
+/// in other blocks or in arrays never match.
+///
+/// ```
+/// # use arara_render::implement_uniform_block;
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct CameraUniform {
+///     u_pv_matrix: [[f32; 4]; 4],
+///     u_position: [f32; 4],
+/// }
+///
+/// implement_uniform_block!(CameraUniform, u_pv_matrix, u_position);
+/// ```
+#[macro_export]
+macro_rules! implement_uniform_block {
+    ($struct_name:ident $(<$lifetime:lifetime>)?, $($field_name:ident),+ $(,)?) => {
+        impl$(<$lifetime>)? ::glium::uniforms::UniformBlock for $struct_name$(<$lifetime>)? {
+            fn matches(
+                layout: &::glium::program::BlockLayout,
+                base_offset: usize,
+            ) -> ::std::result::Result<(), ::glium::uniforms::LayoutMismatchError> {
+                use ::glium::{program::BlockLayout, uniforms::LayoutMismatchError};
+
+                fn matches_from_ty<T: ::glium::uniforms::UniformBlock + ?Sized>(
+                    _: Option<&T>,
+                    layout: &BlockLayout,
+                    base_offset: usize,
+                ) -> ::std::result::Result<(), LayoutMismatchError> {
+                    T::matches(layout, base_offset)
+                }
+
+                let members = match layout {
+                    BlockLayout::Struct { members } => members,
+                    _ => {
+                        return Err(LayoutMismatchError::LayoutMismatch {
+                            expected: layout.clone(),
+                            obtained: <Self as ::glium::uniforms::UniformBlock>::build_layout(
+                                base_offset,
+                            ),
+                        })
+                    }
+                };
+                // every member of the block must be a field of the struct
+                for (name, _) in members {
+                    if $(name != stringify!($field_name) &&)+ true {
+                        return Err(LayoutMismatchError::MissingField { name: name.clone() });
+                    }
+                }
+                $(
+                    let reflected = members
+                        .iter()
+                        .find(|(name, _)| name == stringify!($field_name))
+                        .ok_or_else(|| LayoutMismatchError::MissingField {
+                            name: stringify!($field_name).to_owned(),
+                        })?;
+                    let offset = ::std::mem::offset_of!(Self, $field_name);
+                    let field = None::<&Self>.map(|value| &value.$field_name);
+                    matches_from_ty(field, &reflected.1, base_offset + offset).map_err(|err| {
+                        LayoutMismatchError::MemberMismatch {
+                            member: stringify!($field_name).to_owned(),
+                            err: Box::new(err),
+                        }
+                    })?;
+                )+
+                Ok(())
+            }
+
+            fn build_layout(base_offset: usize) -> ::glium::program::BlockLayout {
+                fn layout_from_ty<T: ::glium::uniforms::UniformBlock + ?Sized>(
+                    _: Option<&T>,
+                    base_offset: usize,
+                ) -> ::glium::program::BlockLayout {
+                    T::build_layout(base_offset)
+                }
+
+                ::glium::program::BlockLayout::Struct {
+                    members: vec![$(
+                        (
+                            stringify!($field_name).to_owned(),
+                            layout_from_ty(
+                                None::<&Self>.map(|value| &value.$field_name),
+                                base_offset + ::std::mem::offset_of!(Self, $field_name),
+                            ),
+                        ),
+                    )+],
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use arara_ecs::{event::Events, world::World};
+use arara_utils::tracing::{error, info};
+
+use crate::{FrameError, Image};
+
+/// Identifies a screenshot requested to the [`ScreenshotManager`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ScreenshotId(usize);
+
+/// An event that is sent once a requested frame has been captured.
+#[derive(Debug, Clone)]
+pub struct ScreenshotCaptured {
+    pub id: ScreenshotId,
+    pub image: Image,
+}
+
+/// An event that is sent instead of [`ScreenshotCaptured`] when the requested frame could not be
+/// read back.
+#[derive(Debug, Clone)]
+pub struct ScreenshotFailed {
+    pub id: ScreenshotId,
+    pub error: String,
+}
+
+pub(crate) struct ScreenshotRequest {
+    id: ScreenshotId,
+    path: Option<PathBuf>,
+}
+
+/// Captures the next rendered frame as an [`Image`].
+///
+/// The captured frame is delivered through a [`ScreenshotCaptured`] event sent at the end of the
+/// [`RenderStage::Render`](crate::RenderStage::Render) stage, or a [`ScreenshotFailed`] event if
+/// it could not be read back.
+#[derive(Default)]
+pub struct ScreenshotManager {
+    next_id: usize,
+    requests: Vec<ScreenshotRequest>,
+}
+
+impl ScreenshotManager {
+    /// Requests a capture of the next frame.
+    pub fn take_screenshot(&mut self) -> ScreenshotId {
+        self.request(None)
+    }
+
+    /// Requests a capture of the next frame and saves it to `path`,
+    /// the file format is deduced from the extension of the path.
+    pub fn save_screenshot_to_disk(&mut self, path: impl Into<PathBuf>) -> ScreenshotId {
+        self.request(Some(path.into()))
+    }
+
+    /// Returns `true` if there is a capture waiting for the next frame.
+    pub fn is_pending(&self) -> bool {
+        !self.requests.is_empty()
+    }
+
+    fn request(&mut self, path: Option<PathBuf>) -> ScreenshotId {
+        let id = ScreenshotId(self.next_id);
+        self.next_id += 1;
+        self.requests.push(ScreenshotRequest { id, path });
+        id
+    }
+
+    pub(crate) fn take_requests(&mut self) -> Vec<ScreenshotRequest> {
+        std::mem::take(&mut self.requests)
+    }
+}
+
+/// Saves and sends the events for every request answered by the captured frame.
+pub(crate) fn send_screenshots(
+    world: &mut World,
+    requests: Vec<ScreenshotRequest>,
+    capture: Result<Image, FrameError>,
+) {
+    let image = match capture {
+        Ok(image) => image,
+        Err(err) => {
+            error!("Failed to capture frame: {}", err);
+            let mut events = world
+                .get_resource_mut::<Events<ScreenshotFailed>>()
+                .unwrap();
+            for ScreenshotRequest { id, .. } in requests {
+                events.send(ScreenshotFailed {
+                    id,
+                    error: err.to_string(),
+                });
+            }
+            return;
+        }
+    };
+    let mut events = world
+        .get_resource_mut::<Events<ScreenshotCaptured>>()
+        .unwrap();
+    for ScreenshotRequest { id, path } in requests {
+        if let Some(path) = path {
+            match image.save(&path) {
+                Ok(()) => info!("Screenshot saved to {}", path.display()),
+                Err(err) => error!("Failed to save screenshot to {}: {}", path.display(), err),
+            }
+        }
+        events.send(ScreenshotCaptured {
+            id,
+            image: image.clone(),
+        });
+    }
+}
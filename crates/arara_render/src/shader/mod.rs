@@ -1,10 +1,12 @@
 mod shader;
 mod shader_loader;
+mod shader_processor;
 
 use arara_app::{App, Plugin};
 use arara_asset::AddAsset;
-pub use shader::Shader;
-pub use shader_loader::ShaderLoader;
+pub use shader::{Shader, ShaderStage};
+pub use shader_loader::*;
+pub use shader_processor::*;
 
 /// Adds the [`Shader`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct ShaderPlugin;
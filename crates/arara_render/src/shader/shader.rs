@@ -1,34 +1,77 @@
-use glium::program::ShaderStage;
 use std::borrow::Cow;
 
 use bevy_reflect::TypeUuid;
 
+use crate::ShaderImport;
+
 /// A shader, as defined by its [ShaderSource] and [ShaderStage]
-/// This is an "unprocessed" shader. It can contain preprocessor directives.
+/// This is an "unprocessed" shader. It can contain preprocessor directives,
+/// see [`ShaderProcessor`](crate::ShaderProcessor).
 #[derive(Debug, Clone, TypeUuid)]
 #[uuid = "d95bc916-6c55-4de3-9622-37e7b6969fda"]
 pub struct Shader {
     source: Source,
+    imports: Vec<ShaderImport>,
 }
 
 impl Shader {
     pub fn from_glsl(source: impl Into<Cow<'static, str>>, stage: ShaderStage) -> Shader {
-        let source = source.into();
-        Shader {
-            source: Source::Glsl(source, stage),
-        }
+        Self::new(Source::Glsl(source.into(), stage))
+    }
+
+    /// A shader that is only meant to be included by other shaders.
+    pub fn from_glsl_include(source: impl Into<Cow<'static, str>>) -> Shader {
+        Self::new(Source::GlslInclude(source.into()))
+    }
+
+    fn new(source: Source) -> Shader {
+        let mut shader = Shader {
+            source,
+            imports: Vec::new(),
+        };
+        shader.imports = shader
+            .source()
+            .lines()
+            .filter_map(ShaderImport::parse)
+            .collect();
+        shader
     }
 
     pub fn source(&self) -> &str {
         match &self.source {
-            Source::Glsl(src, _) => &src,
+            Source::Glsl(src, _) | Source::GlslInclude(src) => src,
+        }
+    }
+
+    /// The stage of the shader, `None` for the shaders only meant to be included.
+    pub fn stage(&self) -> Option<ShaderStage> {
+        match &self.source {
+            Source::Glsl(_, stage) => Some(*stage),
+            Source::GlslInclude(_) => None,
         }
     }
+
+    /// Every shader included by this one, even those in `#ifdef` blocks.
+    pub fn imports(&self) -> &[ShaderImport] {
+        &self.imports
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Source {
     Glsl(Cow<'static, str>, ShaderStage),
+    GlslInclude(Cow<'static, str>),
     // Wgsl(Cow<'static, str>),
     // SpirV(Cow<'static, [u8]>),
 }
+
+/// The pipeline stage a [`Shader`] is compiled for.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShaderStage {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+}
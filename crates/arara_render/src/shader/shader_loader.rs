@@ -1,11 +1,18 @@
-use arara_asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
-use glium::program::ShaderStage;
+use arara_asset::{AssetLoader, AssetPath, BoxedFuture, LoadContext, LoadedAsset};
+use thiserror::Error;
 
-use crate::Shader;
+use crate::{Shader, ShaderStage};
 
 #[derive(Default)]
 pub struct ShaderLoader;
 
+/// An error that occurs when loading a shader.
+#[derive(Error, Debug)]
+pub enum ShaderLoaderError {
+    #[error("unsupported shader extension: {0}")]
+    UnsupportedExtension(String),
+}
+
 impl AssetLoader for ShaderLoader {
     fn load<'a>(
         &'a self,
@@ -13,24 +20,39 @@ impl AssetLoader for ShaderLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
         Box::pin(async move {
-            let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let ext = load_context
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or_default();
+            let stage = match ext {
+                "vert" => Some(ShaderStage::Vertex),
+                "tesc" => Some(ShaderStage::TessellationControl),
+                "tese" => Some(ShaderStage::TessellationEvaluation),
+                "geom" => Some(ShaderStage::Geometry),
+                "frag" => Some(ShaderStage::Fragment),
+                "comp" => Some(ShaderStage::Compute),
+                "glsl" => None,
+                _ => return Err(ShaderLoaderError::UnsupportedExtension(ext.to_string()).into()),
+            };
 
-            let shader = match ext {
-                "vert" => {
-                    Shader::from_glsl(String::from_utf8(Vec::from(bytes))?, ShaderStage::Vertex)
-                }
-                "frag" => {
-                    Shader::from_glsl(String::from_utf8(Vec::from(bytes))?, ShaderStage::Fragment)
-                }
-                _ => panic!("unhandled extension: {}", ext),
+            let source = String::from_utf8(Vec::from(bytes))?;
+            let shader = match stage {
+                Some(stage) => Shader::from_glsl(source, stage),
+                None => Shader::from_glsl_include(source),
             };
 
-            load_context.set_default_asset(LoadedAsset::new(shader));
+            let imports = shader
+                .imports()
+                .iter()
+                .map(|import| AssetPath::from(import.path()).to_owned())
+                .collect();
+            load_context.set_default_asset(LoadedAsset::new(shader).with_dependencies(imports));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["vert", "frag"]
+        &["vert", "tesc", "tese", "geom", "frag", "comp", "glsl"]
     }
 }
@@ -0,0 +1,211 @@
+use arara_asset::{Handle, HandleId};
+use arara_utils::{HashMap, HashSet};
+use thiserror::Error;
+
+use crate::Shader;
+
+/// A shader included by another one, written `#include "shaders/lighting.glsl"` or
+/// `#import "shaders/lighting.glsl"` with a path relative to the assets folder.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShaderImport(pub String);
+
+impl ShaderImport {
+    /// Parses an `#include` or `#import` line.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let path = line
+            .strip_prefix("#include")
+            .or_else(|| line.strip_prefix("#import"))?
+            .trim();
+        let path = path
+            .strip_prefix('"')
+            .and_then(|path| path.strip_suffix('"'))
+            .or_else(|| {
+                path.strip_prefix('<')
+                    .and_then(|path| path.strip_suffix('>'))
+            })
+            .unwrap_or(path);
+        if path.is_empty() {
+            return None;
+        }
+        Some(Self(path.to_string()))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.0
+    }
+
+    /// The handle the asset server gives to the imported shader.
+    pub fn handle(&self) -> Handle<Shader> {
+        Handle::weak(HandleId::from(self.path()))
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProcessShaderError {
+    #[error("Too many '#endif' lines. Each endif should be preceded by an if statement.")]
+    TooManyEndIfs,
+    #[error(
+        "Not enough '#endif' lines. Each if statement should be followed by an endif statement."
+    )]
+    NotEnoughEndIfs,
+    #[error("The imported shader '{}' is not loaded yet.", .0.path())]
+    UnresolvedImport(ShaderImport),
+}
+
+/// Resolves the `#include`/`#import` and the `#ifdef`/`#ifndef`/`#else`/`#endif` of a
+/// [`Shader`], other directives are left to the GLSL compiler.
+///
+/// Every shader is included at most once, later includes of the same file are dropped.
+#[derive(Debug, Default)]
+pub struct ShaderProcessor;
+
+impl ShaderProcessor {
+    /// The processed source of `shader` for the given `shader_defs`.
+    ///
+    /// The imports reached while processing are added to `imports`, even if they aren't loaded,
+    /// so the shader can be processed again once they change.
+    pub fn process(
+        &self,
+        shader: &Shader,
+        shader_defs: &HashSet<String>,
+        shaders: &HashMap<Handle<Shader>, Shader>,
+        imports: &mut HashSet<ShaderImport>,
+    ) -> Result<String, ProcessShaderError> {
+        let mut processed = String::new();
+        self.process_into(
+            shader.source(),
+            shader_defs,
+            shaders,
+            imports,
+            &mut processed,
+        )?;
+        Ok(processed)
+    }
+
+    fn process_into(
+        &self,
+        source: &str,
+        shader_defs: &HashSet<String>,
+        shaders: &HashMap<Handle<Shader>, Shader>,
+        imports: &mut HashSet<ShaderImport>,
+        processed: &mut String,
+    ) -> Result<(), ProcessShaderError> {
+        // whether the lines of each nested if block are kept
+        let mut scopes = vec![true];
+        for line in source.lines() {
+            let directive = line.trim();
+            if let Some(def) = directive.strip_prefix("#ifdef") {
+                let active = *scopes.last().unwrap() && shader_defs.contains(def.trim());
+                scopes.push(active);
+            } else if let Some(def) = directive.strip_prefix("#ifndef") {
+                let active = *scopes.last().unwrap() && !shader_defs.contains(def.trim());
+                scopes.push(active);
+            } else if directive.starts_with("#else") {
+                if scopes.len() == 1 {
+                    return Err(ProcessShaderError::TooManyEndIfs);
+                }
+                let active = !scopes.pop().unwrap() && *scopes.last().unwrap();
+                scopes.push(active);
+            } else if directive.starts_with("#endif") {
+                if scopes.len() == 1 {
+                    return Err(ProcessShaderError::TooManyEndIfs);
+                }
+                scopes.pop();
+            } else if !*scopes.last().unwrap() {
+                continue;
+            } else if let Some(import) = ShaderImport::parse(directive) {
+                if imports.insert(import.clone()) {
+                    let shader = shaders
+                        .get(&import.handle())
+                        .ok_or(ProcessShaderError::UnresolvedImport(import))?;
+                    self.process_into(shader.source(), shader_defs, shaders, imports, processed)?;
+                }
+            } else {
+                processed.push_str(line);
+                processed.push('\n');
+            }
+        }
+        if scopes.len() != 1 {
+            return Err(ProcessShaderError::NotEnoughEndIfs);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ShaderStage;
+
+    fn process(
+        source: &'static str,
+        shader_defs: &[&str],
+        shaders: &HashMap<Handle<Shader>, Shader>,
+    ) -> Result<String, ProcessShaderError> {
+        let shader = Shader::from_glsl(source, ShaderStage::Fragment);
+        let shader_defs = shader_defs.iter().map(|def| def.to_string()).collect();
+        ShaderProcessor.process(&shader, &shader_defs, shaders, &mut HashSet::default())
+    }
+
+    #[test]
+    fn ifdef_blocks() {
+        let source = "#version 460
+#ifdef A
+a
+#ifndef B
+not b
+#else
+b
+#endif
+#else
+not a
+#endif
+end
+";
+        let shaders = HashMap::default();
+        assert_eq!(
+            process(source, &["A"], &shaders).unwrap(),
+            "#version 460\na\nnot b\nend\n"
+        );
+        assert_eq!(
+            process(source, &["A", "B"], &shaders).unwrap(),
+            "#version 460\na\nb\nend\n"
+        );
+        assert_eq!(
+            process(source, &[], &shaders).unwrap(),
+            "#version 460\nnot a\nend\n"
+        );
+        assert_eq!(
+            process("#ifdef A\n", &[], &shaders),
+            Err(ProcessShaderError::NotEnoughEndIfs)
+        );
+        assert_eq!(
+            process("#endif\n", &[], &shaders),
+            Err(ProcessShaderError::TooManyEndIfs)
+        );
+    }
+
+    #[test]
+    fn includes_are_resolved_once() {
+        let mut shaders = HashMap::default();
+        let common = ShaderImport("shaders/common.glsl".to_string());
+        shaders.insert(
+            common.handle(),
+            Shader::from_glsl_include("#ifdef A\ncommon\n#endif"),
+        );
+        let source =
+            "#version 460\n#include \"shaders/common.glsl\"\n#import <shaders/common.glsl>\nmain\n";
+        assert_eq!(
+            process(source, &["A"], &shaders).unwrap(),
+            "#version 460\ncommon\nmain\n"
+        );
+        assert_eq!(
+            process("#include \"shaders/other.glsl\"", &[], &shaders),
+            Err(ProcessShaderError::UnresolvedImport(ShaderImport(
+                "shaders/other.glsl".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use crate::texture::{Image, PixelFormat, TextureError};
+
+/// The container formats loaded without the `image` crate, to keep their mip levels and their
+/// compressed blocks as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompressedImageFormat {
+    Dds,
+    Ktx2,
+}
+
+impl CompressedImageFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "dds" => Some(CompressedImageFormat::Dds),
+            "ktx2" => Some(CompressedImageFormat::Ktx2),
+            _ => None,
+        }
+    }
+
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type {
+            "image/vnd-ms.dds" => Some(CompressedImageFormat::Dds),
+            "image/ktx2" => Some(CompressedImageFormat::Ktx2),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn load_compressed(
+    buffer: &[u8],
+    format: CompressedImageFormat,
+) -> Result<Image, TextureError> {
+    match format {
+        CompressedImageFormat::Dds => load_dds(buffer),
+        CompressedImageFormat::Ktx2 => load_ktx2(buffer),
+    }
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buffer.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u64(buffer: &[u8], offset: usize) -> Option<u64> {
+    let bytes = buffer.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Alpha is only expected from the formats storing more than a single bit of it.
+fn translucent(format: PixelFormat) -> bool {
+    !matches!(format, PixelFormat::Bc1)
+}
+
+/// The size of the `mip_levels` first levels of an image.
+fn levels_size(format: PixelFormat, (width, height): (u32, u32), mip_levels: u32) -> usize {
+    (0..mip_levels)
+        .map(|level| format.level_size(((width >> level).max(1), (height >> level).max(1))))
+        .sum()
+}
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const DDS_HEADER_SIZE: usize = 128;
+const DDS_DX10_HEADER_SIZE: usize = 20;
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+/// Loads the DXT1, DXT3 and DXT5 blocks or the 32 bits RGBA texels of a 2D DDS file.
+fn load_dds(buffer: &[u8]) -> Result<Image, TextureError> {
+    let invalid = |message: &str| TextureError::InvalidDds(message.to_string());
+    if buffer.len() < DDS_HEADER_SIZE || &buffer[..4] != DDS_MAGIC {
+        return Err(invalid("missing header"));
+    }
+    let header = |offset| read_u32(buffer, offset).unwrap();
+    let flags = header(8);
+    let dimensions = (header(16), header(12));
+    let mip_levels = if flags & DDSD_MIPMAPCOUNT != 0 {
+        header(28).max(1)
+    } else {
+        1
+    };
+    if header(112) & (DDSCAPS2_CUBEMAP | DDSCAPS2_VOLUME) != 0 {
+        return Err(invalid("only 2D images are supported"));
+    }
+
+    let pixel_flags = header(80);
+    let four_cc = &buffer[84..88];
+    let mut offset = DDS_HEADER_SIZE;
+    let mut bgra = false;
+    let format = if pixel_flags & DDPF_FOURCC != 0 {
+        match four_cc {
+            b"DXT1" => PixelFormat::Bc1,
+            b"DXT3" => PixelFormat::Bc2,
+            b"DXT5" => PixelFormat::Bc3,
+            b"DX10" => {
+                offset += DDS_DX10_HEADER_SIZE;
+                let dxgi_format =
+                    read_u32(buffer, DDS_HEADER_SIZE).ok_or_else(|| invalid("missing header"))?;
+                let array_size = read_u32(buffer, DDS_HEADER_SIZE + 12).unwrap_or(1);
+                if array_size > 1 {
+                    return Err(invalid("only 2D images are supported"));
+                }
+                match dxgi_format {
+                    2 => PixelFormat::Rgba32Float,
+                    28 | 29 => PixelFormat::Rgba8,
+                    71 | 72 => PixelFormat::Bc1,
+                    74 | 75 => PixelFormat::Bc2,
+                    77 | 78 => PixelFormat::Bc3,
+                    _ => return Err(invalid(&format!("unsupported DXGI format {}", dxgi_format))),
+                }
+            }
+            _ => {
+                return Err(invalid(&format!(
+                    "unsupported four character code {}",
+                    String::from_utf8_lossy(four_cc)
+                )))
+            }
+        }
+    } else if pixel_flags & DDPF_RGB != 0 && header(88) == 32 {
+        // the blue mask tells RGBA and BGRA apart
+        match header(100) {
+            0x00ff0000 => PixelFormat::Rgba8,
+            0x000000ff => {
+                bgra = true;
+                PixelFormat::Rgba8
+            }
+            _ => return Err(invalid("unsupported channel masks")),
+        }
+    } else {
+        return Err(invalid("unsupported pixel format"));
+    };
+
+    let size = levels_size(format, dimensions, mip_levels);
+    let mut data = buffer
+        .get(offset..offset + size)
+        .ok_or_else(|| invalid("missing mip levels"))?
+        .to_vec();
+    if bgra {
+        for texel in data.chunks_exact_mut(4) {
+            texel.swap(0, 2);
+        }
+    }
+    Ok(Image::new_with_format(
+        data,
+        dimensions,
+        format,
+        mip_levels,
+        translucent(format),
+    ))
+}
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const KTX2_LEVEL_INDEX_OFFSET: usize = 80;
+
+/// Loads the levels of a 2D KTX2 file without supercompression, in one of the
+/// [`PixelFormat`]s.
+fn load_ktx2(buffer: &[u8]) -> Result<Image, TextureError> {
+    let invalid = |message: &str| TextureError::InvalidKtx2(message.to_string());
+    if buffer.len() < KTX2_LEVEL_INDEX_OFFSET || buffer[..12] != KTX2_IDENTIFIER {
+        return Err(invalid("missing header"));
+    }
+    let header = |offset| read_u32(buffer, offset).unwrap();
+    let format = match header(12) {
+        37 | 43 => PixelFormat::Rgba8,
+        109 => PixelFormat::Rgba32Float,
+        131..=134 => PixelFormat::Bc1,
+        135 | 136 => PixelFormat::Bc2,
+        137 | 138 => PixelFormat::Bc3,
+        vk_format => return Err(invalid(&format!("unsupported Vulkan format {}", vk_format))),
+    };
+    let dimensions = (header(20), header(24));
+    // a level count of 0 asks for the mipmaps to be generated
+    let mip_levels = header(40).max(1);
+    if header(28) > 1 || header(32) > 1 || header(36) != 1 {
+        return Err(invalid("only 2D images are supported"));
+    }
+    if header(44) != 0 {
+        return Err(invalid("supercompression is not supported"));
+    }
+
+    let mut data = Vec::with_capacity(levels_size(format, dimensions, mip_levels));
+    for level in 0..mip_levels {
+        let index = KTX2_LEVEL_INDEX_OFFSET + level as usize * 24;
+        let (offset, length) = read_u64(buffer, index)
+            .zip(read_u64(buffer, index + 8))
+            .ok_or_else(|| invalid("missing level index"))?;
+        let level_dimensions = (
+            (dimensions.0 >> level).max(1),
+            (dimensions.1 >> level).max(1),
+        );
+        if length as usize != format.level_size(level_dimensions) {
+            return Err(invalid(&format!("level {} has an invalid size", level)));
+        }
+        let level_data = buffer
+            .get(offset as usize..(offset + length) as usize)
+            .ok_or_else(|| invalid("missing mip levels"))?;
+        data.extend_from_slice(level_data);
+    }
+    Ok(Image::new_with_format(
+        data,
+        dimensions,
+        format,
+        mip_levels,
+        translucent(format),
+    ))
+}
+
+/// Flips a level of compressed blocks upside down, as OpenGL expects its bottom row first.
+///
+/// The rows are flipped inside of the blocks too, the levels whose height is above 4 but not
+/// a multiple of it are shifted by the padding of their blocks.
+pub(crate) fn flip_blocks(
+    data: &[u8],
+    format: PixelFormat,
+    (width, height): (u32, u32),
+) -> Vec<u8> {
+    let block_size = format.level_size((4, 4));
+    let row_size = (width as usize).div_ceil(4) * block_size;
+    let rows = height.min(4) as usize;
+    let mut flipped = Vec::with_capacity(data.len());
+    for block_row in data.chunks(row_size).rev() {
+        for block in block_row.chunks(block_size) {
+            let mut block: [u8; 16] = {
+                let mut padded = [0; 16];
+                padded[..block_size].copy_from_slice(block);
+                padded
+            };
+            match format {
+                PixelFormat::Bc1 => flip_color_block(&mut block[..8], rows),
+                PixelFormat::Bc2 => {
+                    // 4 bits of alpha per texel
+                    let alpha: Vec<[u8; 2]> =
+                        block[..8].chunks(2).map(|row| [row[0], row[1]]).collect();
+                    for row in 0..rows {
+                        block[row * 2..row * 2 + 2].copy_from_slice(&alpha[rows - 1 - row]);
+                    }
+                    flip_color_block(&mut block[8..], rows);
+                }
+                PixelFormat::Bc3 => {
+                    // 3 bits of alpha index per texel, 12 per row
+                    let mut bits = [0; 8];
+                    bits[..6].copy_from_slice(&block[2..8]);
+                    let indices = u64::from_le_bytes(bits);
+                    let mut flipped_indices = indices;
+                    for row in 0..rows {
+                        let source = (indices >> ((rows - 1 - row) * 12)) & 0xfff;
+                        flipped_indices &= !(0xfff << (row * 12));
+                        flipped_indices |= source << (row * 12);
+                    }
+                    block[2..8].copy_from_slice(&flipped_indices.to_le_bytes()[..6]);
+                    flip_color_block(&mut block[8..], rows);
+                }
+                PixelFormat::Rgba8 | PixelFormat::Rgba32Float => {
+                    unreachable!("only compressed formats have blocks")
+                }
+            }
+            flipped.extend_from_slice(&block[..block_size]);
+        }
+    }
+    flipped
+}
+
+/// Flips the 2 bit color indices of a DXT1 block, one byte per row after the two colors.
+fn flip_color_block(block: &mut [u8], rows: usize) {
+    block[4..4 + rows].reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dds_header(four_cc: &[u8; 4], dimensions: (u32, u32), mip_levels: u32) -> Vec<u8> {
+        let mut header = vec![0; DDS_HEADER_SIZE];
+        header[..4].copy_from_slice(DDS_MAGIC);
+        header[4..8].copy_from_slice(&124u32.to_le_bytes());
+        header[8..12].copy_from_slice(&DDSD_MIPMAPCOUNT.to_le_bytes());
+        header[12..16].copy_from_slice(&dimensions.1.to_le_bytes());
+        header[16..20].copy_from_slice(&dimensions.0.to_le_bytes());
+        header[28..32].copy_from_slice(&mip_levels.to_le_bytes());
+        header[80..84].copy_from_slice(&DDPF_FOURCC.to_le_bytes());
+        header[84..88].copy_from_slice(four_cc);
+        header
+    }
+
+    #[test]
+    fn dds_keeps_its_mip_levels() {
+        // 8x8, 4x4, 2x2 and 1x1 levels, the last two in a single block each
+        let mut buffer = dds_header(b"DXT5", (8, 8), 4);
+        buffer.extend((0..4 * 16 + 16 + 16 + 16).map(|i| i as u8));
+        let image = Image::from_buffer(&buffer, crate::ImageType::Extension("dds")).unwrap();
+        assert_eq!(image.format, PixelFormat::Bc3);
+        assert_eq!(image.mip_levels, 4);
+        assert_eq!(image.level(1).unwrap(), (&buffer[192..208], (4, 4)));
+        assert_eq!(image.level(3).unwrap().1, (1, 1));
+
+        buffer.truncate(buffer.len() - 1);
+        assert!(matches!(
+            Image::from_buffer(&buffer, crate::ImageType::Extension("dds")),
+            Err(TextureError::InvalidDds(_))
+        ));
+    }
+
+    #[test]
+    fn blocks_flip_their_rows() {
+        // two DXT1 blocks on top of each other
+        let top = [1, 0, 2, 0, 0x00, 0x01, 0x02, 0x03];
+        let bottom = [3, 0, 4, 0, 0x10, 0x11, 0x12, 0x13];
+        let flipped = flip_blocks(&[top, bottom].concat(), PixelFormat::Bc1, (4, 8));
+        assert_eq!(
+            flipped,
+            [3, 0, 4, 0, 0x13, 0x12, 0x11, 0x10, 1, 0, 2, 0, 0x03, 0x02, 0x01, 0x00]
+        );
+
+        // only the rows of a 2 texels high level are swapped
+        let flipped = flip_blocks(&top, PixelFormat::Bc1, (4, 2));
+        assert_eq!(flipped, [1, 0, 2, 0, 0x01, 0x00, 0x02, 0x03]);
+
+        // the alpha indices of DXT5 are 12 bits per row
+        let mut block = [0; 16];
+        block[2..8].copy_from_slice(&[0x21, 0x43, 0x65, 0x87, 0xa9, 0xcb]);
+        let flipped = flip_blocks(&block, PixelFormat::Bc3, (4, 4));
+        assert_eq!(&flipped[2..8], &[0xba, 0x7c, 0x98, 0x54, 0x16, 0x32]);
+    }
+}
@@ -1,15 +1,32 @@
-use crate::texture::Image;
+use crate::texture::{Image, PixelFormat};
 
 /// Converts a [`DynamicImage`] to an [`Image`].
 pub(crate) fn dynamic_image_to_image(dyn_img: image::DynamicImage) -> Image {
     // Remove this once a way to add diferent size textures is figured out
-    let translucent = match dyn_img {
-        image::DynamicImage::ImageRgba8(_) | image::DynamicImage::ImageRgba16(_) => true,
-        _ => false,
-    };
+    let translucent = matches!(
+        dyn_img,
+        image::DynamicImage::ImageRgba8(_)
+            | image::DynamicImage::ImageRgba16(_)
+            | image::DynamicImage::ImageRgba32F(_)
+    );
 
-    let dyn_img = dyn_img.into_rgba8();
-    let dimensions = dyn_img.dimensions();
-    let data: Vec<u8> = dyn_img.into_raw();
-    Image::new(data, dimensions, translucent)
+    match dyn_img {
+        // HDR images keep their range
+        image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_) => {
+            let dyn_img = dyn_img.into_rgba32f();
+            let dimensions = dyn_img.dimensions();
+            let data: Vec<u8> = dyn_img
+                .into_raw()
+                .into_iter()
+                .flat_map(f32::to_ne_bytes)
+                .collect();
+            Image::new_with_format(data, dimensions, PixelFormat::Rgba32Float, 1, translucent)
+        }
+        _ => {
+            let dyn_img = dyn_img.into_rgba8();
+            let dimensions = dyn_img.dimensions();
+            let data: Vec<u8> = dyn_img.into_raw();
+            Image::new(data, dimensions, translucent)
+        }
+    }
 }
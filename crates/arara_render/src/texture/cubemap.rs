@@ -0,0 +1,326 @@
+use std::f32::consts::PI;
+
+use arara_asset::{AssetEvent, Assets, Handle};
+use arara_ecs::{
+    event::EventReader,
+    system::{NonSend, NonSendMut, Res},
+};
+use arara_utils::{
+    tracing::{error, trace},
+    HashMap,
+};
+use arara_window::Window;
+use bevy_reflect::TypeUuid;
+use glam::Vec3;
+use glium::{
+    backend::Facade,
+    framebuffer::SimpleFrameBuffer,
+    texture::{
+        CubeLayer, Cubemap as GliumCubemap, MipmapsOption, RawImage2d, UncompressedFloatFormat,
+    },
+    uniforms::MagnifySamplerFilter,
+    BlitTarget, Surface, Texture2d,
+};
+use thiserror::Error;
+
+use crate::{DefaultImageSampler, Image, PixelFormat};
+
+const CUBE_LAYERS: [CubeLayer; 6] = [
+    CubeLayer::PositiveX,
+    CubeLayer::NegativeX,
+    CubeLayer::PositiveY,
+    CubeLayer::NegativeY,
+    CubeLayer::PositiveZ,
+    CubeLayer::NegativeZ,
+];
+
+/// The six faces of a cube surrounding the viewer, sampled with a direction, like the
+/// `Skybox` of a camera.
+///
+/// The images are uploaded once all of them are loaded, in a float texture keeping the range of
+/// HDR images. Compressed images are not supported.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "3c1f2d8e-7b4a-4e2b-9f61-8d0c5a7e2b14"]
+#[allow(clippy::large_enum_variant)]
+pub enum Cubemap {
+    /// The `+X`, `-X`, `+Y`, `-Y`, `+Z` and `-Z` faces, square images of the same size.
+    Faces([Handle<Image>; 6]),
+    /// A panorama covering every direction, the longitude along its width and the latitude
+    /// along its height. Every face is half as wide as the image is high.
+    Equirectangular(Handle<Image>),
+}
+
+impl Cubemap {
+    pub fn images(&self) -> &[Handle<Image>] {
+        match self {
+            Cubemap::Faces(faces) => faces,
+            Cubemap::Equirectangular(image) => std::slice::from_ref(image),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CubemapError {
+    #[error("the faces of a cubemap must be square images of the same size")]
+    InvalidFaces,
+    #[error("compressed images can't be used in a cubemap")]
+    Compressed,
+    #[error(transparent)]
+    TextureCreation(#[from] glium::texture::TextureCreationError),
+    #[error(transparent)]
+    Framebuffer(#[from] glium::framebuffer::ValidationError),
+}
+
+/// Every loaded [`Cubemap`] uploaded to the GPU, kept in sync with the [`Assets<Cubemap>`].
+#[derive(Default)]
+pub struct GpuCubemaps {
+    cubemaps: HashMap<Handle<Cubemap>, GliumCubemap>,
+    /// Created or modified cubemaps waiting for their images
+    queue: Vec<Handle<Cubemap>>,
+}
+
+impl GpuCubemaps {
+    pub fn get(&self, handle: &Handle<Cubemap>) -> Option<&GliumCubemap> {
+        self.cubemaps.get(handle)
+    }
+
+    pub fn contains(&self, handle: &Handle<Cubemap>) -> bool {
+        self.cubemaps.contains_key(handle)
+    }
+
+    fn enqueue(&mut self, handle: &Handle<Cubemap>) {
+        if !self.queue.contains(handle) {
+            self.queue.push(handle.clone_weak());
+        }
+    }
+}
+
+/// Uploads the cubemaps whose images are loaded, again when one of them is modified.
+pub(crate) fn extract_cubemaps(
+    window: NonSend<Window>,
+    cubemaps: Res<Assets<Cubemap>>,
+    images: Res<Assets<Image>>,
+    default_sampler: Res<DefaultImageSampler>,
+    mut cubemap_events: EventReader<AssetEvent<Cubemap>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut gpu_cubemaps: NonSendMut<GpuCubemaps>,
+) {
+    for event in cubemap_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                gpu_cubemaps.enqueue(handle);
+            }
+            AssetEvent::Removed { handle } => {
+                gpu_cubemaps.cubemaps.remove(handle);
+                gpu_cubemaps.queue.retain(|queued| queued != handle);
+            }
+        }
+    }
+    for event in image_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            for (id, cubemap) in cubemaps.iter() {
+                if cubemap.images().contains(handle) {
+                    gpu_cubemaps.enqueue(&Handle::weak(id));
+                }
+            }
+        }
+    }
+
+    let queue = std::mem::take(&mut gpu_cubemaps.queue);
+    for handle in queue {
+        let cubemap = match cubemaps.get(&handle) {
+            Some(cubemap) => cubemap,
+            None => continue,
+        };
+        let loaded: Option<Vec<&Image>> = cubemap
+            .images()
+            .iter()
+            .map(|image| images.get(image))
+            .collect();
+        let loaded = match loaded {
+            Some(loaded) => loaded,
+            None => {
+                gpu_cubemaps.queue.push(handle);
+                continue;
+            }
+        };
+        trace!("uploading cubemap {:?}", handle);
+        let uploaded = match loaded.as_slice() {
+            _ if loaded.iter().any(|image| image.format.is_compressed()) => {
+                Err(CubemapError::Compressed)
+            }
+            [image] => {
+                let faces = equirectangular_to_faces(image);
+                upload_cubemap(
+                    window.facade(),
+                    &faces.iter().collect::<Vec<_>>(),
+                    &default_sampler,
+                )
+            }
+            faces => upload_cubemap(window.facade(), faces, &default_sampler),
+        };
+        match uploaded {
+            Ok(texture) => {
+                gpu_cubemaps.cubemaps.insert(handle, texture);
+            }
+            Err(err) => error!("Failed to upload cubemap {:?}: {}", handle, err),
+        }
+    }
+}
+
+/// Uploads every face then copies it to its layer, glium can't write to a cubemap directly.
+fn upload_cubemap(
+    facade: &dyn Facade,
+    faces: &[&Image],
+    default_sampler: &DefaultImageSampler,
+) -> Result<GliumCubemap, CubemapError> {
+    let size = faces[0].dimensions.0;
+    if faces.len() != 6 || faces.iter().any(|face| face.dimensions != (size, size)) {
+        return Err(CubemapError::InvalidFaces);
+    }
+
+    let cubemap = GliumCubemap::empty_with_format(
+        facade,
+        UncompressedFloatFormat::F16F16F16F16,
+        MipmapsOption::NoMipmap,
+        size,
+    )?;
+    let target_rect = BlitTarget {
+        left: 0,
+        bottom: 0,
+        width: size as i32,
+        height: size as i32,
+    };
+    for (face, layer) in faces.iter().zip(CUBE_LAYERS) {
+        // the first row of a face is its top, as cubemaps expect
+        let srgb = face.sampler.resolve(default_sampler).srgb;
+        let raw_image = RawImage2d::from_raw_rgba(linear_texels(face, srgb), face.dimensions);
+        let texture = Texture2d::with_format(
+            facade,
+            raw_image,
+            UncompressedFloatFormat::F16F16F16F16,
+            MipmapsOption::NoMipmap,
+        )?;
+        let source = SimpleFrameBuffer::new(facade, &texture)?;
+        let target = SimpleFrameBuffer::new(facade, cubemap.main_level().image(layer))?;
+        source.blit_whole_color_to(&target, &target_rect, MagnifySamplerFilter::Nearest);
+    }
+    Ok(cubemap)
+}
+
+/// The channels of the first level of an uncompressed `image` in linear space, decoding them
+/// if they are `srgb`.
+fn linear_texels(image: &Image, srgb: bool) -> Vec<f32> {
+    let (data, _) = image.level(0).unwrap();
+    match image.format {
+        PixelFormat::Rgba8 => data
+            .chunks_exact(4)
+            .flat_map(|texel| {
+                let channel = |value: u8| {
+                    let value = value as f32 / 255.0;
+                    if srgb {
+                        srgb_to_linear(value)
+                    } else {
+                        value
+                    }
+                };
+                // alpha is always linear
+                [
+                    channel(texel[0]),
+                    channel(texel[1]),
+                    channel(texel[2]),
+                    texel[3] as f32 / 255.0,
+                ]
+            })
+            .collect(),
+        PixelFormat::Rgba32Float => data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect(),
+        PixelFormat::Bc1 | PixelFormat::Bc2 | PixelFormat::Bc3 => {
+            unreachable!("compressed images are rejected before being uploaded")
+        }
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The direction of the texel at `(s, t)` of the face of `layer`, both in `[-1, 1]` with `t`
+/// going down the face, following the OpenGL cubemap conventions.
+fn face_direction(layer: CubeLayer, s: f32, t: f32) -> Vec3 {
+    match layer {
+        CubeLayer::PositiveX => Vec3::new(1.0, -t, -s),
+        CubeLayer::NegativeX => Vec3::new(-1.0, -t, s),
+        CubeLayer::PositiveY => Vec3::new(s, 1.0, t),
+        CubeLayer::NegativeY => Vec3::new(s, -1.0, -t),
+        CubeLayer::PositiveZ => Vec3::new(s, -t, 1.0),
+        CubeLayer::NegativeZ => Vec3::new(-s, -t, -1.0),
+    }
+}
+
+/// Samples the six faces out of an equirectangular panorama, with the `+Z` face looking at
+/// its center. The faces keep the format and the sampler of the panorama.
+///
+/// # Panics
+/// Panics if the panorama is compressed.
+pub fn equirectangular_to_faces(image: &Image) -> [Image; 6] {
+    let (width, height) = image.dimensions;
+    let size = (height / 2).max(1);
+    let texel_size = image
+        .format
+        .texel_size()
+        .expect("compressed panoramas can't be sampled");
+    CUBE_LAYERS.map(|layer| {
+        let mut data = Vec::with_capacity(size as usize * size as usize * texel_size);
+        for y in 0..size {
+            for x in 0..size {
+                let s = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let t = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let direction = face_direction(layer, s, t).normalize();
+                let u = 0.5 + direction.x.atan2(direction.z) / (2.0 * PI);
+                let v = 0.5 - direction.y.asin() / PI;
+                let column = ((u * width as f32) as u32).min(width - 1);
+                let row = ((v * height as f32) as u32).min(height - 1);
+                let i = (row * width + column) as usize * texel_size;
+                data.extend_from_slice(&image.data[i..i + texel_size]);
+            }
+        }
+        Image::new_with_format(data, (size, size), image.format, 1, image.translucent)
+            .with_sampler(image.sampler)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faces_sample_their_side_of_the_panorama() {
+        // the top half is white, the bottom half black
+        let (width, height) = (8, 4);
+        let data = (0..width * height)
+            .flat_map(|i| {
+                let value = if i < width * height / 2 { 255 } else { 0 };
+                [value, value, value, 255]
+            })
+            .collect();
+        let faces = equirectangular_to_faces(&Image::new(data, (width, height), false));
+
+        assert!(faces.iter().all(|face| face.dimensions == (2, 2)));
+        // +Y and -Y
+        assert!(faces[2].data.chunks(4).all(|texel| texel[0] == 255));
+        assert!(faces[3].data.chunks(4).all(|texel| texel[0] == 0));
+        // the first row of a side face is above the horizon
+        assert_eq!(
+            &faces[4].data[..8],
+            &[255, 255, 255, 255, 255, 255, 255, 255]
+        );
+        assert_eq!(&faces[4].data[8..], &[0, 0, 0, 255, 0, 0, 0, 255]);
+    }
+}
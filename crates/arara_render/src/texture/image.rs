@@ -1,13 +1,69 @@
-use super::converters::dynamic_image_to_image;
+use std::path::Path;
+
+use super::{
+    compressed::{load_compressed, CompressedImageFormat},
+    converters::dynamic_image_to_image,
+};
+use crate::ImageSampler;
 use bevy_reflect::TypeUuid;
 use thiserror::Error;
 
+/// How the texels of an [`Image`] are stored in its data.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8 bits per channel, sRGB or linear depending on the [`ImageSampler`]
+    Rgba8,
+    /// A linear `f32` per channel, for HDR images, uploaded as a float texture
+    Rgba32Float,
+    /// S3TC DXT1 compressed blocks, without or with a 1 bit alpha
+    Bc1,
+    /// S3TC DXT3 compressed blocks, with an explicit alpha
+    Bc2,
+    /// S3TC DXT5 compressed blocks, with an interpolated alpha
+    Bc3,
+}
+
+impl PixelFormat {
+    /// Whether the texels are stored in blocks of 4x4 texels.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self, PixelFormat::Bc1 | PixelFormat::Bc2 | PixelFormat::Bc3)
+    }
+
+    /// The size in bytes of a texel, `None` for compressed formats.
+    pub fn texel_size(&self) -> Option<usize> {
+        match self {
+            PixelFormat::Rgba8 => Some(4),
+            PixelFormat::Rgba32Float => Some(16),
+            PixelFormat::Bc1 | PixelFormat::Bc2 | PixelFormat::Bc3 => None,
+        }
+    }
+
+    /// The size in bytes of an image of `dimensions`.
+    pub fn level_size(&self, (width, height): (u32, u32)) -> usize {
+        let (width, height) = (width as usize, height as usize);
+        match self {
+            PixelFormat::Rgba8 | PixelFormat::Rgba32Float => {
+                width * height * self.texel_size().unwrap()
+            }
+            PixelFormat::Bc1 => width.div_ceil(4) * height.div_ceil(4) * 8,
+            PixelFormat::Bc2 | PixelFormat::Bc3 => width.div_ceil(4) * height.div_ceil(4) * 16,
+        }
+    }
+}
+
 #[derive(Debug, Clone, TypeUuid)]
 #[uuid = "6ea26da6-6cf8-4ea2-9986-1d7bf6c17d6f"]
 pub struct Image {
+    /// Every mip level one after the other, starting with the full size image, each of them
+    /// starting with its top row
     pub data: Vec<u8>,
     pub dimensions: (u32, u32),
+    pub format: PixelFormat,
+    /// Mip levels in the data, the mipmaps of the [`ImageSampler`] are only generated when
+    /// there is a single level
+    pub mip_levels: u32,
     pub translucent: bool,
+    pub sampler: ImageSampler,
 }
 
 impl Default for Image {
@@ -18,31 +74,85 @@ impl Default for Image {
 }
 
 impl Image {
-    /// Creates a new image from raw binary data and the corresponding metadata.
+    /// Creates a new [`PixelFormat::Rgba8`] image from raw binary data and the corresponding
+    /// metadata.
+    pub fn new(data: Vec<u8>, dimensions: (u32, u32), translucent: bool) -> Self {
+        Self::new_with_format(data, dimensions, PixelFormat::Rgba8, 1, translucent)
+    }
+
+    /// Creates a new image holding `mip_levels` levels of `format`.
     ///
     /// # Panics
-    /// Panics if the length of the `data`, volume of the `size` and the size of the `format`
-    /// do not match.
-    pub fn new(data: Vec<u8>, dimensions: (u32, u32), translucent: bool) -> Self {
-        Self {
+    /// Panics if the length of the `data` doesn't match the size of the levels.
+    pub fn new_with_format(
+        data: Vec<u8>,
+        dimensions: (u32, u32),
+        format: PixelFormat,
+        mip_levels: u32,
+        translucent: bool,
+    ) -> Self {
+        let image = Self {
             data,
             dimensions,
+            format,
+            mip_levels: mip_levels.max(1),
             translucent,
+            sampler: ImageSampler::Default,
+        };
+        let size: usize = (0..image.mip_levels)
+            .map(|level| format.level_size(image.level_dimensions(level)))
+            .sum();
+        assert_eq!(
+            image.data.len(),
+            size,
+            "the data doesn't match the levels of the image"
+        );
+        image
+    }
+
+    pub fn with_sampler(mut self, sampler: ImageSampler) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// The dimensions of the mip `level`, `0` being the full size image.
+    pub fn level_dimensions(&self, level: u32) -> (u32, u32) {
+        let (width, height) = self.dimensions;
+        ((width >> level).max(1), (height >> level).max(1))
+    }
+
+    /// The data and the dimensions of the mip `level`, if the image has it.
+    pub fn level(&self, level: u32) -> Option<(&[u8], (u32, u32))> {
+        if level >= self.mip_levels {
+            return None;
         }
+        let start: usize = (0..level)
+            .map(|level| self.format.level_size(self.level_dimensions(level)))
+            .sum();
+        let dimensions = self.level_dimensions(level);
+        let end = start + self.format.level_size(dimensions);
+        Some((&self.data[start..end], dimensions))
     }
 
     /// Load a bytes buffer in a [`Texture`], according to type `image_type`, using the `image`
-    /// crate`
+    /// crate, or the DDS and KTX2 loaders which keep the mip levels of the file.
     pub fn from_buffer(buffer: &[u8], image_type: ImageType) -> Result<Image, TextureError> {
+        let compressed_format = match image_type {
+            ImageType::MimeType(mime_type) => CompressedImageFormat::from_mime_type(mime_type),
+            ImageType::Extension(extension) => CompressedImageFormat::from_extension(extension),
+        };
+        if let Some(compressed_format) = compressed_format {
+            return load_compressed(buffer, compressed_format);
+        }
+
         let format = match image_type {
             ImageType::MimeType(mime_type) => match mime_type {
                 "image/png" => Ok(image::ImageFormat::Png),
                 "image/jpeg" => Ok(image::ImageFormat::Jpeg),
-                // "image/bmp" => Ok(image::ImageFormat::Bmp),
-                // "image/vnd-ms.dds" => Ok(image::ImageFormat::Dds),
-                // "image/x-targa" => Ok(image::ImageFormat::Tga),
-                // "image/x-tga" => Ok(image::ImageFormat::Tga),
-                // "image/x-bmp" => Ok(image::ImageFormat::Bmp),
+                "image/bmp" | "image/x-bmp" => Ok(image::ImageFormat::Bmp),
+                "image/x-targa" | "image/x-tga" => Ok(image::ImageFormat::Tga),
+                "image/vnd.radiance" => Ok(image::ImageFormat::Hdr),
+                "image/x-exr" => Ok(image::ImageFormat::OpenExr),
                 _ => Err(TextureError::InvalidImageMimeType(mime_type.to_string())),
             },
             ImageType::Extension(extension) => image::ImageFormat::from_extension(extension)
@@ -58,6 +168,19 @@ impl Image {
         let dyn_img = image::load_from_memory_with_format(buffer, format)?;
         Ok(dynamic_image_to_image(dyn_img))
     }
+
+    /// Saves the image to `path` using the `image` crate,
+    /// the file format is deduced from the extension of the path.
+    /// Only the first level of [`PixelFormat::Rgba8`] images can be saved.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), TextureError> {
+        if self.format != PixelFormat::Rgba8 {
+            return Err(TextureError::UnsupportedFormat(self.format));
+        }
+        let (width, height) = self.dimensions;
+        let (data, _) = self.level(0).unwrap();
+        image::save_buffer(path, data, width, height, image::ColorType::Rgba8)?;
+        Ok(())
+    }
 }
 
 /// An error that occurs when loading a texture
@@ -69,9 +192,16 @@ pub enum TextureError {
     InvalidImageExtension(String),
     #[error("failed to load an image: {0}")]
     ImageError(#[from] image::ImageError),
+    #[error("invalid DDS file: {0}")]
+    InvalidDds(String),
+    #[error("invalid KTX2 file: {0}")]
+    InvalidKtx2(String),
+    #[error("unsupported pixel format {0:?}")]
+    UnsupportedFormat(PixelFormat),
 }
 
 /// The type of a raw image buffer.
+#[derive(Clone, Copy)]
 pub enum ImageType<'a> {
     /// The mime type of an image, for example `"image/png"`.
     #[allow(dead_code)]
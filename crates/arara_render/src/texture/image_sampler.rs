@@ -0,0 +1,170 @@
+use glium::uniforms::{
+    MagnifySamplerFilter, MinifySamplerFilter, SamplerBehavior, SamplerWrapFunction,
+};
+
+/// How the texels of an image are blended together.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum FilterMode {
+    /// The closest texel, keeps pixel art sharp
+    Nearest,
+    /// Interpolates between the closest texels
+    Linear,
+}
+
+/// How the texture coordinates outside of `[0, 1]` are handled.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Tiles the image
+    Repeat,
+    /// Tiles the image, mirroring every other tile
+    MirroredRepeat,
+    /// Stretches the texels of the edges
+    ClampToEdge,
+}
+
+impl From<WrapMode> for SamplerWrapFunction {
+    fn from(wrap_mode: WrapMode) -> Self {
+        match wrap_mode {
+            WrapMode::Repeat => SamplerWrapFunction::Repeat,
+            WrapMode::MirroredRepeat => SamplerWrapFunction::Mirror,
+            WrapMode::ClampToEdge => SamplerWrapFunction::Clamp,
+        }
+    }
+}
+
+/// How an [`Image`](crate::Image) is uploaded and sampled.
+///
+/// The mipmaps and the color space are applied when the texture is uploaded, the image is
+/// uploaded again when they change.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SamplerDescriptor {
+    /// Used when the image is drawn bigger than it is
+    pub mag_filter: FilterMode,
+    /// Used when the image is drawn smaller than it is
+    pub min_filter: FilterMode,
+    /// Used between the mipmap levels
+    pub mipmap_filter: FilterMode,
+    pub wrap_u: WrapMode,
+    pub wrap_v: WrapMode,
+    /// Maximum anisotropy of the filtering, `1` disables it
+    pub anisotropy: u16,
+    /// Uses the mip levels of the image, generated when it is uploaded if it has a single one
+    pub mipmaps: bool,
+    /// The data is sRGB encoded like colors are, otherwise it is linear like normal maps
+    pub srgb: bool,
+}
+
+impl Default for SamplerDescriptor {
+    fn default() -> Self {
+        Self {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            wrap_u: WrapMode::MirroredRepeat,
+            wrap_v: WrapMode::MirroredRepeat,
+            anisotropy: 1,
+            mipmaps: true,
+            srgb: true,
+        }
+    }
+}
+
+impl SamplerDescriptor {
+    /// Sharp texels without mipmaps, for pixel art.
+    pub fn nearest() -> Self {
+        Self {
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            mipmaps: false,
+            ..Default::default()
+        }
+    }
+
+    /// Tiles the image in both directions.
+    pub fn repeat() -> Self {
+        Self {
+            wrap_u: WrapMode::Repeat,
+            wrap_v: WrapMode::Repeat,
+            ..Default::default()
+        }
+    }
+
+    /// The glium sampler of a texture, which only uses the mipmaps if it `has_mipmaps`.
+    pub fn behavior(&self, has_mipmaps: bool) -> SamplerBehavior {
+        let minify_filter = match (self.min_filter, has_mipmaps.then_some(self.mipmap_filter)) {
+            (FilterMode::Nearest, None) => MinifySamplerFilter::Nearest,
+            (FilterMode::Linear, None) => MinifySamplerFilter::Linear,
+            (FilterMode::Nearest, Some(FilterMode::Nearest)) => {
+                MinifySamplerFilter::NearestMipmapNearest
+            }
+            (FilterMode::Linear, Some(FilterMode::Nearest)) => {
+                MinifySamplerFilter::LinearMipmapNearest
+            }
+            (FilterMode::Nearest, Some(FilterMode::Linear)) => {
+                MinifySamplerFilter::NearestMipmapLinear
+            }
+            (FilterMode::Linear, Some(FilterMode::Linear)) => {
+                MinifySamplerFilter::LinearMipmapLinear
+            }
+        };
+        SamplerBehavior {
+            wrap_function: (
+                self.wrap_u.into(),
+                self.wrap_v.into(),
+                SamplerWrapFunction::Mirror,
+            ),
+            minify_filter,
+            magnify_filter: match self.mag_filter {
+                FilterMode::Nearest => MagnifySamplerFilter::Nearest,
+                FilterMode::Linear => MagnifySamplerFilter::Linear,
+            },
+            depth_texture_comparison: None,
+            max_anisotropy: self.anisotropy.max(1),
+        }
+    }
+}
+
+/// The sampler of an [`Image`](crate::Image).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum ImageSampler {
+    /// The [`DefaultImageSampler`]
+    #[default]
+    Default,
+    Descriptor(SamplerDescriptor),
+}
+
+impl ImageSampler {
+    /// The descriptor of the sampler, `default` for [`ImageSampler::Default`].
+    pub fn resolve(&self, default: &DefaultImageSampler) -> SamplerDescriptor {
+        match self {
+            ImageSampler::Default => default.0,
+            ImageSampler::Descriptor(descriptor) => *descriptor,
+        }
+    }
+}
+
+/// The sampler of the images using [`ImageSampler::Default`], they are uploaded again when it
+/// changes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultImageSampler(pub SamplerDescriptor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mipmap_filters_need_mipmaps() {
+        let descriptor = SamplerDescriptor::default();
+        assert_eq!(
+            descriptor.behavior(true).minify_filter,
+            MinifySamplerFilter::LinearMipmapLinear
+        );
+        assert_eq!(
+            descriptor.behavior(false).minify_filter,
+            MinifySamplerFilter::Linear
+        );
+        // glium's default sampler
+        assert_eq!(descriptor.behavior(true), SamplerBehavior::default());
+    }
+}
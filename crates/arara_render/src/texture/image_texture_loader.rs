@@ -5,11 +5,14 @@ use thiserror::Error;
 
 use crate::texture::{Image, ImageType, TextureError};
 
-/// Loader for images that can be read by the `image` crate.
+/// Loader for images that can be read by the `image` crate, HDR and EXR images keep their float
+/// texels. DDS and KTX2 images keep their mip levels and their compressed blocks.
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
-const FILE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg" /*"dds", "tga", "bmp"*/];
+const FILE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "bmp", "tga", "hdr", "exr", "dds", "ktx2",
+];
 
 impl AssetLoader for ImageTextureLoader {
     fn load<'a>(
@@ -55,11 +58,15 @@ impl std::fmt::Display for FileTextureError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::texture::compressed::CompressedImageFormat;
 
     #[test]
     fn test_supported_file_extensions() {
         for ext in FILE_EXTENSIONS {
-            assert!(image::ImageFormat::from_extension(ext).is_some())
+            assert!(
+                image::ImageFormat::from_extension(ext).is_some()
+                    || CompressedImageFormat::from_extension(ext).is_some()
+            )
         }
     }
 }
@@ -1,5 +1,8 @@
+mod compressed;
 pub(crate) mod converters;
+mod cubemap;
 mod image;
+mod image_sampler;
 mod image_texture_loader;
 mod texture_buffer;
 
@@ -9,24 +12,34 @@ pub use self::image::*;
 use arara_app::{App, Plugin};
 use arara_asset::{AddAsset, Assets, HandleUntyped};
 use bevy_reflect::TypeUuid;
+pub use cubemap::*;
+pub use image_sampler::*;
 pub use image_texture_loader::*;
 pub use texture_buffer::*;
 
 pub const DEFAULT_IMAGE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Image::TYPE_UUID, 13148262314052771789);
 
-/// Adds the [`Image`] as an asset and makes sure that they are extracted and prepared for the GPU.
+/// Adds the [`Image`] and the [`Cubemap`] as assets and makes sure that they are extracted and prepared for the GPU.
 pub struct ImagePlugin;
 
 impl Plugin for ImagePlugin {
     fn build(&self, app: &mut App) {
         app.init_asset_loader::<ImageTextureLoader>()
-            .add_asset::<Image>();
+            .add_asset::<Image>()
+            .add_asset::<Cubemap>();
         app.world
             .get_resource_mut::<Assets<Image>>()
             .unwrap()
             .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
-        app.init_non_send_resource::<TextureBuffer>()
+        app.init_resource::<TextureBufferSettings>()
+            .init_resource::<DefaultImageSampler>()
+            .init_non_send_resource::<TextureBuffer>()
+            .init_non_send_resource::<GpuCubemaps>()
+            .add_system_to_stage(RenderStage::Extract, apply_texture_buffer_settings)
+            .add_system_to_stage(RenderStage::Extract, remove_modified_textures)
+            .add_system_to_stage(RenderStage::Extract, apply_default_image_sampler)
+            .add_system_to_stage(RenderStage::Extract, extract_cubemaps)
             .add_system_to_stage(RenderStage::Queue, process_queue_to_gpu)
             .add_system_to_stage(RenderStage::Cleanup, update_texture_cache_system);
     }
@@ -1,113 +1,487 @@
-use arara_asset::{Assets, Handle};
-use arara_ecs::system::{NonSend, NonSendMut, Res};
-use arara_utils::tracing::trace;
+use arara_asset::{AssetEvent, Assets, Handle};
+use arara_ecs::{
+    event::EventReader,
+    system::{NonSend, NonSendMut, Res},
+};
+use arara_utils::tracing::{error, trace};
 use arara_utils::HashMap;
 use arara_window::Window;
-use glium::implement_uniform_block;
-use glium::texture::{
-    RawImage2d, ResidentTexture, SrgbTexture2d, TextureHandle as GliumTextureHandle,
+use glium::{
+    backend::Facade,
+    buffer::BufferCreationError,
+    program::UniformBlock as UniformBlockLayout,
+    texture::{
+        CompressedFormat, CompressedMipmapsOption, CompressedSrgbFormat, CompressedSrgbTexture2d,
+        CompressedTexture2d, MipmapsOption, RawImage2d, ResidentTexture, SrgbTexture2d, TextureAny,
+        TextureCreationError, TextureHandle as GliumTextureHandle, UncompressedFloatFormat,
+    },
+    uniforms::{
+        LayoutMismatchError, SamplerBehavior, UniformBlock, UniformBuffer, UniformValue, Uniforms,
+    },
+    Rect, Texture2d,
+};
+use std::{cell::RefCell, rc::Rc};
+use thiserror::Error;
+
+use super::compressed::flip_blocks;
+use crate::{
+    DefaultImageSampler, Image, ImageSampler, PixelFormat, RenderCapabilities, RenderPipelineCache,
+    RenderTargets, SamplerDescriptor, DEFAULT_IMAGE_HANDLE,
 };
-use std::collections::VecDeque;
 
-use crate::{Image, DEFAULT_IMAGE_HANDLE};
+/// Name of the shader define holding [`TextureBufferSettings::slots`], to be used as the size
+/// of the sampler array: `uniform samplers { sampler2D tex[TEXTURE_BUFFER_SIZE]; };`
+pub const TEXTURE_BUFFER_SIZE_DEFINE: &str = "TEXTURE_BUFFER_SIZE";
+
+/// Name of the shader define declaring one `uniform sampler2D tex_<slot>;` per slot, for the
+/// contexts without bindless textures.
+pub const TEXTURE_SAMPLERS_DEFINE: &str = "TEXTURE_SAMPLERS";
+
+/// Configures the amount of textures bound by a single draw call.
+///
+/// Batches sampling more images than that are split into several draw calls, so a higher value
+/// means less draw calls at the cost of a bigger uniform buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct TextureBufferSettings {
+    /// Clamped to at least 1.
+    pub slots: usize,
+}
+
+impl Default for TextureBufferSettings {
+    fn default() -> Self {
+        Self { slots: 16 }
+    }
+}
+
+/// The images bound by a single draw call, shaders sample them as `tex[slot]`.
+#[derive(Debug, Clone)]
+pub struct TextureSlots {
+    capacity: usize,
+    images: Vec<Handle<Image>>,
+    per_texture: bool,
+}
+
+impl TextureSlots {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            images: Vec::new(),
+            per_texture: false,
+        }
+    }
+
+    /// Slots for shaders that can't index the textures with a per instance value, the images
+    /// of every instance have to be bound to the same slots, in order.
+    pub fn per_texture(capacity: usize) -> Self {
+        Self {
+            per_texture: true,
+            ..Self::new(capacity)
+        }
+    }
 
-pub const TEXTURE_BUFFER_SIZE: usize = 5;
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
 
-#[derive(Copy, Clone)]
-pub struct TextureUniformBuffer<'a> {
-    pub tex: [GliumTextureHandle<'a>; TEXTURE_BUFFER_SIZE],
+    pub fn images(&self) -> &[Handle<Image>] {
+        &self.images
+    }
+
+    /// The slot of `image`, if it is bound.
+    pub fn get(&self, image: &Handle<Image>) -> Option<usize> {
+        self.images.iter().position(|bound| bound == image)
+    }
+
+    /// Whether all the `images` can be bound at the same time as the already bound ones.
+    pub fn fits<'a>(&self, images: impl IntoIterator<Item = &'a Handle<Image>>) -> bool {
+        if self.per_texture {
+            return images.into_iter().enumerate().all(|(slot, image)| {
+                match self.images.get(slot) {
+                    Some(bound) => bound == image,
+                    None => slot < self.capacity,
+                }
+            });
+        }
+        let mut missing: Vec<&Handle<Image>> = Vec::new();
+        for image in images {
+            if self.get(image).is_none() && !missing.contains(&image) {
+                missing.push(image);
+            }
+        }
+        self.images.len() + missing.len() <= self.capacity
+    }
+
+    /// The slot of `image`, binding it to a free one if needed.
+    /// Returns `None` when every slot is taken by other images.
+    pub fn get_or_insert(&mut self, image: &Handle<Image>) -> Option<usize> {
+        if let Some(slot) = self.get(image) {
+            return Some(slot);
+        }
+        if self.images.len() == self.capacity {
+            return None;
+        }
+        self.images.push(image.clone_weak());
+        Some(self.images.len() - 1)
+    }
 }
 
-implement_uniform_block!(TextureUniformBuffer<'a>, tex);
+/// A texture of the [`TextureBuffer`].
+pub enum GpuTexture {
+    /// Sampled through a bindless handle, see [`RenderCapabilities::bindless_textures`].
+    Resident(ResidentTexture),
+    /// An sRGB image bound to a sampler uniform.
+    Srgb(SrgbTexture2d),
+    /// A linear or float image, or a [`RenderTarget`](crate::RenderTarget) bound to a sampler
+    /// uniform.
+    Linear(Texture2d),
+    /// A compressed sRGB image bound to a sampler uniform.
+    CompressedSrgb(CompressedSrgbTexture2d),
+    /// A compressed linear image bound to a sampler uniform.
+    Compressed(CompressedTexture2d),
+}
+
+impl GpuTexture {
+    pub fn texture(&self) -> &TextureAny {
+        match self {
+            GpuTexture::Resident(texture) => texture,
+            GpuTexture::Srgb(texture) => texture,
+            GpuTexture::Linear(texture) => texture,
+            GpuTexture::CompressedSrgb(texture) => texture,
+            GpuTexture::Compressed(texture) => texture,
+        }
+    }
+
+    fn handle(&self, sampler: &SamplerBehavior) -> Option<GliumTextureHandle<'_>> {
+        match self {
+            GpuTexture::Resident(texture) => Some(GliumTextureHandle::new(texture, sampler)),
+            _ => None,
+        }
+    }
+
+    fn sampler_uniform(&self, sampler: SamplerBehavior) -> Option<UniformValue<'_>> {
+        let sampler = Some(sampler);
+        match self {
+            GpuTexture::Resident(_) => None,
+            GpuTexture::Srgb(texture) => Some(UniformValue::SrgbTexture2d(texture, sampler)),
+            GpuTexture::Linear(texture) => Some(UniformValue::Texture2d(texture, sampler)),
+            GpuTexture::CompressedSrgb(texture) => {
+                Some(UniformValue::CompressedSrgbTexture2d(texture, sampler))
+            }
+            GpuTexture::Compressed(texture) => {
+                Some(UniformValue::CompressedTexture2d(texture, sampler))
+            }
+        }
+    }
+}
+
+/// The textures of a draw call.
+pub enum TextureBindings<'a> {
+    /// The `samplers` uniform block, with one bindless handle per slot.
+    Bindless(Rc<UniformBuffer<[u64]>>),
+    /// One `tex_<slot>` sampler uniform per bound image.
+    Samplers(Vec<UniformValue<'a>>),
+}
+
+impl Uniforms for TextureBindings<'_> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        match self {
+            TextureBindings::Bindless(buffer) => {
+                fn matches_layout(block: &UniformBlockLayout) -> Result<(), LayoutMismatchError> {
+                    <[GliumTextureHandle] as UniformBlock>::matches(&block.layout, 0)
+                }
+                output(
+                    "samplers",
+                    UniformValue::Block(buffer.as_slice_any(), matches_layout),
+                )
+            }
+            TextureBindings::Samplers(samplers) => {
+                for (slot, sampler) in samplers.iter().enumerate() {
+                    output(&format!("tex_{}", slot), *sampler);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TextureBindingsError {
+    #[error("none of the textures are on the GPU yet")]
+    NotUploaded,
+    #[error("failed to create the uniform buffer of the texture handles: {0}")]
+    Buffer(#[from] BufferCreationError),
+}
+
+#[derive(Error, Debug)]
+pub enum UploadImageError {
+    #[error("failed to create the texture: {0}")]
+    Texture(#[from] TextureCreationError),
+    #[error("failed to write the level {0} of the compressed texture")]
+    CompressedLevel(u32),
+    #[error("the context doesn't support bindless textures")]
+    BindlessTexturesNotSupported,
+}
+
+/// A `samplers` uniform block of [`TextureBindings::Bindless`], shared by the draw calls
+/// binding the same textures.
+struct CachedHandleBuffer {
+    buffer: Rc<UniformBuffer<[u64]>>,
+    frames_since_last_use: usize,
+}
 
 pub struct CachedTextureMeta {
-    pub texture: Option<ResidentTexture>,
-    pub uniform_buffer_position: usize,
+    /// Shared with the [`RenderTargets`] for images rendered by a camera
+    pub texture: Option<Rc<GpuTexture>>,
+    /// The [`ImageSampler`] of the image, resolved when it is uploaded
+    pub sampler: SamplerBehavior,
     pub frames_since_last_use: usize,
-    pub taken: bool,
 }
 
+/// The GPU textures of the images drawn in the last frames.
 pub struct TextureBuffer {
-    available_positions: VecDeque<usize>,
+    slots: usize,
+    bindless_textures: bool,
     pub textures: HashMap<Handle<Image>, CachedTextureMeta>,
     queue: Vec<Handle<Image>>,
+    /// Keyed by the handles they hold, so a buffer never outlives the textures it points to
+    handle_buffers: RefCell<HashMap<Vec<u64>, CachedHandleBuffer>>,
 }
 
 impl Default for TextureBuffer {
     fn default() -> Self {
         Self {
-            available_positions: (0..TEXTURE_BUFFER_SIZE).collect(),
+            slots: TextureBufferSettings::default().slots,
+            bindless_textures: true,
             textures: Default::default(),
             queue: Default::default(),
+            handle_buffers: Default::default(),
         }
     }
 }
 
 impl TextureBuffer {
-    /// Inserts a texture that matches the `Handle`.
-    /// If no matching one is found a new [`glium::TextureHandle`] is created.
-    pub fn get_or_insert(&mut self, image_handle: Handle<Image>) -> usize {
-        match self.textures.entry(image_handle) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
-                let meta = entry.get_mut();
-                meta.frames_since_last_use = 0;
-                meta.taken = true;
-                meta.uniform_buffer_position
-            }
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                trace!("inserting in texture buffer {:?}", entry);
-                assert!(
-                    !self.available_positions.is_empty(),
-                    "
-                    No texture slot avaible in the Texture Buffer
-                    You are probably using more than {} textures!
-                    this value can be tweaked on [arara_render::texture::texture_buffer]
-                    ",
-                    TEXTURE_BUFFER_SIZE
+    /// Amount of textures bound by a single draw call, see [`TextureBufferSettings`].
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Empty [`TextureSlots`] for a new draw call.
+    pub fn new_slots(&self) -> TextureSlots {
+        if self.bindless_textures {
+            TextureSlots::new(self.slots)
+        } else {
+            TextureSlots::per_texture(self.slots)
+        }
+    }
+
+    /// Makes sure the texture of the image is on the GPU for this frame.
+    /// If it isn't cached yet it is uploaded before the draw calls.
+    pub fn insert(&mut self, image_handle: &Handle<Image>) {
+        match self.textures.get_mut(image_handle) {
+            Some(meta) => meta.frames_since_last_use = 0,
+            None => {
+                trace!("inserting in texture buffer {:?}", image_handle);
+                self.queue.push(image_handle.clone_weak());
+                self.textures.insert(
+                    image_handle.clone_weak(),
+                    CachedTextureMeta {
+                        texture: None,
+                        sampler: SamplerBehavior::default(),
+                        frames_since_last_use: 0,
+                    },
                 );
-                self.queue.push(entry.key().clone_weak());
-                let uniform_buffer_position = self.available_positions.pop_front().unwrap();
-                entry.insert(CachedTextureMeta {
-                    texture: None,
-                    uniform_buffer_position,
-                    frames_since_last_use: 0,
-                    taken: true,
-                });
-                uniform_buffer_position
             }
         }
     }
 
-    pub fn texture_uniform_buffer(&self) -> TextureUniformBuffer<'_> {
-        let default_handle = GliumTextureHandle::new(
-            self.textures
-                .get(&DEFAULT_IMAGE_HANDLE.typed())
-                .unwrap_or_else(|| self.textures.iter().next().expect("No texture in buffer").1)
-                .texture
-                .as_ref()
-                .unwrap(),
-            &Default::default(),
+    /// Binds the image to `slots` and makes sure its texture is on the GPU.
+    /// Returns `None` when every slot is taken by other images.
+    pub fn bind(
+        &mut self,
+        slots: &mut TextureSlots,
+        image_handle: &Handle<Image>,
+    ) -> Option<usize> {
+        let slot = slots.get_or_insert(image_handle)?;
+        self.insert(image_handle);
+        Some(slot)
+    }
+
+    /// Drops the texture of the image, it is uploaded again the next time it is drawn.
+    pub fn remove(&mut self, image_handle: &Handle<Image>) {
+        self.textures.remove(image_handle);
+        self.queue.retain(|queued| queued != image_handle);
+    }
+
+    /// Binds `texture` instead of the current texture of the image, if it is in the buffer.
+    pub fn replace_texture(&mut self, image_handle: &Handle<Image>, texture: Rc<GpuTexture>) {
+        if let Some(meta) = self.textures.get_mut(image_handle) {
+            if meta.texture.is_some() {
+                meta.texture = Some(texture);
+            }
+        }
+    }
+
+    fn get_texture(&self, image_handle: &Handle<Image>) -> Option<(&GpuTexture, SamplerBehavior)> {
+        let meta = self.textures.get(image_handle)?;
+        Some((meta.texture.as_deref()?, meta.sampler))
+    }
+
+    /// The textures of a draw call binding `slots`.
+    ///
+    /// With bindless textures the unused slots hold the default image, or any texture of `slots`
+    /// if it isn't cached. The uniform buffer of the handles is reused by the draw calls binding
+    /// the same textures.
+    pub fn texture_bindings<'a>(
+        &'a self,
+        facade: &dyn Facade,
+        slots: &TextureSlots,
+    ) -> Result<TextureBindings<'a>, TextureBindingsError> {
+        let fallback = self
+            .get_texture(&DEFAULT_IMAGE_HANDLE.typed())
+            .or_else(|| {
+                slots
+                    .images()
+                    .iter()
+                    .find_map(|image| self.get_texture(image))
+            })
+            .ok_or(TextureBindingsError::NotUploaded)?;
+        let texture = |slot: usize| {
+            slots
+                .images()
+                .get(slot)
+                .and_then(|image| self.get_texture(image))
+                .unwrap_or(fallback)
+        };
+
+        if !self.bindless_textures {
+            let samplers = (0..slots.images().len())
+                .map(|slot| {
+                    let (texture, sampler) = texture(slot);
+                    texture.sampler_uniform(sampler)
+                })
+                .collect::<Option<_>>()
+                .ok_or(TextureBindingsError::NotUploaded)?;
+            return Ok(TextureBindings::Samplers(samplers));
+        }
+        let handles: Vec<u64> = (0..self.slots)
+            .map(|slot| {
+                let (texture, sampler) = texture(slot);
+                texture.handle(&sampler).map(handle_value)
+            })
+            .collect::<Option<_>>()
+            .ok_or(TextureBindingsError::NotUploaded)?;
+
+        let mut handle_buffers = self.handle_buffers.borrow_mut();
+        if let Some(cached) = handle_buffers.get_mut(&handles) {
+            cached.frames_since_last_use = 0;
+            return Ok(TextureBindings::Bindless(cached.buffer.clone()));
+        }
+        let buffer = UniformBuffer::empty_unsized(
+            facade,
+            handles.len() * std::mem::size_of::<GliumTextureHandle>(),
+        )?;
+        buffer.write(handles.as_slice());
+        let buffer = Rc::new(buffer);
+        handle_buffers.insert(
+            handles,
+            CachedHandleBuffer {
+                buffer: buffer.clone(),
+                frames_since_last_use: 0,
+            },
         );
-        let mut tex = [default_handle; TEXTURE_BUFFER_SIZE];
-        self.textures.values().for_each(|v| {
-            tex[v.uniform_buffer_position] =
-                GliumTextureHandle::new(v.texture.as_ref().unwrap(), &Default::default());
-        });
-        TextureUniformBuffer { tex }
+        Ok(TextureBindings::Bindless(buffer))
     }
 
-    /// Updates the cache and only retains recently used textures.
+    /// Updates the cache and only retains recently used textures and handle buffers.
     pub fn update(&mut self) {
         for meta in self.textures.values_mut() {
             meta.frames_since_last_use += 1;
-            meta.taken = false;
-            if meta.frames_since_last_use > 3 {
-                self.available_positions
-                    .push_back(meta.uniform_buffer_position);
-            }
         }
         self.textures
             .retain(|_, meta| meta.frames_since_last_use <= 3);
+
+        let handle_buffers = self.handle_buffers.get_mut();
+        for cached in handle_buffers.values_mut() {
+            cached.frames_since_last_use += 1;
+        }
+        handle_buffers.retain(|_, cached| cached.frames_since_last_use <= 3);
+    }
+}
+
+/// The GPU handle of a resident texture, as stored in the `samplers` uniform block.
+fn handle_value(handle: GliumTextureHandle<'_>) -> u64 {
+    // SAFETY: the handle only holds the `GLuint64` of the texture besides a marker, glium
+    // copies it into the uniform buffers as is
+    unsafe { std::mem::transmute::<GliumTextureHandle<'_>, u64>(handle) }
+}
+
+/// Applies the [`TextureBufferSettings`] to the [`TextureBuffer`] and to the
+/// `TEXTURE_BUFFER_SIZE` and `TEXTURE_SAMPLERS` defines of the shaders.
+pub fn apply_texture_buffer_settings(
+    settings: Res<TextureBufferSettings>,
+    capabilities: Res<RenderCapabilities>,
+    mut texture_cache: NonSendMut<TextureBuffer>,
+    mut pipeline_cache: NonSendMut<RenderPipelineCache>,
+) {
+    if !settings.is_changed() && !capabilities.is_changed() {
+        return;
+    }
+    texture_cache.slots = settings.slots.max(1);
+    texture_cache.bindless_textures = capabilities.bindless_textures;
+    pipeline_cache.set_global_define(TEXTURE_BUFFER_SIZE_DEFINE, texture_cache.slots);
+    pipeline_cache.set_global_define(
+        TEXTURE_SAMPLERS_DEFINE,
+        sampler_declarations(texture_cache.slots),
+    );
+}
+
+/// `tex_1` is always declared since the shaders reference the normal maps, with a single slot
+/// they are never bound.
+fn sampler_declarations(slots: usize) -> String {
+    (0..slots.max(2))
+        .map(|slot| format!("uniform sampler2D tex_{};", slot))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drops the textures of the modified images so they are uploaded again, for instance when
+/// they are hot reloaded.
+pub fn remove_modified_textures(
+    mut events: EventReader<AssetEvent<Image>>,
+    mut texture_cache: NonSendMut<TextureBuffer>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                trace!("removing from texture buffer {:?}", handle);
+                texture_cache.remove(handle);
+            }
+            AssetEvent::Created { .. } => {}
+        }
+    }
+}
+
+/// Drops the textures of the images sampled with the [`DefaultImageSampler`] when it changes,
+/// so they are uploaded again.
+pub fn apply_default_image_sampler(
+    default_sampler: Res<DefaultImageSampler>,
+    images: Res<Assets<Image>>,
+    mut texture_cache: NonSendMut<TextureBuffer>,
+) {
+    if !default_sampler.is_changed() || default_sampler.is_added() {
+        return;
+    }
+    let outdated: Vec<_> = texture_cache
+        .textures
+        .keys()
+        .filter(|handle| {
+            images
+                .get(*handle)
+                .is_some_and(|image| image.sampler == ImageSampler::Default)
+        })
+        .cloned()
+        .collect();
+    for handle in outdated {
+        texture_cache.remove(&handle);
     }
 }
 
@@ -116,21 +490,262 @@ pub fn process_queue_to_gpu(
     mut texture_cache: NonSendMut<TextureBuffer>,
     window: NonSend<Window>,
     images: Res<Assets<Image>>,
+    default_sampler: Res<DefaultImageSampler>,
+    render_targets: NonSend<RenderTargets>,
 ) {
-    let display = window.display();
+    let facade = window.facade();
+    let bindless_textures = texture_cache.bindless_textures;
     let handles: Vec<_> = texture_cache.queue.drain(..).collect();
+    let mut failed = Vec::new();
     for image_handle in handles {
         let image = images.get(&image_handle).unwrap();
-        let raw_image = RawImage2d::from_raw_rgba_reversed(&image.data, image.dimensions);
-        let texture = SrgbTexture2d::new(display, raw_image).unwrap();
-        let texture = texture.resident().unwrap();
+        let descriptor = image.sampler.resolve(&default_sampler);
+        let (texture, sampler) = match render_targets.get(&image_handle) {
+            Some(target) => (target.color.clone(), descriptor.behavior(false)),
+            None => match upload_image(facade, image, &descriptor, bindless_textures) {
+                Ok((texture, has_mipmaps)) => (Rc::new(texture), descriptor.behavior(has_mipmaps)),
+                Err(err) => {
+                    error!("Failed to upload the image {:?}: {}", image_handle, err);
+                    failed.push(image_handle);
+                    continue;
+                }
+            },
+        };
 
         let meta = texture_cache.textures.get_mut(&image_handle).unwrap();
         meta.texture = Some(texture);
+        meta.sampler = sampler;
+    }
+
+    // the images that can't be uploaded are drawn with the default image until they change
+    let default = texture_cache
+        .textures
+        .get(&DEFAULT_IMAGE_HANDLE.typed())
+        .and_then(|meta| Some((meta.texture.clone()?, meta.sampler)));
+    for image_handle in failed {
+        let meta = texture_cache.textures.get_mut(&image_handle).unwrap();
+        if let Some((texture, sampler)) = &default {
+            meta.texture = Some(texture.clone());
+            meta.sampler = *sampler;
+        }
     }
 }
 
+/// Uploads the levels of `image` in a texture of its [`PixelFormat`], returns the texture and
+/// whether it has mipmaps.
+///
+/// The rows are flipped since OpenGL expects the bottom row first.
+fn upload_image(
+    facade: &dyn Facade,
+    image: &Image,
+    descriptor: &SamplerDescriptor,
+    bindless_textures: bool,
+) -> Result<(GpuTexture, bool), UploadImageError> {
+    let levels = if descriptor.mipmaps {
+        image.mip_levels
+    } else {
+        1
+    };
+    let generate_mipmaps = descriptor.mipmaps && levels == 1 && !image.format.is_compressed();
+    let mipmaps = if levels > 1 {
+        MipmapsOption::EmptyMipmapsMax(levels - 1)
+    } else if generate_mipmaps {
+        MipmapsOption::AutoGeneratedMipmaps
+    } else {
+        MipmapsOption::NoMipmap
+    };
+    let compressed_mipmaps = if levels > 1 {
+        CompressedMipmapsOption::EmptyMipmapsMax(levels - 1)
+    } else {
+        CompressedMipmapsOption::NoMipmap
+    };
+    let rect = |level: u32| {
+        let (width, height) = image.level_dimensions(level);
+        Rect {
+            left: 0,
+            bottom: 0,
+            width,
+            height,
+        }
+    };
+    let rgba8_level = |level: u32| {
+        let (data, dimensions) = image.level(level).unwrap();
+        RawImage2d::from_raw_rgba_reversed(data, dimensions)
+    };
+    let float_level = |level: u32| {
+        let (data, dimensions) = image.level(level).unwrap();
+        let texels: Vec<f32> = data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        RawImage2d::from_raw_rgba_reversed(&texels, dimensions)
+    };
+    let compressed_level = |level: u32| {
+        let (data, dimensions) = image.level(level).unwrap();
+        (flip_blocks(data, image.format, dimensions), dimensions)
+    };
+
+    let texture = match image.format {
+        PixelFormat::Rgba8 if descriptor.srgb => {
+            let texture = SrgbTexture2d::with_mipmaps(facade, rgba8_level(0), mipmaps)?;
+            for level in 1..levels {
+                texture
+                    .mipmap(level)
+                    .unwrap()
+                    .write(rect(level), rgba8_level(level));
+            }
+            if bindless_textures {
+                GpuTexture::Resident(
+                    texture
+                        .resident()
+                        .map_err(|_| UploadImageError::BindlessTexturesNotSupported)?,
+                )
+            } else {
+                GpuTexture::Srgb(texture)
+            }
+        }
+        PixelFormat::Rgba8 | PixelFormat::Rgba32Float => {
+            let texture = if image.format == PixelFormat::Rgba8 {
+                let texture = Texture2d::with_mipmaps(facade, rgba8_level(0), mipmaps)?;
+                for level in 1..levels {
+                    texture
+                        .mipmap(level)
+                        .unwrap()
+                        .write(rect(level), rgba8_level(level));
+                }
+                texture
+            } else {
+                let texture = Texture2d::with_format(
+                    facade,
+                    float_level(0),
+                    UncompressedFloatFormat::F32F32F32F32,
+                    mipmaps,
+                )?;
+                for level in 1..levels {
+                    texture
+                        .mipmap(level)
+                        .unwrap()
+                        .write(rect(level), float_level(level));
+                }
+                texture
+            };
+            if bindless_textures {
+                GpuTexture::Resident(
+                    texture
+                        .resident()
+                        .map_err(|_| UploadImageError::BindlessTexturesNotSupported)?,
+                )
+            } else {
+                GpuTexture::Linear(texture)
+            }
+        }
+        PixelFormat::Bc1 | PixelFormat::Bc2 | PixelFormat::Bc3 if descriptor.srgb => {
+            let format = match image.format {
+                PixelFormat::Bc1 => CompressedSrgbFormat::S3tcDxt1Alpha,
+                PixelFormat::Bc2 => CompressedSrgbFormat::S3tcDxt3Alpha,
+                _ => CompressedSrgbFormat::S3tcDxt5Alpha,
+            };
+            let (data, (width, height)) = compressed_level(0);
+            let texture = CompressedSrgbTexture2d::with_compressed_data(
+                facade,
+                &data,
+                width,
+                height,
+                format,
+                compressed_mipmaps,
+            )?;
+            for level in 1..levels {
+                let (data, (width, height)) = compressed_level(level);
+                texture
+                    .mipmap(level)
+                    .unwrap()
+                    .write_compressed_data(rect(level), &data, width, height, format)
+                    .map_err(|_| UploadImageError::CompressedLevel(level))?;
+            }
+            if bindless_textures {
+                GpuTexture::Resident(
+                    texture
+                        .resident()
+                        .map_err(|_| UploadImageError::BindlessTexturesNotSupported)?,
+                )
+            } else {
+                GpuTexture::CompressedSrgb(texture)
+            }
+        }
+        PixelFormat::Bc1 | PixelFormat::Bc2 | PixelFormat::Bc3 => {
+            let format = match image.format {
+                PixelFormat::Bc1 => CompressedFormat::S3tcDxt1Alpha,
+                PixelFormat::Bc2 => CompressedFormat::S3tcDxt3Alpha,
+                _ => CompressedFormat::S3tcDxt5Alpha,
+            };
+            let (data, (width, height)) = compressed_level(0);
+            let texture = CompressedTexture2d::with_compressed_data(
+                facade,
+                &data,
+                width,
+                height,
+                format,
+                compressed_mipmaps,
+            )?;
+            for level in 1..levels {
+                let (data, (width, height)) = compressed_level(level);
+                texture
+                    .mipmap(level)
+                    .unwrap()
+                    .write_compressed_data(rect(level), &data, width, height, format)
+                    .map_err(|_| UploadImageError::CompressedLevel(level))?;
+            }
+            if bindless_textures {
+                GpuTexture::Resident(
+                    texture
+                        .resident()
+                        .map_err(|_| UploadImageError::BindlessTexturesNotSupported)?,
+                )
+            } else {
+                GpuTexture::Compressed(texture)
+            }
+        }
+    };
+    Ok((texture, levels > 1 || generate_mipmaps))
+}
+
 /// Updates the [`TextureCache`] to only retains recently used textures.
 pub fn update_texture_cache_system(mut texture_cache: NonSendMut<TextureBuffer>) {
     texture_cache.update();
 }
+
+#[cfg(test)]
+mod tests {
+    use arara_asset::HandleId;
+    use bevy_reflect::TypeUuid;
+
+    use super::*;
+
+    fn image(id: u64) -> Handle<Image> {
+        Handle::weak(HandleId::Id(Image::TYPE_UUID, id))
+    }
+
+    #[test]
+    fn per_texture_slots_keep_their_order() {
+        let (a, b, c) = (image(0), image(1), image(2));
+        let mut slots = TextureSlots::per_texture(2);
+        assert!(slots.fits([&a]));
+        assert_eq!(slots.get_or_insert(&a), Some(0));
+        assert!(slots.fits([&a, &b]));
+        assert_eq!(slots.get_or_insert(&b), Some(1));
+        assert!(!slots.fits([&b]));
+        assert!(!slots.fits([&a, &c]));
+    }
+
+    #[test]
+    fn texture_slots_fill_up() {
+        let (a, b, c) = (image(0), image(1), image(2));
+        let mut slots = TextureSlots::new(2);
+        assert_eq!(slots.get_or_insert(&a), Some(0));
+        assert_eq!(slots.get_or_insert(&b), Some(1));
+        assert_eq!(slots.get_or_insert(&a), Some(0));
+        assert!(slots.fits([&a, &b]));
+        assert!(!slots.fits([&a, &c]));
+        assert_eq!(slots.get_or_insert(&c), None);
+    }
+}
@@ -1,22 +1,76 @@
-use arara_camera::Camera;
-use arara_ecs::system::{Commands, Query};
+use arara_camera::{Camera, Viewport};
+use arara_ecs::{
+    entity::Entity,
+    prelude::Component,
+    system::{Commands, Query},
+};
 use arara_transform::GlobalTransform;
-use arara_utils::tracing::error;
 use glam::{Mat4, Vec3};
 
-#[derive(Debug, Default)]
+use crate::{Frustum, RenderLayers, RenderTarget, VisibleEntities};
+
+/// The render data of an active [`Camera`], inserted on the camera entity every frame along
+/// with its [`VisibleEntities`].
+#[derive(Component, Debug, Clone)]
 pub struct ExtractedView {
     pub pv_matrix: Mat4,
+    /// Transforms from world space to the view space of the camera, looking down `-Z`
+    pub view_matrix: Mat4,
     pub position: Vec3,
+    pub frustum: Frustum,
+    pub priority: isize,
+    pub viewport: Option<Viewport>,
+    pub target: RenderTarget,
+    pub render_layers: RenderLayers,
 }
 
-pub(crate) fn extract_cameras(mut commands: Commands, query: Query<(&Camera, &GlobalTransform)>) {
-    let (camera, transform) = query.get_single().unwrap_or_else(|_| {
-        error!("Missing Camera");
-        panic!("Missing Camera");
-    });
-    commands.insert_resource(ExtractedView {
-        pv_matrix: camera.projection * transform.view_matrix(),
-        position: transform.translation,
-    });
+/// A view drawing into its own textures instead of a [`RenderTarget`], like the shadow map of a
+/// light. Its [`RenderPhases`](crate::RenderPhases) run before the ones of the cameras.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct OffscreenView;
+
+pub(crate) fn extract_cameras(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &Camera,
+        &GlobalTransform,
+        Option<&RenderTarget>,
+        Option<&RenderLayers>,
+        Option<&mut ExtractedView>,
+    )>,
+) {
+    for (entity, camera, transform, target, render_layers, extracted_view) in query.iter_mut() {
+        if !camera.is_active {
+            if extracted_view.is_some() {
+                commands
+                    .entity(entity)
+                    .remove::<ExtractedView>()
+                    .remove::<VisibleEntities>();
+            }
+            continue;
+        }
+        let view_matrix = transform.view_matrix();
+        let pv_matrix = camera.projection * view_matrix;
+        let view = ExtractedView {
+            pv_matrix,
+            view_matrix,
+            position: transform.translation,
+            frustum: Frustum::from_view_projection(&pv_matrix),
+            priority: camera.priority,
+            viewport: camera.viewport,
+            target: target.cloned().unwrap_or_default(),
+            render_layers: render_layers.copied().unwrap_or_default(),
+        };
+        // updated in place, only the new views are inserted
+        match extracted_view {
+            Some(mut extracted_view) => *extracted_view = view,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(view)
+                    .insert(VisibleEntities::default());
+            }
+        }
+    }
 }
@@ -0,0 +1,89 @@
+use glam::{Mat4, Vec3, Vec4, Vec4Swizzles};
+
+use crate::Aabb;
+
+/// The six planes bounding what a camera can see, in world space.
+/// Points inside satisfy `plane.xyz · point + plane.w >= 0` for every plane.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Frustum {
+    pub planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from a projection times view matrix.
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let row0 = view_projection.row(0);
+        let row1 = view_projection.row(1);
+        let row2 = view_projection.row(2);
+        let row3 = view_projection.row(3);
+        Self {
+            planes: [
+                row3 + row0,
+                row3 - row0,
+                row3 + row1,
+                row3 - row1,
+                row3 + row2,
+                row3 - row2,
+            ],
+        }
+    }
+
+    /// Whether the `aabb`, transformed by `model`, is at least partially inside the frustum.
+    pub fn intersects_obb(&self, aabb: &Aabb, model: &Mat4) -> bool {
+        let center = model.transform_point3(aabb.center);
+        let axes = [
+            model.x_axis.xyz() * aabb.half_extents.x,
+            model.y_axis.xyz() * aabb.half_extents.y,
+            model.z_axis.xyz() * aabb.half_extents.z,
+        ];
+        self.planes.iter().all(|plane| {
+            let normal: Vec3 = plane.xyz();
+            let radius: f32 = axes.iter().map(|axis| normal.dot(*axis).abs()).sum();
+            normal.dot(center) + plane.w >= -radius
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Mat4, Vec3};
+
+    use super::Frustum;
+    use crate::Aabb;
+
+    fn frustum() -> Frustum {
+        let projection = Mat4::perspective_rh_gl(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        let view = Mat4::look_at_rh(Vec3::ZERO, -Vec3::Z, Vec3::Y);
+        Frustum::from_view_projection(&(projection * view))
+    }
+
+    #[test]
+    fn culls_boxes_outside_the_frustum() {
+        let frustum = frustum();
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+        let in_front = Mat4::from_translation(Vec3::new(0.0, 0.0, -10.0));
+        let behind = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let too_far = Mat4::from_translation(Vec3::new(0.0, 0.0, -200.0));
+        let to_the_side = Mat4::from_translation(Vec3::new(20.0, 0.0, -10.0));
+
+        assert!(frustum.intersects_obb(&aabb, &in_front));
+        assert!(!frustum.intersects_obb(&aabb, &behind));
+        assert!(!frustum.intersects_obb(&aabb, &too_far));
+        assert!(!frustum.intersects_obb(&aabb, &to_the_side));
+    }
+
+    #[test]
+    fn keeps_boxes_crossing_a_plane() {
+        let frustum = frustum();
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+        let crossing = Mat4::from_scale_rotation_translation(
+            Vec3::splat(15.0),
+            Default::default(),
+            Vec3::new(20.0, 0.0, -10.0),
+        );
+
+        assert!(frustum.intersects_obb(&aabb, &crossing));
+    }
+}
@@ -1,5 +1,24 @@
 mod extracted_view;
+mod frustum;
+mod render_layers;
+mod render_target;
 mod visibility;
 
 pub use extracted_view::*;
-pub use visibility::Visibility;
+pub use frustum::*;
+pub use render_layers::*;
+pub use render_target::{GpuRenderTarget, RenderTarget, RenderTargetError, RenderTargets};
+pub use visibility::{NoFrustumCulling, Visibility, VisibleEntities};
+
+pub(crate) use render_target::{prepare_render_targets, update_camera_target_sizes};
+pub(crate) use visibility::check_visibility;
+
+use arara_ecs::schedule::SystemLabel;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum ViewSystem {
+    ExtractCameras,
+    /// Writes [`Visibility::visible`] and the [`VisibleEntities`] of the views, systems extracting
+    /// visible entities should run after it.
+    CheckVisibility,
+}
@@ -0,0 +1,57 @@
+use arara_ecs::prelude::Component;
+
+/// The layers an entity belongs to, cameras only render the entities sharing a layer with them.
+///
+/// Entities and cameras without this component are on layer `0`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderLayers(u32);
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        Self::layer(0)
+    }
+}
+
+impl RenderLayers {
+    pub const TOTAL_LAYERS: u8 = 32;
+
+    /// Only the given layer.
+    pub fn layer(layer: u8) -> Self {
+        Self::none().with(layer)
+    }
+
+    pub fn all() -> Self {
+        Self(u32::MAX)
+    }
+
+    pub fn none() -> Self {
+        Self(0)
+    }
+
+    /// Adds the given layer.
+    pub fn with(mut self, layer: u8) -> Self {
+        assert!(
+            layer < Self::TOTAL_LAYERS,
+            "layer {} is out of range",
+            layer
+        );
+        self.0 |= 1 << layer;
+        self
+    }
+
+    /// Removes the given layer.
+    pub fn without(mut self, layer: u8) -> Self {
+        assert!(
+            layer < Self::TOTAL_LAYERS,
+            "layer {} is out of range",
+            layer
+        );
+        self.0 &= !(1 << layer);
+        self
+    }
+
+    /// Whether both share at least one layer.
+    pub fn intersects(&self, other: &RenderLayers) -> bool {
+        self.0 & other.0 != 0
+    }
+}
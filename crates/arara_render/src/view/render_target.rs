@@ -0,0 +1,191 @@
+use std::rc::Rc;
+
+use arara_asset::{Assets, Handle};
+use arara_camera::Camera;
+use arara_ecs::{
+    prelude::Component,
+    system::{NonSend, NonSendMut, Query, Res},
+};
+use arara_utils::{
+    tracing::{error, trace},
+    HashMap, HashSet,
+};
+use arara_window::{Window, WindowBackend};
+use glam::UVec2;
+use glium::{
+    backend::Facade,
+    framebuffer::{ColorAttachment, SimpleFrameBuffer, ValidationError},
+    texture::{DepthTexture2d, MipmapsOption, UncompressedFloatFormat},
+    Texture2d,
+};
+use thiserror::Error;
+
+use crate::{ExtractedView, GpuTexture, Image, RenderCapabilities, TextureBuffer};
+
+/// Where a camera is rendered to, cameras without this component render to the window.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum RenderTarget {
+    #[default]
+    Window,
+    /// An offscreen texture with the size of the [`Image`], other entities can use the
+    /// handle as any loaded image to show what the camera sees.
+    ///
+    /// Images targeted by a camera should not be visible to that same camera.
+    Image(Handle<Image>),
+}
+
+impl RenderTarget {
+    /// Size in pixels of the target, `None` if the image is not loaded.
+    pub fn physical_size(&self, window: &Window, images: &Assets<Image>) -> Option<UVec2> {
+        match self {
+            RenderTarget::Window => Some(UVec2::from(match window.backend() {
+                WindowBackend::Display(display) => display.get_framebuffer_dimensions(),
+                WindowBackend::Headless(headless) => headless.color().dimensions(),
+            })),
+            RenderTarget::Image(handle) => images
+                .get(handle)
+                .map(|image| UVec2::from(image.dimensions).max(UVec2::ONE)),
+        }
+    }
+}
+
+/// The color and depth textures of a [`RenderTarget::Image`].
+pub struct GpuRenderTarget {
+    pub color: Rc<GpuTexture>,
+    pub depth: DepthTexture2d,
+}
+
+impl GpuRenderTarget {
+    /// The color texture is made resident if `bindless_textures` is set.
+    pub fn new(
+        facade: &dyn Facade,
+        size: UVec2,
+        bindless_textures: bool,
+    ) -> Result<Self, RenderTargetError> {
+        let color = Texture2d::empty_with_format(
+            facade,
+            UncompressedFloatFormat::U8U8U8U8,
+            MipmapsOption::NoMipmap,
+            size.x,
+            size.y,
+        )?;
+        let color = if bindless_textures {
+            GpuTexture::Resident(
+                color
+                    .resident()
+                    .map_err(|_| RenderTargetError::BindlessTexturesNotSupported)?,
+            )
+        } else {
+            GpuTexture::Linear(color)
+        };
+        Ok(Self {
+            color: Rc::new(color),
+            depth: DepthTexture2d::empty(facade, size.x, size.y)?,
+        })
+    }
+
+    pub fn size(&self) -> UVec2 {
+        let color = self.color.texture();
+        UVec2::new(color.get_width(), color.get_height().unwrap_or(1))
+    }
+
+    /// A framebuffer drawing into the textures.
+    pub fn framebuffer<'a>(
+        &'a self,
+        facade: &dyn Facade,
+    ) -> Result<SimpleFrameBuffer<'a>, RenderTargetError> {
+        let color = self
+            .color
+            .texture()
+            .main_level()
+            .first_layer()
+            .into_image(None)
+            .unwrap();
+        Ok(SimpleFrameBuffer::with_depth_buffer(
+            facade,
+            ColorAttachment::Texture(color),
+            &self.depth,
+        )?)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RenderTargetError {
+    #[error(transparent)]
+    TextureCreation(#[from] glium::texture::TextureCreationError),
+    #[error("bindless textures are not supported")]
+    BindlessTexturesNotSupported,
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+}
+
+/// The [`GpuRenderTarget`] of every [`RenderTarget::Image`] used by an active camera.
+#[derive(Default)]
+pub struct RenderTargets {
+    targets: HashMap<Handle<Image>, GpuRenderTarget>,
+}
+
+impl RenderTargets {
+    pub fn get(&self, handle: &Handle<Image>) -> Option<&GpuRenderTarget> {
+        self.targets.get(handle)
+    }
+}
+
+/// Allocates the textures of the image render targets, reallocating them when the image is
+/// resized, and makes the [`TextureBuffer`] bind them instead of the image data.
+pub(crate) fn prepare_render_targets(
+    window: NonSend<Window>,
+    images: Res<Assets<Image>>,
+    capabilities: Res<RenderCapabilities>,
+    views: Query<&ExtractedView>,
+    mut render_targets: NonSendMut<RenderTargets>,
+    mut texture_buffer: NonSendMut<TextureBuffer>,
+) {
+    let mut used = HashSet::default();
+    for view in views.iter() {
+        let handle = match &view.target {
+            RenderTarget::Image(handle) => handle,
+            RenderTarget::Window => continue,
+        };
+        let image = match images.get(handle) {
+            Some(image) => image,
+            None => continue,
+        };
+        used.insert(handle.clone_weak());
+
+        let size = UVec2::from(image.dimensions).max(UVec2::ONE);
+        if matches!(render_targets.get(handle), Some(target) if target.size() == size) {
+            continue;
+        }
+        trace!("allocating render target {:?} of size {}", handle, size);
+        match GpuRenderTarget::new(window.facade(), size, capabilities.bindless_textures) {
+            Ok(target) => {
+                texture_buffer.replace_texture(handle, target.color.clone());
+                render_targets.targets.insert(handle.clone_weak(), target);
+            }
+            Err(err) => error!("Failed to create the render target {:?}: {}", handle, err),
+        }
+    }
+    render_targets
+        .targets
+        .retain(|handle, _| used.contains(handle));
+}
+
+/// Keeps [`Camera::target_size`] in sync with the size of the [`RenderTarget::Image`].
+pub(crate) fn update_camera_target_sizes(
+    images: Res<Assets<Image>>,
+    mut cameras: Query<(&mut Camera, Option<&RenderTarget>)>,
+) {
+    for (mut camera, target) in cameras.iter_mut() {
+        let target_size = match target {
+            Some(RenderTarget::Image(handle)) => images
+                .get(handle)
+                .map(|image| UVec2::from(image.dimensions).max(UVec2::ONE)),
+            _ => None,
+        };
+        // only write when needed, the camera would be changed again otherwise
+        if camera.target_size != target_size {
+            camera.target_size = target_size;
+        }
+    }
+}
@@ -1,8 +1,16 @@
-use arara_ecs::prelude::Component;
+use arara_asset::Handle;
+use arara_ecs::prelude::*;
+use arara_transform::GlobalTransform;
 
+use crate::{Aabb, ExtractedView, Mesh, MeshAabbs, RenderLayers};
+
+/// Whether an entity is rendered.
 #[derive(Component, Clone, Copy)]
 pub struct Visibility {
+    /// Set by the user to hide the entity.
     pub active: bool,
+    /// Written every frame by the frustum culling, `false` when no camera can see the entity.
+    /// The entities seen by each camera are in its [`VisibleEntities`].
     pub visible: bool,
 }
 
@@ -27,3 +35,61 @@ impl Visibility {
         }
     }
 }
+
+/// The entities seen by a view, written every frame by the frustum culling.
+///
+/// Entities without bounds are seen by every view sharing a [`RenderLayers`] with them.
+#[derive(Component, Debug, Default, Clone)]
+pub struct VisibleEntities {
+    pub entities: Vec<Entity>,
+}
+
+/// Entities with this component are never frustum culled.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct NoFrustumCulling;
+
+/// Tests the bounds of every entity with an [`Aabb`] or a [`Handle<Mesh>`] against the frustum of
+/// every [`ExtractedView`] sharing a [`RenderLayers`] with it, and lists them in the
+/// [`VisibleEntities`] of the views seeing them.
+pub(crate) fn check_visibility(
+    mut views: Query<(&ExtractedView, &mut VisibleEntities)>,
+    mesh_aabbs: Res<MeshAabbs>,
+    mut query: Query<(
+        Entity,
+        &mut Visibility,
+        &GlobalTransform,
+        Option<&Aabb>,
+        Option<&Handle<Mesh>>,
+        Option<&RenderLayers>,
+        Option<&NoFrustumCulling>,
+    )>,
+) {
+    for (_, mut visible_entities) in views.iter_mut() {
+        visible_entities.entities.clear();
+    }
+    for (entity, mut visibility, global_transform, aabb, mesh, render_layers, no_frustum_culling) in
+        query.iter_mut()
+    {
+        let aabb = aabb.or_else(|| mesh.and_then(|mesh| mesh_aabbs.get(mesh)));
+        let render_layers = render_layers.copied().unwrap_or_default();
+        let model = global_transform.compute_matrix();
+        let mut visible = no_frustum_culling.is_some();
+        for (view, mut visible_entities) in views.iter_mut() {
+            let seen = view.render_layers.intersects(&render_layers)
+                && match aabb {
+                    Some(aabb) if no_frustum_culling.is_none() => {
+                        view.frustum.intersects_obb(aabb, &model)
+                    }
+                    _ => true,
+                };
+            if seen {
+                visible_entities.entities.push(entity);
+                visible = true;
+            }
+        }
+        // entities without bounds are never culled
+        if aabb.is_some() && visibility.visible != visible {
+            visibility.visible = visible;
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use arara_asset::Handle;
 use arara_ecs::bundle::Bundle;
-use arara_render::{Color, Image, Visibility, DEFAULT_IMAGE_HANDLE};
+use arara_render::{Aabb, Color, Image, Visibility, DEFAULT_IMAGE_HANDLE};
 use arara_transform::{GlobalTransform, Transform};
+use glam::vec3;
 
 use crate::{
     sprite::Sprite,
@@ -21,6 +22,8 @@ pub struct SpriteBundle {
     pub color: Color,
     /// Indication of whether an entity is visible
     pub visibility: Visibility,
+    /// Bounds of the sprite quad, used for frustum culling
+    pub aabb: Aabb,
 }
 
 impl Default for SpriteBundle {
@@ -32,13 +35,14 @@ impl Default for SpriteBundle {
             color: Default::default(),
             image: DEFAULT_IMAGE_HANDLE.typed(),
             visibility: Default::default(),
+            aabb: quad_aabb(),
         }
     }
 }
 
 /// A Bundle of components for drawing a single sprite from a sprite sheet (also referred
 /// to as a `TextureAtlas`)
-#[derive(Bundle, Clone, Default)]
+#[derive(Bundle, Clone)]
 pub struct SpriteSheetBundle {
     /// The specific sprite from the texture atlas to be drawn
     pub sprite: TextureAtlasSprite,
@@ -51,4 +55,25 @@ pub struct SpriteSheetBundle {
     pub color: Color,
     /// Indication of whether an entity is visible
     pub visibility: Visibility,
+    /// Bounds of the sprite quad, used for frustum culling
+    pub aabb: Aabb,
+}
+
+impl Default for SpriteSheetBundle {
+    fn default() -> Self {
+        Self {
+            sprite: Default::default(),
+            texture_atlas: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            color: Default::default(),
+            visibility: Default::default(),
+            aabb: quad_aabb(),
+        }
+    }
+}
+
+/// The bounds of [`QUAD_MESH_HANDLE`](crate::QUAD_MESH_HANDLE), sprites are scaled by their transform.
+fn quad_aabb() -> Aabb {
+    Aabb::from_min_max(vec3(-0.5, -0.5, 0.0), vec3(0.5, 0.5, 0.0))
 }
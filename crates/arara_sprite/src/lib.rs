@@ -5,9 +5,10 @@ mod texture_atlas;
 
 use arara_app::{App, Plugin};
 use arara_asset::{AddAsset, Assets, HandleUntyped};
+use arara_ecs::schedule::ParallelSystemDescriptorCoercion;
 use arara_render::{
-    clear_phase_system, DrawFunctions, Mesh, RenderPhase, RenderPhases, RenderStage,
-    SpecializedPipelines, Square,
+    clear_phase_system, despawn_render_entities, extract_camera_phases, sort_phase_system,
+    DrawFunctions, Mesh, RenderPhases, RenderStage, SpecializedPipelines, Square, ViewSystem,
 };
 use bevy_reflect::TypeUuid;
 
@@ -17,7 +18,7 @@ use crate::{
         extract_phase::{extract_sprite_entities, ExtractedSprites},
         phase_items::Transparent2D,
         pipelines::SpritePipeline,
-        prepare_phase::prepare_sprite_phase,
+        prepare_phase::{prepare_sprite_phase, SpriteBatch},
         queue_phase::queue_sprite_phase,
     },
     texture_atlas::TextureAtlas,
@@ -42,12 +43,17 @@ impl Plugin for SpritePlugin {
             .init_resource::<SpritePipeline>()
             .init_resource::<SpecializedPipelines<SpritePipeline>>()
             .init_resource::<DrawFunctions<Transparent2D>>()
-            .init_resource::<RenderPhase<Transparent2D>>()
             .init_resource::<ExtractedSprites>()
-            .add_system_to_stage(RenderStage::Extract, extract_sprite_entities)
+            .add_system_to_stage(RenderStage::Extract, extract_camera_phases::<Transparent2D>)
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_sprite_entities.after(ViewSystem::CheckVisibility),
+            )
             .add_system_to_stage(RenderStage::Prepare, prepare_sprite_phase)
             .add_system_to_stage(RenderStage::Queue, queue_sprite_phase)
-            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Transparent2D>);
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent2D>)
+            .add_system_to_stage(RenderStage::Cleanup, clear_phase_system::<Transparent2D>)
+            .add_system_to_stage(RenderStage::Cleanup, despawn_render_entities::<SpriteBatch>);
 
         let draw_sprite = DrawSprite::new(&mut app.world);
         app.world
@@ -1,19 +1,21 @@
 use arara_ecs::{
+    entity::Entity,
     system::{
-        lifetimeless::{Read, SQuery, SRes},
+        lifetimeless::{Read, SQuery},
         NonSend, SystemState,
     },
     world::World,
 };
 use arara_render::{
-    CachedPipelinePhaseItem, Draw, EntityPhaseItem, ExtractedView, RenderPipelineCache,
-    TextureBuffer, TrackedFrame,
+    implement_uniform_block, CachedPipelinePhaseItem, ChainedUniforms, Draw, EntityPhaseItem,
+    ExtractedView, RenderPipelineCache, TextureBindingsError, TextureBuffer, TrackedFrame,
 };
+use arara_utils::tracing::error;
 use arara_window::Window;
-use glium::implement_uniform_block;
 
 use crate::render::{phase_items::Transparent2D, prepare_phase::SpriteBatch};
 
+#[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
 struct CameraUniformBuffer {
     u_pv_matrix: [[f32; 4]; 4],
@@ -27,19 +29,12 @@ impl CameraUniformBuffer {
 
 implement_uniform_block!(CameraUniformBuffer, u_pv_matrix);
 
-#[derive(Copy, Clone)]
-struct TextureUniformBuffer<'a> {
-    tex: [glium::texture::TextureHandle<'a>; 5],
-}
-
-implement_uniform_block!(TextureUniformBuffer<'a>, tex);
-
 pub struct DrawSprite {
     params: SystemState<(
         NonSend<'static, Window>,
         NonSend<'static, TextureBuffer>,
         NonSend<'static, RenderPipelineCache>,
-        SRes<ExtractedView>,
+        SQuery<Read<ExtractedView>>,
         SQuery<Read<SpriteBatch>>,
     )>,
 }
@@ -53,35 +48,58 @@ impl DrawSprite {
 }
 
 impl Draw<Transparent2D> for DrawSprite {
-    fn draw<'w>(&mut self, world: &'w World, frame: &mut TrackedFrame, item: &Transparent2D) {
-        let (window, texture_buffer, pipeline_cache, view, query) = self.params.get(world);
+    fn draw(
+        &mut self,
+        world: &World,
+        frame: &mut TrackedFrame,
+        view: Entity,
+        item: &Transparent2D,
+    ) {
+        let (window, texture_buffer, pipeline_cache, views, query) = self.params.get(world);
+
+        let view = match views.get(view) {
+            Ok(view) => view,
+            Err(_) => return,
+        };
 
-        let display = window.display();
+        let facade = window.facade();
 
         let pv_matrix: [[f32; 4]; 4] = view.pv_matrix.to_cols_array_2d();
         let camera_uniform_buffer =
-            glium::uniforms::UniformBuffer::new(display, CameraUniformBuffer::new(pv_matrix))
-                .unwrap();
-
-        let texture_uniform_buffer =
-            glium::uniforms::UniformBuffer::new(display, texture_buffer.texture_uniform_buffer())
+            glium::uniforms::UniformBuffer::new(facade, CameraUniformBuffer::new(pv_matrix))
                 .unwrap();
 
-        let uniforms = glium::uniform! {
-            camera: &camera_uniform_buffer,
-            samplers: &texture_uniform_buffer,
-        };
-
         let pipeline = match pipeline_cache.get(item.cached_pipeline()) {
             Some(pipeline) => pipeline,
             None => return,
         };
 
-        let SpriteBatch { vertices, indices } = query.get(item.entity()).unwrap();
+        let SpriteBatch {
+            vertices,
+            indices,
+            texture_slots,
+            ..
+        } = query.get(item.entity()).unwrap();
+
+        let texture_bindings = match texture_buffer.texture_bindings(facade, texture_slots) {
+            Ok(bindings) => bindings,
+            Err(TextureBindingsError::NotUploaded) => return,
+            Err(err) => {
+                error!("Failed to bind the textures: {}", err);
+                return;
+            }
+        };
+
+        let uniforms = ChainedUniforms(
+            glium::uniform! {
+                camera: &camera_uniform_buffer,
+            },
+            texture_bindings,
+        );
 
-        let vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
+        let vertex_buffer = glium::VertexBuffer::new(facade, vertices).unwrap();
         let index_buffer: glium::IndexBuffer<u32> =
-            glium::IndexBuffer::new(display, glium::index::PrimitiveType::TrianglesList, indices)
+            glium::IndexBuffer::new(facade, glium::index::PrimitiveType::TrianglesList, indices)
                 .unwrap();
 
         frame
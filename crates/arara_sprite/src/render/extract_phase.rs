@@ -3,7 +3,7 @@ use arara_ecs::{
     query::With,
     system::{Query, Res, ResMut},
 };
-use arara_render::{Color, Image, Visibility};
+use arara_render::{Color, Image, RenderLayers, Visibility};
 use arara_transform::GlobalTransform;
 
 use crate::{
@@ -20,17 +20,27 @@ pub(crate) fn extract_sprite_entities(
     mut extracts: ResMut<ExtractedSprites>,
     images: Res<Assets<Image>>,
     atlases: Res<Assets<TextureAtlas>>,
-    query: Query<(&Handle<Image>, &GlobalTransform, &Color, &Visibility), With<Sprite>>,
+    query: Query<
+        (
+            &Handle<Image>,
+            &GlobalTransform,
+            &Color,
+            &Visibility,
+            Option<&RenderLayers>,
+        ),
+        With<Sprite>,
+    >,
     texture_atlas_query: Query<(
         &TextureAtlasSprite,
         &Handle<TextureAtlas>,
         &GlobalTransform,
         &Color,
         &Visibility,
+        Option<&RenderLayers>,
     )>,
 ) {
     extracts.items.clear();
-    for (image, transform, color, visibility) in query.iter() {
+    for (image, transform, color, visibility, render_layers) in query.iter() {
         if !visibility.active || !visibility.visible {
             continue;
         }
@@ -43,9 +53,12 @@ pub(crate) fn extract_sprite_entities(
             uv_coord: None,
             color: *color,
             z: transform.translation.z,
+            render_layers: render_layers.copied().unwrap_or_default(),
         });
     }
-    for (sprite, atlas_handle, transform, color, visibility) in texture_atlas_query.iter() {
+    for (sprite, atlas_handle, transform, color, visibility, render_layers) in
+        texture_atlas_query.iter()
+    {
         if !visibility.active || !visibility.visible {
             continue;
         }
@@ -67,6 +80,7 @@ pub(crate) fn extract_sprite_entities(
             uv_coord: Some(uv_coord),
             color: *color,
             z: transform.translation.z,
+            render_layers: render_layers.copied().unwrap_or_default(),
         });
     }
 }
@@ -38,7 +38,10 @@ impl SpecializedPipeline for SpritePipeline {
         };
         RenderPipelineDescriptor {
             vertex_shader: vertex_shader.clone_weak(),
+            tessellation_shaders: None,
+            geometry_shader: None,
             fragment_shader: fragment_shader.clone_weak(),
+            shader_defs: Vec::new(),
             draw_parameters,
         }
     }
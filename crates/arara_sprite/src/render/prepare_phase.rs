@@ -1,6 +1,7 @@
 use arara_asset::Assets;
 use arara_ecs::prelude::*;
-use arara_render::{Mesh, TextureBuffer};
+use arara_render::{Mesh, RenderLayers, TextureBuffer, TextureSlots, VertexAttributeValues};
+use arara_utils::HashMap;
 use glam::vec4;
 
 use crate::{render::extract_phase::ExtractedSprites, sprite::ExtractedSprite, QUAD_MESH_HANDLE};
@@ -15,10 +16,30 @@ pub struct Vertex {
 
 glium::implement_vertex!(Vertex, i_position, i_color, i_tex_coord, i_tex_id);
 
+/// Consecutive sprites sharing the same [`RenderLayers`], sorted by `z`.
+///
+/// A new batch is started when the images of the sprites don't fit in the [`TextureSlots`].
 #[derive(Component)]
 pub struct SpriteBatch {
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u32>,
+    pub render_layers: RenderLayers,
+    /// `z` of the first sprite
+    pub z: f32,
+    /// The images sampled by the sprites, `i_tex_id` indexes them
+    pub texture_slots: TextureSlots,
+}
+
+impl SpriteBatch {
+    fn new(render_layers: RenderLayers, z: f32, texture_slots: TextureSlots) -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            render_layers,
+            z,
+            texture_slots,
+        }
+    }
 }
 
 pub(crate) fn prepare_sprite_phase(
@@ -31,8 +52,15 @@ pub(crate) fn prepare_sprite_phase(
         return;
     }
     let mesh = meshes.get(QUAD_MESH_HANDLE).unwrap();
-    let mut vertices = Vec::with_capacity(extracts.items.len() * 4);
-    let mut indices = Vec::with_capacity(extracts.items.len() * 6);
+    let positions = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(VertexAttributeValues::as_float3)
+        .unwrap();
+    let tex_coords = mesh
+        .attribute(Mesh::ATTRIBUTE_UV_0)
+        .and_then(VertexAttributeValues::as_float2)
+        .unwrap();
+    let mut batches: HashMap<RenderLayers, Vec<SpriteBatch>> = HashMap::default();
 
     extracts
         .items
@@ -43,26 +71,42 @@ pub(crate) fn prepare_sprite_phase(
         transform,
         color,
         uv_coord,
-        z: _,
+        z,
+        render_layers,
     } in extracts.items.iter()
     {
-        let tex_id = texture_buffer.get_or_insert(image_handle.clone_weak());
+        let layer_batches = batches.entry(*render_layers).or_default();
+        match layer_batches.last() {
+            Some(batch) if batch.texture_slots.fits([image_handle]) => {}
+            _ => layer_batches.push(SpriteBatch::new(
+                *render_layers,
+                *z,
+                texture_buffer.new_slots(),
+            )),
+        }
+        let SpriteBatch {
+            vertices,
+            indices,
+            texture_slots,
+            ..
+        } = layer_batches.last_mut().unwrap();
+        let tex_id = texture_buffer.bind(texture_slots, image_handle).unwrap();
         let color: [f32; 4] = color.to_owned().into();
         let offset = vertices.len() as u32;
 
-        for vertex in mesh.vertices.iter() {
+        for (vertex_position, tex_coord) in positions.iter().zip(tex_coords) {
             let position = vec4(
-                vertex.position[0],
-                vertex.position[1],
-                vertex.position[2],
+                vertex_position[0],
+                vertex_position[1],
+                vertex_position[2],
                 1.0,
             );
             let i_tex_coord = match uv_coord {
                 Some(coord) => [
-                    coord.point.x + vertex.tex_coord[0] * coord.size.x,
-                    coord.point.y + vertex.tex_coord[1] * coord.size.y,
+                    coord.point.x + tex_coord[0] * coord.size.x,
+                    coord.point.y + tex_coord[1] * coord.size.y,
                 ],
-                None => vertex.tex_coord,
+                None => *tex_coord,
             };
             let position = *transform * position;
             vertices.push(Vertex {
@@ -76,5 +120,7 @@ pub(crate) fn prepare_sprite_phase(
             indices.push(*idx + offset);
         }
     }
-    commands.spawn().insert(SpriteBatch { vertices, indices });
+    for batch in batches.into_values().flatten() {
+        commands.spawn().insert(batch);
+    }
 }
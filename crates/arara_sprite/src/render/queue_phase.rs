@@ -1,5 +1,7 @@
 use arara_ecs::prelude::*;
-use arara_render::{DrawFunctions, RenderPhase, RenderPipelineCache, SpecializedPipelines};
+use arara_render::{
+    DrawFunctions, ExtractedView, RenderPhase, RenderPipelineCache, SpecializedPipelines,
+};
 
 use crate::render::{
     draw_function::DrawSprite,
@@ -9,8 +11,8 @@ use crate::render::{
 };
 
 pub(crate) fn queue_sprite_phase(
-    mut phase: ResMut<RenderPhase<Transparent2D>>,
-    query: Query<Entity, With<SpriteBatch>>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent2D>)>,
+    query: Query<(Entity, &SpriteBatch)>,
     mut render_pipeline_cache: NonSendMut<RenderPipelineCache>,
     pipeline: Res<SpritePipeline>,
     mut pipelines: ResMut<SpecializedPipelines<SpritePipeline>>,
@@ -25,12 +27,17 @@ pub(crate) fn queue_sprite_phase(
 
     let draw_sprite_function = draw_functions.read().get_id::<DrawSprite>().unwrap();
 
-    for entity in query.iter() {
-        phase.add(Transparent2D {
-            distance: 0.0,
-            entity,
-            draw_function: draw_sprite_function,
-            pipeline: sprite_pipeline,
-        });
+    for (entity, batch) in query.iter() {
+        for (view, mut phase) in views.iter_mut() {
+            if !view.render_layers.intersects(&batch.render_layers) {
+                continue;
+            }
+            phase.add(Transparent2D {
+                distance: batch.z,
+                entity,
+                draw_function: draw_sprite_function,
+                pipeline: sprite_pipeline,
+            });
+        }
     }
 }
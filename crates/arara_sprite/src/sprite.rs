@@ -1,6 +1,6 @@
 use arara_asset::Handle;
 use arara_ecs::component::Component;
-use arara_render::{Color, Image};
+use arara_render::{Color, Image, RenderLayers};
 use glam::Mat4;
 
 use crate::texture_atlas::TextureAtlasCoord;
@@ -15,4 +15,5 @@ pub struct ExtractedSprite {
     pub uv_coord: Option<TextureAtlasCoord>,
     pub color: Color,
     pub z: f32,
+    pub render_layers: RenderLayers,
 }
@@ -36,10 +36,7 @@ pub struct TextureAtlasSprite {
 
 impl TextureAtlasSprite {
     pub fn new(index: usize) -> TextureAtlasSprite {
-        Self {
-            index,
-            ..Default::default()
-        }
+        Self { index }
     }
 }
 
@@ -117,7 +114,7 @@ impl TextureAtlas {
     /// # Arguments
     ///
     /// * `texture` - The section of the atlas that contains the texture to be added,
-    /// from the top-left corner of the texture to the bottom-right corner
+    ///   from the top-left corner of the texture to the bottom-right corner
     pub fn add_texture(&mut self, texture: TextureAtlasCoord) -> usize {
         self.textures.push(texture);
         self.textures.len() - 1
@@ -0,0 +1,92 @@
+use arara_render::Image;
+use thiserror::Error;
+
+/// The result of comparing two images pixel by pixel with [`compare_images`].
+#[derive(Debug, Clone)]
+pub struct ImageComparison {
+    /// Amount of pixels where any channel differs by more than the tolerance.
+    pub mismatched_pixels: usize,
+    pub total_pixels: usize,
+    /// Mismatched pixels painted red over a darkened grayscale copy of the expected image.
+    pub diff: Image,
+}
+
+impl ImageComparison {
+    /// The fraction of mismatched pixels, between `0.0` and `1.0`.
+    pub fn mismatch_ratio(&self) -> f32 {
+        if self.total_pixels == 0 {
+            0.0
+        } else {
+            self.mismatched_pixels as f32 / self.total_pixels as f32
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CompareError {
+    #[error("image dimensions differ, expected {expected:?} but got {actual:?}")]
+    DimensionMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+}
+
+/// Compares two RGBA8 images, a pixel matches when none of its channels
+/// differs by more than `tolerance`.
+pub fn compare_images(
+    expected: &Image,
+    actual: &Image,
+    tolerance: u8,
+) -> Result<ImageComparison, CompareError> {
+    if expected.dimensions != actual.dimensions {
+        return Err(CompareError::DimensionMismatch {
+            expected: expected.dimensions,
+            actual: actual.dimensions,
+        });
+    }
+
+    let mut mismatched_pixels = 0;
+    let mut diff = Vec::with_capacity(expected.data.len());
+    for (e, a) in expected.data.chunks(4).zip(actual.data.chunks(4)) {
+        let matches = e.iter().zip(a).all(|(e, a)| e.abs_diff(*a) <= tolerance);
+        if matches {
+            let luma = ((e[0] as u32 * 299 + e[1] as u32 * 587 + e[2] as u32 * 114) / 1000) as u8;
+            diff.extend_from_slice(&[luma / 3, luma / 3, luma / 3, 255]);
+        } else {
+            mismatched_pixels += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+
+    Ok(ImageComparison {
+        mismatched_pixels,
+        total_pixels: diff.len() / 4,
+        diff: Image::new(diff, expected.dimensions, false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerance_is_per_channel() {
+        let expected = Image::new(vec![100, 100, 100, 255, 0, 0, 0, 255], (2, 1), false);
+        let actual = Image::new(vec![102, 99, 100, 255, 0, 10, 0, 255], (2, 1), false);
+
+        let comparison = compare_images(&expected, &actual, 2).unwrap();
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert_eq!(comparison.total_pixels, 2);
+        assert_eq!(&comparison.diff.data[4..], &[255, 0, 0, 255]);
+
+        let comparison = compare_images(&expected, &actual, 10).unwrap();
+        assert_eq!(comparison.mismatched_pixels, 0);
+    }
+
+    #[test]
+    fn different_dimensions() {
+        let expected = Image::new(vec![0; 8], (2, 1), false);
+        let actual = Image::new(vec![0; 8], (1, 2), false);
+        assert!(compare_images(&expected, &actual, 0).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use arara_app::App;
+use arara_ecs::event::Events;
+use arara_render::{
+    Image, ImageType, ScreenshotCaptured, ScreenshotFailed, ScreenshotManager, TextureError,
+};
+use arara_time::{Duration, Time};
+use arara_window::WindowProps;
+use thiserror::Error;
+
+use crate::{compare_images, CompareError};
+
+/// When this environment variable is set the golden images are overwritten with the captured frames.
+pub const UPDATE_GOLDEN_ENV: &str = "ARARA_UPDATE_GOLDEN";
+
+/// Renders an [`App`] for a fixed amount of frames and compares the last one against a stored PNG.
+///
+/// The app must render headlessly, e.g. with `HeadlessPlugins`. Time advances by a fixed delta
+/// every frame so the captured frame does not depend on how fast the machine is.
+///
+/// ```ignore
+/// # use arara::prelude::*;
+/// # use arara_test::GoldenTest;
+/// GoldenTest::new("empty").assert(|app| {
+///     app.add_plugins(HeadlessPlugins);
+/// });
+/// ```
+///
+/// On mismatch the captured frame and a diff image are written next to the golden image as
+/// `<name>.actual.png` and `<name>.diff.png`.
+/// Run with `ARARA_UPDATE_GOLDEN=1` to create or update the golden images.
+pub struct GoldenTest {
+    name: String,
+    golden_dir: PathBuf,
+    width: u32,
+    height: u32,
+    frames: usize,
+    delta: Duration,
+    tolerance: u8,
+    max_mismatch_ratio: f32,
+}
+
+impl GoldenTest {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            golden_dir: PathBuf::from("tests/golden"),
+            width: 400,
+            height: 300,
+            frames: 10,
+            delta: Duration::from_secs_f64(1.0 / 60.0),
+            tolerance: 2,
+            max_mismatch_ratio: 0.0,
+        }
+    }
+
+    /// The directory where `<name>.png` is stored, relative paths start at the package root.
+    pub fn golden_dir(mut self, golden_dir: impl Into<PathBuf>) -> Self {
+        self.golden_dir = golden_dir.into();
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Amount of updates ran before capturing the frame.
+    pub fn frames(mut self, frames: usize) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// The fixed [`Time`] delta of every update.
+    pub fn delta(mut self, delta: Duration) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Maximum difference of a single channel for a pixel to still match.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Fraction of the pixels allowed to mismatch, for scenes with some randomness.
+    pub fn max_mismatch_ratio(mut self, max_mismatch_ratio: f32) -> Self {
+        self.max_mismatch_ratio = max_mismatch_ratio;
+        self
+    }
+
+    pub fn golden_path(&self) -> PathBuf {
+        self.output_path("png")
+    }
+
+    fn output_path(&self, extension: &str) -> PathBuf {
+        self.golden_dir.join(format!("{}.{}", self.name, extension))
+    }
+
+    /// Builds the app with `setup`, renders it and compares the captured frame with the golden image.
+    pub fn run(&self, setup: impl FnOnce(&mut App)) -> Result<(), GoldenError> {
+        let actual = self.capture(setup)?;
+        let golden_path = self.golden_path();
+
+        if env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+            fs::create_dir_all(&self.golden_dir)?;
+            actual.save(&golden_path)?;
+            return Ok(());
+        }
+
+        if !golden_path.exists() {
+            return Err(GoldenError::MissingGolden(golden_path));
+        }
+        let expected = load_png(&golden_path)?;
+
+        let comparison = compare_images(&expected, &actual, self.tolerance)?;
+        if comparison.mismatch_ratio() > self.max_mismatch_ratio {
+            let diff_path = self.output_path("diff.png");
+            actual.save(self.output_path("actual.png"))?;
+            comparison.diff.save(&diff_path)?;
+            return Err(GoldenError::Mismatch {
+                name: self.name.clone(),
+                mismatched_pixels: comparison.mismatched_pixels,
+                total_pixels: comparison.total_pixels,
+                diff_path,
+            });
+        }
+        Ok(())
+    }
+
+    /// Same as [`GoldenTest::run`] but panics on failure.
+    pub fn assert(&self, setup: impl FnOnce(&mut App)) {
+        if let Err(err) = self.run(setup) {
+            panic!("golden test `{}` failed: {}", self.name, err);
+        }
+    }
+
+    /// Builds the app with `setup`, runs the configured amount of frames and returns the next one.
+    pub fn capture(&self, setup: impl FnOnce(&mut App)) -> Result<Image, GoldenError> {
+        let mut app = App::new();
+        app.insert_resource(WindowProps {
+            width: self.width,
+            height: self.height,
+            title: self.name.clone(),
+            ..Default::default()
+        });
+        setup(&mut app);
+
+        app.world
+            .get_resource_mut::<Time>()
+            .expect("GoldenTest requires the TimePlugin")
+            .set_fixed_delta(Some(self.delta));
+
+        for _ in 0..self.frames {
+            app.update();
+        }
+
+        let id = app
+            .world
+            .get_resource_mut::<ScreenshotManager>()
+            .expect("GoldenTest requires the RenderPlugin")
+            .take_screenshot();
+        app.update();
+
+        let failures = app
+            .world
+            .get_resource::<Events<ScreenshotFailed>>()
+            .unwrap();
+        if let Some(failed) = failures
+            .get_reader()
+            .iter(failures)
+            .find(|failed| failed.id == id)
+        {
+            return Err(GoldenError::CaptureFailed(failed.error.clone()));
+        }
+
+        let events = app
+            .world
+            .get_resource::<Events<ScreenshotCaptured>>()
+            .unwrap();
+        let image = events
+            .get_reader()
+            .iter(events)
+            .find(|captured| captured.id == id)
+            .map(|captured| captured.image.clone());
+        image.ok_or(GoldenError::NotCaptured)
+    }
+}
+
+fn load_png(path: &Path) -> Result<Image, GoldenError> {
+    let buffer = fs::read(path)?;
+    Ok(Image::from_buffer(&buffer, ImageType::Extension("png"))?)
+}
+
+#[derive(Error, Debug)]
+pub enum GoldenError {
+    #[error("golden image {} not found, run with `ARARA_UPDATE_GOLDEN=1` to create it", .0.display())]
+    MissingGolden(PathBuf),
+    #[error("no frame was captured, is the app rendering?")]
+    NotCaptured,
+    #[error("failed to capture the frame: {0}")]
+    CaptureFailed(String),
+    #[error("{mismatched_pixels} of {total_pixels} pixels of `{name}` differ, see {}", .diff_path.display())]
+    Mismatch {
+        name: String,
+        mismatched_pixels: usize,
+        total_pixels: usize,
+        diff_path: PathBuf,
+    },
+    #[error(transparent)]
+    Compare(#[from] CompareError),
+    #[error("failed to read or write an image: {0}")]
+    Texture(#[from] TextureError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,9 @@
+mod compare;
+mod golden;
+
+pub use compare::*;
+pub use golden::*;
+
+pub mod prelude {
+    pub use crate::{compare::compare_images, golden::GoldenTest};
+}
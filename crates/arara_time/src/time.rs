@@ -9,6 +9,7 @@ pub struct Time {
     startup_delta: Duration,
     last_update: Option<Instant>,
     delta: Duration,
+    fixed_delta: Option<Duration>,
 }
 
 impl Default for Time {
@@ -18,13 +19,31 @@ impl Default for Time {
             startup_delta: Duration::from_secs(0),
             last_update: None,
             delta: Duration::from_secs(0),
+            fixed_delta: None,
         }
     }
 }
 
 impl Time {
     pub fn update(&mut self) {
-        self.update_with_instant(Instant::now());
+        match self.fixed_delta {
+            Some(delta) => {
+                let last_update = *self.last_update.get_or_insert(self.startup);
+                self.update_with_instant(last_update + delta);
+            }
+            None => self.update_with_instant(Instant::now()),
+        }
+    }
+
+    /// Makes every update advance the time by exactly `delta` instead of the wall clock elapsed time,
+    /// useful for deterministic runs such as tests
+    pub fn set_fixed_delta(&mut self, delta: Option<Duration>) {
+        self.fixed_delta = delta;
+    }
+
+    /// The delta every update advances by, if it was fixed with [`Time::set_fixed_delta`]
+    pub fn fixed_delta(&self) -> Option<Duration> {
+        self.fixed_delta
     }
 
     pub(crate) fn update_with_instant(&mut self, instant: Instant) {
@@ -69,3 +88,21 @@ impl Time {
 pub(crate) fn update_time(mut time: ResMut<Time>) {
     time.update();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_delta_ignores_wall_clock() {
+        let mut time = Time::default();
+        time.set_fixed_delta(Some(Duration::from_millis(16)));
+
+        time.update();
+        time.update();
+        time.update();
+
+        assert_eq!(time.delta(), Duration::from_millis(16));
+        assert_eq!(time.delta_since_startup(), Duration::from_millis(48));
+    }
+}
@@ -34,7 +34,7 @@ impl Timer {
     ///
     /// # Example
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// ```
     pub fn from_seconds(duration: f32, repeating: bool) -> Self {
@@ -49,7 +49,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.tick(Duration::from_secs_f32(1.5));
@@ -65,7 +65,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.tick(Duration::from_secs_f32(1.5));
@@ -84,7 +84,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.tick(Duration::from_secs_f32(0.5));
@@ -106,7 +106,7 @@ impl Timer {
     ///
     /// #
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.set_elapsed(Duration::from_secs(2));
@@ -122,7 +122,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let timer = Timer::new(Duration::from_secs(1), false);
     /// assert_eq!(timer.duration(), Duration::from_secs(1));
@@ -135,7 +135,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.5, false);
     /// timer.set_duration(Duration::from_secs(1));
@@ -149,7 +149,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// let mut timer = Timer::from_seconds(1.0, true);
     /// assert!(timer.repeating());
     /// ```
@@ -161,7 +161,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// let mut timer = Timer::from_seconds(1.0, true);
     /// timer.set_repeating(false);
     /// assert!(!timer.repeating());
@@ -182,7 +182,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// let mut repeating = Timer::from_seconds(1.0, true);
@@ -226,7 +226,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.pause();
@@ -243,7 +243,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.pause();
@@ -262,7 +262,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// assert!(!timer.paused());
     /// timer.pause();
@@ -280,7 +280,7 @@ impl Timer {
     ///
     /// Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, false);
     /// timer.tick(Duration::from_secs_f32(1.5));
@@ -299,7 +299,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(2.0, false);
     /// timer.tick(Duration::from_secs_f32(0.5));
@@ -313,7 +313,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(2.0, false);
     /// timer.tick(Duration::from_secs_f32(0.5));
@@ -331,7 +331,7 @@ impl Timer {
     ///
     /// # Examples
     /// ```
-    /// # use arara_core::*;
+    /// # use arara_time::*;
     /// use std::time::Duration;
     /// let mut timer = Timer::from_seconds(1.0, true);
     /// timer.tick(Duration::from_secs_f32(6.0));
@@ -358,10 +358,10 @@ mod tests {
         t.tick(Duration::from_secs_f32(0.25));
         assert_eq!(t.elapsed_secs(), 0.25);
         assert_eq!(t.duration(), Duration::from_secs_f32(10.0));
-        assert_eq!(t.finished(), false);
-        assert_eq!(t.just_finished(), false);
+        assert!(!t.finished());
+        assert!(!t.just_finished());
         assert_eq!(t.times_finished(), 0);
-        assert_eq!(t.repeating(), false);
+        assert!(!t.repeating());
         assert_eq!(t.percent(), 0.025);
         assert_eq!(t.percent_left(), 0.975);
         // Ticking while paused changes nothing
@@ -369,26 +369,26 @@ mod tests {
         t.tick(Duration::from_secs_f32(500.0));
         assert_eq!(t.elapsed_secs(), 0.25);
         assert_eq!(t.duration(), Duration::from_secs_f32(10.0));
-        assert_eq!(t.finished(), false);
-        assert_eq!(t.just_finished(), false);
+        assert!(!t.finished());
+        assert!(!t.just_finished());
         assert_eq!(t.times_finished(), 0);
-        assert_eq!(t.repeating(), false);
+        assert!(!t.repeating());
         assert_eq!(t.percent(), 0.025);
         assert_eq!(t.percent_left(), 0.975);
         // Tick past the end and make sure elapsed doesn't go past 0.0 and other things update
         t.unpause();
         t.tick(Duration::from_secs_f32(500.0));
         assert_eq!(t.elapsed_secs(), 10.0);
-        assert_eq!(t.finished(), true);
-        assert_eq!(t.just_finished(), true);
+        assert!(t.finished());
+        assert!(t.just_finished());
         assert_eq!(t.times_finished(), 1);
         assert_eq!(t.percent(), 1.0);
         assert_eq!(t.percent_left(), 0.0);
         // Continuing to tick when finished should only change just_finished
         t.tick(Duration::from_secs_f32(1.0));
         assert_eq!(t.elapsed_secs(), 10.0);
-        assert_eq!(t.finished(), true);
-        assert_eq!(t.just_finished(), false);
+        assert!(t.finished());
+        assert!(!t.just_finished());
         assert_eq!(t.times_finished(), 0);
         assert_eq!(t.percent(), 1.0);
         assert_eq!(t.percent_left(), 0.0);
@@ -401,25 +401,25 @@ mod tests {
         t.tick(Duration::from_secs_f32(0.75));
         assert_eq!(t.elapsed_secs(), 0.75);
         assert_eq!(t.duration(), Duration::from_secs_f32(2.0));
-        assert_eq!(t.finished(), false);
-        assert_eq!(t.just_finished(), false);
+        assert!(!t.finished());
+        assert!(!t.just_finished());
         assert_eq!(t.times_finished(), 0);
-        assert_eq!(t.repeating(), true);
+        assert!(t.repeating());
         assert_eq!(t.percent(), 0.375);
         assert_eq!(t.percent_left(), 0.625);
         // Tick past the end and make sure elapsed wraps
         t.tick(Duration::from_secs_f32(1.5));
         assert_eq!(t.elapsed_secs(), 0.25);
-        assert_eq!(t.finished(), true);
-        assert_eq!(t.just_finished(), true);
+        assert!(t.finished());
+        assert!(t.just_finished());
         assert_eq!(t.times_finished(), 1);
         assert_eq!(t.percent(), 0.125);
         assert_eq!(t.percent_left(), 0.875);
         // Continuing to tick should turn off both finished & just_finished for repeating timers
         t.tick(Duration::from_secs_f32(1.0));
         assert_eq!(t.elapsed_secs(), 1.25);
-        assert_eq!(t.finished(), false);
-        assert_eq!(t.just_finished(), false);
+        assert!(!t.finished());
+        assert!(!t.just_finished());
         assert_eq!(t.times_finished(), 0);
         assert_eq!(t.percent(), 0.625);
         assert_eq!(t.percent_left(), 0.375);
@@ -147,7 +147,7 @@ pub struct WorldChildBuilder<'w> {
 }
 
 impl<'w> WorldChildBuilder<'w> {
-    pub fn spawn_bundle(&mut self, bundle: impl Bundle + Send + Sync + 'static) -> EntityMut<'_> {
+    pub fn spawn_bundle(&mut self, bundle: impl Bundle + 'static) -> EntityMut<'_> {
         let parent_entity = self.parent_entity();
         let entity = self
             .world
@@ -334,6 +334,7 @@ mod tests {
     use smallvec::{smallvec, SmallVec};
 
     #[derive(Component)]
+    #[allow(dead_code)]
     struct C(u32);
 
     #[test]
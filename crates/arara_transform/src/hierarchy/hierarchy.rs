@@ -181,7 +181,7 @@ mod tests {
         {
             let children = world.get::<Children>(grandparent_entity).unwrap();
             assert!(
-                !children.iter().any(|&i| i == parent_entity),
+                !children.contains(&parent_entity),
                 "grandparent should no longer know about its child which has been removed"
             );
         }
@@ -36,7 +36,7 @@ pub fn parent_update_system(
 
             // Remove from `PreviousParent.Children`.
             if let Ok(mut previous_parent_children) = children_query.get_mut(previous_parent.0) {
-                (*previous_parent_children).0.retain(|e| *e != entity);
+                previous_parent_children.0.retain(|e| *e != entity);
             }
 
             // Set `PreviousParent = Parent`.
@@ -50,10 +50,10 @@ pub fn parent_update_system(
         if let Ok(mut new_parent_children) = children_query.get_mut(parent.0) {
             // This is the parent
             debug_assert!(
-                !(*new_parent_children).0.contains(&entity),
+                !new_parent_children.0.contains(&entity),
                 "children already added"
             );
-            (*new_parent_children).0.push(entity);
+            new_parent_children.0.push(entity);
         } else {
             // The parent doesn't have a children entity, lets add it
             children_additions
@@ -129,7 +129,7 @@ mod test {
         );
 
         // Parent `e1` to `e2`.
-        (*world.get_mut::<Parent>(children[0]).unwrap()).0 = children[1];
+        world.get_mut::<Parent>(children[0]).unwrap().0 = children[1];
 
         schedule.run(&mut world);
 
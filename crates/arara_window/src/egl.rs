@@ -0,0 +1,118 @@
+use std::{ffi::CString, os::raw::c_void, ptr};
+
+use glium::{backend::Backend, SwapBuffersError};
+use glutin_egl_sys::egl::{self, types::EGLint};
+
+/// `EGL_PLATFORM_SURFACELESS_MESA`, missing from the bindings.
+const PLATFORM_SURFACELESS_MESA: egl::types::EGLenum = 0x31DD;
+
+/// Most recent versions first, the first one the driver supports is used.
+const GL_VERSIONS: [(EGLint, EGLint); 4] = [(4, 6), (4, 5), (4, 3), (3, 3)];
+
+/// An OpenGL context of Mesa's surfaceless EGL platform, which renders through the GPU render
+/// nodes or in software without a display server.
+///
+/// It has no default framebuffer, everything is drawn into framebuffer objects.
+pub(crate) struct SurfacelessContext {
+    egl: egl::Egl,
+    display: egl::types::EGLDisplay,
+    context: egl::types::EGLContext,
+    dimensions: (u32, u32),
+    // the function pointers of `egl` live in the library
+    _library: libloading::Library,
+}
+
+impl SurfacelessContext {
+    pub(crate) fn new(dimensions: (u32, u32)) -> Result<Self, String> {
+        let library = unsafe { libloading::Library::new("libEGL.so.1") }
+            .map_err(|err| format!("failed to load libEGL: {}", err))?;
+        let egl = egl::Egl::load_with(|symbol| {
+            let symbol = CString::new(symbol).unwrap();
+            unsafe {
+                library
+                    .get::<*const c_void>(symbol.as_bytes_with_nul())
+                    .map_or(ptr::null(), |function| *function)
+            }
+        });
+
+        unsafe {
+            let display =
+                egl.GetPlatformDisplay(PLATFORM_SURFACELESS_MESA, ptr::null_mut(), ptr::null());
+            if display == egl::NO_DISPLAY {
+                return Err("the surfaceless EGL platform is not available".to_string());
+            }
+            if egl.Initialize(display, ptr::null_mut(), ptr::null_mut()) == egl::FALSE
+                || egl.BindAPI(egl::OPENGL_API) == egl::FALSE
+            {
+                return Err(format!("failed to initialize EGL: {:#x}", egl.GetError()));
+            }
+
+            // nothing is drawn into surfaces so the context needs no config
+            let context = GL_VERSIONS
+                .iter()
+                .map(|&(major, minor)| {
+                    let attributes = [
+                        egl::CONTEXT_MAJOR_VERSION as EGLint,
+                        major,
+                        egl::CONTEXT_MINOR_VERSION as EGLint,
+                        minor,
+                        egl::CONTEXT_OPENGL_PROFILE_MASK as EGLint,
+                        egl::CONTEXT_OPENGL_CORE_PROFILE_BIT as EGLint,
+                        egl::NONE as EGLint,
+                    ];
+                    egl.CreateContext(display, ptr::null(), egl::NO_CONTEXT, attributes.as_ptr())
+                })
+                .find(|&context| context != egl::NO_CONTEXT)
+                .ok_or_else(|| format!("failed to create the context: {:#x}", egl.GetError()))?;
+
+            Ok(Self {
+                egl,
+                display,
+                context,
+                dimensions,
+                _library: library,
+            })
+        }
+    }
+}
+
+unsafe impl Backend for SurfacelessContext {
+    fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+        Ok(())
+    }
+
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        let symbol = CString::new(symbol).unwrap();
+        self.egl.GetProcAddress(symbol.as_ptr()) as *const c_void
+    }
+
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    fn is_current(&self) -> bool {
+        unsafe { self.egl.GetCurrentContext() == self.context }
+    }
+
+    unsafe fn make_current(&self) {
+        self.egl
+            .MakeCurrent(self.display, egl::NO_SURFACE, egl::NO_SURFACE, self.context);
+    }
+}
+
+impl Drop for SurfacelessContext {
+    fn drop(&mut self) {
+        // the display is shared by every context of the process, so it isn't terminated
+        unsafe {
+            if self.is_current() {
+                self.egl.MakeCurrent(
+                    self.display,
+                    egl::NO_SURFACE,
+                    egl::NO_SURFACE,
+                    egl::NO_CONTEXT,
+                );
+            }
+            self.egl.DestroyContext(self.display, self.context);
+        }
+    }
+}
@@ -13,6 +13,7 @@ impl Default for EventLoop {
 }
 
 impl EventLoop {
+    #[allow(clippy::should_implement_trait)]
     pub fn borrow(&self) -> &Option<WinitEventLoop<()>> {
         &self.event_loop
     }
@@ -0,0 +1,60 @@
+use arara_app::{App, AppExit, Plugin};
+use arara_ecs::event::{Events, ManualEventReader};
+use arara_utils::tracing::trace;
+
+use crate::{add_window_events, exit_on_window_close, Window, WindowProps, WindowResized};
+
+/// Replaces the [`WindowPlugin`](crate::WindowPlugin) for environments without a display server.
+///
+/// A headless [`Window`] is created from the [`WindowProps`] resource and the app is updated in a
+/// loop until an [`AppExit`] event is sent, no window or input events are ever produced.
+///
+/// # Panics
+/// Panics if no OpenGL context can be created, use [`Window::headless`] to handle the error.
+#[derive(Default)]
+pub struct HeadlessWindowPlugin;
+
+impl Plugin for HeadlessWindowPlugin {
+    fn build(&self, app: &mut App) {
+        let window_props = app
+            .world
+            .get_resource::<WindowProps>()
+            .cloned()
+            .unwrap_or_default();
+
+        add_window_events(app);
+        let window = match Window::headless(&window_props) {
+            Ok(window) => window,
+            Err(err) => panic!("failed to create the headless window: {}", err),
+        };
+        app.world.insert_non_send(window);
+        app.world
+            .get_resource_mut::<Events<WindowResized>>()
+            .unwrap()
+            .send(WindowResized {
+                width: window_props.width,
+                height: window_props.height,
+            });
+        app.add_system(exit_on_window_close)
+            .set_runnable(run_headless);
+    }
+}
+
+pub fn run_headless(mut app: App) {
+    let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
+
+    trace!("Entering headless loop");
+    loop {
+        app.update();
+
+        if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
+            if app_exit_event_reader
+                .iter(&app_exit_events)
+                .next_back()
+                .is_some()
+            {
+                break;
+            }
+        }
+    }
+}
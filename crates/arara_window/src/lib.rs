@@ -1,18 +1,28 @@
 mod converters;
+#[cfg(target_os = "linux")]
+mod egl;
 mod event;
 mod event_loop;
+mod headless;
 mod runnable;
 mod window;
 mod window_props;
 
 pub use event::*;
 pub use event_loop::*;
+pub use headless::*;
 pub use runnable::*;
 pub use window::*;
 pub use window_props::*;
 
 pub mod prelude {
-    pub use crate::{event::*, window::{Window, WindowMode}, window_props::WindowProps, WindowPlugin};
+    pub use crate::{
+        event::*,
+        headless::HeadlessWindowPlugin,
+        window::{Window, WindowMode},
+        window_props::WindowProps,
+        WindowPlugin,
+    };
 }
 
 use arara_app::{App, AppExit, Plugin};
@@ -23,18 +33,8 @@ pub struct WindowPlugin;
 
 impl Plugin for WindowPlugin {
     fn build(&self, app_builder: &mut App) {
+        add_window_events(app_builder);
         app_builder
-            .add_event::<WindowResized>()
-            .add_event::<WindowCreated>()
-            .add_event::<WindowCloseRequested>()
-            .add_event::<CloseWindow>()
-            .add_event::<CursorMoved>()
-            .add_event::<CursorEntered>()
-            .add_event::<CursorLeft>()
-            .add_event::<ReceivedCharacter>()
-            .add_event::<WindowFocused>()
-            .add_event::<FileDragAndDrop>()
-            .add_event::<WindowMoved>()
             .init_non_send_resource::<EventLoop>()
             .init_non_send_resource::<Window>()
             .add_system(exit_on_window_close)
@@ -42,6 +42,20 @@ impl Plugin for WindowPlugin {
     }
 }
 
+pub(crate) fn add_window_events(app: &mut App) {
+    app.add_event::<WindowResized>()
+        .add_event::<WindowCreated>()
+        .add_event::<WindowCloseRequested>()
+        .add_event::<CloseWindow>()
+        .add_event::<CursorMoved>()
+        .add_event::<CursorEntered>()
+        .add_event::<CursorLeft>()
+        .add_event::<ReceivedCharacter>()
+        .add_event::<WindowFocused>()
+        .add_event::<FileDragAndDrop>()
+        .add_event::<WindowMoved>();
+}
+
 pub fn exit_on_window_close(
     mut app_exit_events: EventWriter<AppExit>,
     mut window_close_requested_events: EventReader<WindowCloseRequested>,
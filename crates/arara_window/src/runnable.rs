@@ -32,16 +32,16 @@ pub fn run(mut app: App) {
         }
 
         match ev {
-            Event::DeviceEvent { ref event, .. } => match event {
-                DeviceEvent::MouseMotion { delta } => {
-                    let mut mouse_motion_events =
-                        app.world.get_resource_mut::<Events<MouseMotion>>().unwrap();
-                    mouse_motion_events.send(MouseMotion {
-                        delta: (delta.0 as f32, delta.1 as f32),
-                    });
-                }
-                _ => (),
-            },
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                let mut mouse_motion_events =
+                    app.world.get_resource_mut::<Events<MouseMotion>>().unwrap();
+                mouse_motion_events.send(MouseMotion {
+                    delta: (delta.0 as f32, delta.1 as f32),
+                });
+            }
             Event::WindowEvent { event, window_id } => {
                 let world = app.world.cell();
                 let mut window = world.get_non_send_mut::<Window>().unwrap();
@@ -1,16 +1,26 @@
+use std::{fmt, rc::Rc};
+
 use arara_ecs::world::{FromWorld, World};
 use glium::{
     self,
-    glutin::{self, dpi},
-    Display,
+    backend::{Context, Facade},
+    framebuffer::{DepthRenderBuffer, RenderBufferCreationError},
+    glutin::{self, dpi, event_loop::EventLoop as WinitEventLoop},
+    texture::{DepthFormat, MipmapsOption, TextureCreationError, UncompressedFloatFormat},
+    Display, HeadlessRenderer, IncompatibleOpenGl, Texture2d,
 };
+use arara_utils::tracing::trace;
+use thiserror::Error;
 
 use crate::{event_loop::EventLoop, window_props::WindowProps};
 
 /// An operating system window that can present content and receive user input.
+///
+/// A headless window has no visible surface, its frames are rendered into an offscreen
+/// framebuffer instead, see [`Window::headless`].
 #[derive(Debug)]
 pub struct Window {
-    display: Display,
+    backend: WindowBackend,
     width: u32,
     height: u32,
     title: String,
@@ -27,8 +37,7 @@ pub struct Window {
 impl FromWorld for Window {
     fn from_world(world: &mut World) -> Self {
         let window_props = world
-            .get_resource::<WindowProps>()
-            .and_then(|w| Some(w.to_owned()))
+            .get_resource::<WindowProps>().map(|w| w.to_owned())
             .unwrap_or_default();
         let event_loop = world.get_non_send_resource::<EventLoop>().unwrap();
 
@@ -49,8 +58,46 @@ impl FromWorld for Window {
 
 impl Window {
     pub fn new(display: Display, window_props: &WindowProps) -> Self {
+        Self::with_backend(WindowBackend::Display(display), window_props)
+    }
+
+    /// Creates a window without a visible surface.
+    ///
+    /// The OpenGL context is created through OSMesa or Mesa's surfaceless EGL platform when
+    /// available, so no display server is needed, falling back to a regular headless context
+    /// otherwise.
+    /// Frames are drawn into an offscreen framebuffer of `window_props.width` by
+    /// `window_props.height` pixels.
+    pub fn headless(window_props: &WindowProps) -> Result<Self, HeadlessError> {
+        let size = dpi::PhysicalSize::new(window_props.width, window_props.height);
+        let (renderer, event_loop) = build_headless_context(size)?;
+
+        let color = Texture2d::empty_with_format(
+            &renderer,
+            UncompressedFloatFormat::U8U8U8U8,
+            MipmapsOption::NoMipmap,
+            window_props.width,
+            window_props.height,
+        )?;
+        let depth = DepthRenderBuffer::new(
+            &renderer,
+            DepthFormat::I24,
+            window_props.width,
+            window_props.height,
+        )?;
+
+        let backend = WindowBackend::Headless(HeadlessBackend {
+            renderer,
+            color,
+            depth,
+            _event_loop: event_loop,
+        });
+        Ok(Self::with_backend(backend, window_props))
+    }
+
+    fn with_backend(backend: WindowBackend, window_props: &WindowProps) -> Self {
         Window {
-            display,
+            backend,
             width: window_props.width,
             height: window_props.height,
             title: window_props.title.clone(),
@@ -65,9 +112,35 @@ impl Window {
         }
     }
 
+    /// The glutin display of the window.
+    ///
+    /// # Panics
+    /// Panics if the window is headless, use [`Window::facade`] to create OpenGL objects instead.
     #[inline]
     pub fn display(&self) -> &Display {
-        &self.display
+        match &self.backend {
+            WindowBackend::Display(display) => display,
+            WindowBackend::Headless(_) => panic!("Headless windows have no Display"),
+        }
+    }
+
+    /// The OpenGL context of the window, regardless of its backend.
+    #[inline]
+    pub fn facade(&self) -> &dyn Facade {
+        match &self.backend {
+            WindowBackend::Display(display) => display,
+            WindowBackend::Headless(headless) => &headless.renderer,
+        }
+    }
+
+    #[inline]
+    pub fn backend(&self) -> &WindowBackend {
+        &self.backend
+    }
+
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        matches!(self.backend, WindowBackend::Headless(_))
     }
 
     /// The current logical width of the window's client area.
@@ -147,6 +220,136 @@ impl Window {
     }
 }
 
+/// The OpenGL context backing a [`Window`].
+#[allow(clippy::large_enum_variant)]
+pub enum WindowBackend {
+    /// An operating system window presenting frames on screen.
+    Display(Display),
+    /// A context without a visible surface, frames are drawn into an offscreen framebuffer.
+    Headless(HeadlessBackend),
+}
+
+impl fmt::Debug for WindowBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowBackend::Display(display) => f.debug_tuple("Display").field(display).finish(),
+            WindowBackend::Headless(headless) => f
+                .debug_struct("Headless")
+                .field("dimensions", &headless.color.dimensions())
+                .finish(),
+        }
+    }
+}
+
+pub struct HeadlessBackend {
+    renderer: Rc<Context>,
+    color: Texture2d,
+    depth: DepthRenderBuffer,
+    // Keeps the platform connection alive when the context needs a display server
+    _event_loop: Option<WinitEventLoop<()>>,
+}
+
+impl HeadlessBackend {
+    #[inline]
+    pub fn renderer(&self) -> &Rc<Context> {
+        &self.renderer
+    }
+
+    /// The color attachment of the offscreen framebuffer.
+    #[inline]
+    pub fn color(&self) -> &Texture2d {
+        &self.color
+    }
+
+    /// The depth attachment of the offscreen framebuffer.
+    #[inline]
+    pub fn depth(&self) -> &DepthRenderBuffer {
+        &self.depth
+    }
+}
+
+/// An error that occurs when creating a headless [`Window`].
+#[derive(Error, Debug)]
+pub enum HeadlessError {
+    #[error("failed to create the OpenGL context: {0}")]
+    Context(#[from] glutin::CreationError),
+    #[error(transparent)]
+    IncompatibleOpenGl(#[from] IncompatibleOpenGl),
+    #[error("failed to create the color attachment: {0}")]
+    Color(#[from] TextureCreationError),
+    #[error("failed to create the depth attachment: {0}")]
+    Depth(#[from] RenderBufferCreationError),
+}
+
+fn build_headless_context(
+    size: dpi::PhysicalSize<u32>,
+) -> Result<(Rc<Context>, Option<WinitEventLoop<()>>), HeadlessError> {
+    let cb = glutin::ContextBuilder::new().with_depth_buffer(24);
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    {
+        use glutin::platform::unix::HeadlessContextExt;
+        if let Ok(context) = cb.clone().build_osmesa(size) {
+            return Ok((HeadlessRenderer::new(context)?.get_context().clone(), None));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    match crate::egl::SurfacelessContext::new((size.width, size.height)) {
+        Ok(context) => {
+            // SAFETY: the context stays current on this thread, glium makes it current again
+            // otherwise
+            let context = unsafe { Context::new(context, true, Default::default())? };
+            return Ok((context, None));
+        }
+        Err(err) => trace!("no surfaceless EGL context: {}", err),
+    }
+
+    let event_loop = headless_event_loop()?;
+    let context = cb.build_headless(&event_loop, size)?;
+    Ok((
+        HeadlessRenderer::new(context)?.get_context().clone(),
+        Some(event_loop),
+    ))
+}
+
+/// Headless apps may live outside of the main thread, e.g. in tests.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn headless_event_loop() -> Result<WinitEventLoop<()>, HeadlessError> {
+    use glutin::platform::unix::EventLoopExtUnix;
+    // winit panics when there is no display server to connect to
+    if std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        return Err(glutin::CreationError::NotSupported(
+            "no OSMesa and no display server to create a headless context".to_string(),
+        )
+        .into());
+    }
+    Ok(WinitEventLoop::new_any_thread())
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn headless_event_loop() -> Result<WinitEventLoop<()>, HeadlessError> {
+    Ok(WinitEventLoop::new())
+}
+
 /// Defines the way a window is displayed
 /// The use_size option that is used in the Fullscreen variant
 /// defines whether a videomode is chosen that best fits the width and height
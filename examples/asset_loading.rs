@@ -6,7 +6,7 @@ fn main() {
         .add_startup_system(add_shapes)
         .add_startup_system(add_camera)
         .insert_resource(ClearColor(Color::WHITE))
-        .insert_resource(BPLight::new(-5.0, 10.0, 0.0))
+        .add_startup_system(add_light)
         .run()
 }
 
@@ -48,3 +48,14 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(-5.0, 10.0, 0.0),
+        ..Default::default()
+    });
+}
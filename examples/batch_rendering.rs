@@ -7,7 +7,7 @@ fn main() {
         .add_plugin(EntityCountDiagnosticPlugin)
         .add_plugin(AssetCountDiagnosticsPlugin::<Mesh>::default())
         .add_plugin(LogDiagnosticPlugin::default())
-        .insert_resource(BPLight::new(-5.0, -5.0, -5.0))
+        .add_startup_system(add_light)
         .insert_resource(ClearColor(Color::WHITE))
         .add_startup_system(add_camera)
         .add_startup_system(add_cubes)
@@ -52,3 +52,14 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(-5.0, -5.0, -5.0),
+        ..Default::default()
+    });
+}
@@ -1,4 +1,4 @@
-use crate::{Block, Board, Border, GameState, GameStateResource, Player};
+use super::{Block, Board, Border, GameState, GameStateResource, Player};
 use arara::prelude::*;
 
 #[derive(Component)]
@@ -13,7 +13,7 @@ pub fn move_ball(
     keyboard: Res<Input<KeyCode>>,
     time: Res<Time>,
 ) {
-    if let GameState::PLAYING = game_state.game_state {
+    if let GameState::Playing = game_state.game_state {
         let (mut transform, ball) = query_ball.single_mut();
         let mut speedy = 300.0 * ball.speed * time.delta_seconds() * ball.velocity;
         if keyboard.pressed(KeyCode::Space) {
@@ -1,4 +1,4 @@
-use crate::{Border, GameStateResource};
+use super::{Border, GameStateResource};
 use arara::prelude::*;
 
 #[derive(Component)]
@@ -3,8 +3,9 @@ mod block;
 mod player;
 
 use arara::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::{ball::*, block::*, player::*};
+use self::{ball::*, block::*, player::*};
 
 //|
 
@@ -25,29 +26,54 @@ fn main() {
         .add_plugin(EntityCountDiagnosticPlugin)
         .add_plugin(AssetCountDiagnosticsPlugin::<Image>::default())
         .add_plugin(LogDiagnosticPlugin::default())
-        .insert_resource(ClearColor(Color::ANTIQUE_WHITE))
-        .init_resource::<GameStateResource>()
-        .add_startup_system(add_camera)
-        .add_startup_system(setup)
-        .add_system_to_stage(CoreStage::PreUpdate, reset)
-        .add_system(show_block)
-        .add_system(move_player)
-        .add_system(move_ball)
-        .add_system_to_stage(CoreStage::PostUpdate, calculate_collision)
-        .add_system_to_stage(CoreStage::PostUpdate, is_victory_condition)
+        .add_plugin(BreakoutPlugin)
         .run()
 }
 
+/// The breakout game, shared with the golden image tests.
+pub struct BreakoutPlugin;
+
+impl Plugin for BreakoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClearColor(Color::ANTIQUE_WHITE))
+            .init_resource::<GameStateResource>()
+            .init_resource::<GameRng>()
+            .add_startup_system(add_camera)
+            .add_startup_system(setup)
+            .add_system_to_stage(CoreStage::PreUpdate, reset)
+            .add_system(show_block)
+            .add_system(move_player)
+            .add_system(move_ball)
+            .add_system_to_stage(CoreStage::PostUpdate, calculate_collision)
+            .add_system_to_stage(CoreStage::PostUpdate, is_victory_condition);
+    }
+}
+
 #[derive(Component)]
 pub struct Board;
 
 #[derive(Component)]
 pub struct Border;
 
+/// The ball directions and block colors are random, a seeded generator makes every game the same.
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
 enum GameState {
-    DEFEAT,
-    PLAYING,
-    VICTORY,
+    Defeat,
+    Playing,
+    Victory,
 }
 
 pub struct GameStateResource {
@@ -58,7 +84,7 @@ pub struct GameStateResource {
 impl Default for GameStateResource {
     fn default() -> Self {
         Self {
-            game_state: GameState::PLAYING,
+            game_state: GameState::Playing,
             timer: None,
         }
     }
@@ -66,29 +92,29 @@ impl Default for GameStateResource {
 
 impl GameStateResource {
     pub fn set_victory(&mut self, border_color: &mut Color) {
-        if let GameState::VICTORY = self.game_state {
+        if let GameState::Victory = self.game_state {
             return;
         }
         *border_color = Color::GREEN;
-        self.game_state = GameState::VICTORY;
+        self.game_state = GameState::Victory;
         self.timer = Some(Timer::from_seconds(1.0, false));
     }
 
     pub fn set_playing(&mut self, border_color: &mut Color) {
-        if let GameState::PLAYING = self.game_state {
+        if let GameState::Playing = self.game_state {
             return;
         }
         *border_color = Color::ORANGE;
-        self.game_state = GameState::PLAYING;
+        self.game_state = GameState::Playing;
         self.timer = None;
     }
 
     pub fn set_defeat(&mut self, border_color: &mut Color) {
-        if let GameState::DEFEAT = self.game_state {
+        if let GameState::Defeat = self.game_state {
             return;
         }
         *border_color = Color::RED;
-        self.game_state = GameState::DEFEAT;
+        self.game_state = GameState::Defeat;
         self.timer = Some(Timer::from_seconds(1.0, false));
     }
 }
@@ -101,9 +127,10 @@ fn reset(
     mut query_blocks: Query<&mut Block>,
     mut query_border: Query<&mut Color, With<Border>>,
     mut game_state: ResMut<GameStateResource>,
+    mut rng: ResMut<GameRng>,
     time: Res<Time>,
 ) {
-    if let GameState::PLAYING = game_state.game_state {
+    if let GameState::Playing = game_state.game_state {
         return;
     }
 
@@ -114,14 +141,14 @@ fn reset(
         let (mut ball_transform, mut ball) = ball_query.single_mut();
         ball_transform.translation.x = 0.0;
         ball_transform.translation.y = -255.5;
-        let vx: f32 = rand::random::<f32>() * 1.8 - 0.9;
+        let vx: f32 = rng.0.gen::<f32>() * 1.8 - 0.9;
         let mut aux: f32 = vx;
         if aux < 0.0 {
             aux = -aux;
         }
         let vy = 1.0 - aux;
         ball.velocity = vec2(vx, vy);
-        if let GameState::DEFEAT = game_state.game_state {
+        if let GameState::Defeat = game_state.game_state {
             ball.speed = 1.0;
         }
 
@@ -137,7 +164,12 @@ fn reset(
     }
 }
 
-fn setup(mut commands: Commands, _asset_server: Res<AssetServer>, _window: NonSend<Window>) {
+fn setup(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    _asset_server: Res<AssetServer>,
+    _window: NonSend<Window>,
+) {
     commands
         .spawn_bundle(TransformBundle::new(Transform::from_xyz(400.0, 300.0, 0.0)))
         .with_children(|parent| {
@@ -170,7 +202,7 @@ fn setup(mut commands: Commands, _asset_server: Res<AssetServer>, _window: NonSe
                 })
                 .insert(Player { speed: 0.7 });
             // Ball
-            let vx: f32 = rand::random::<f32>() * 1.8 - 0.9;
+            let vx: f32 = rng.0.gen::<f32>() * 1.8 - 0.9;
             let mut aux: f32 = vx;
             if aux < 0.0 {
                 aux = -aux;
@@ -207,7 +239,7 @@ fn setup(mut commands: Commands, _asset_server: Res<AssetServer>, _window: NonSe
                                 scale: vec3(width, height, 1.0),
                                 ..Default::default()
                             },
-                            color: Color::rgb(rand::random(), rand::random(), rand::random()),
+                            color: Color::rgb(rng.0.gen(), rng.0.gen(), rng.0.gen()),
                             ..Default::default()
                         })
                         .insert(Block {
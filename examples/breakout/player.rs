@@ -1,4 +1,4 @@
-use crate::{Board, GameState, GameStateResource};
+use super::{Board, GameState, GameStateResource};
 use arara::prelude::*;
 
 #[derive(Component)]
@@ -23,7 +23,7 @@ pub fn move_player(
     let (mut player_transform, player) = player_query.single_mut();
     let player_size = player_transform.scale.x / 2.0;
     let player_speed = 300.0 * player.speed * time.delta_seconds();
-    if let GameState::PLAYING = game_state.game_state {
+    if let GameState::Playing = game_state.game_state {
         if keyboard.pressed(KeyCode::Left) {
             player_transform.translation.x -= player_speed;
         } else if keyboard.pressed(KeyCode::Right) {
@@ -9,20 +9,25 @@ fn main() {
         .insert_resource(CoordinateSystem {
             count: 10,
             lenght: 10.0,
-            radius: 1.0,
         })
         .add_plugin(LogDiagnosticPlugin {
             wait_duration: Duration::from_secs(3),
         })
-        .add_startup_system(add_shapes.system())
-        .insert_resource(BPLight {
-            position: vec3(5.0, 30.0, 55.0),
-        })
+        .add_plugin(CastlePlugin)
         // .insert_resource(Camera::new(vec3(0.0, 30.0, 70.0), -FRAC_PI_2, -FRAC_PI_6))
         // .insert_resource(FlyCamera::new(20.0, 0.5))
         .run()
 }
 
+/// The castle scene, shared with the golden image tests.
+pub struct CastlePlugin;
+
+impl Plugin for CastlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(add_shapes.system());
+    }
+}
+
 #[derive(Component)]
 struct Tower;
 #[derive(Component)]
@@ -35,6 +40,19 @@ fn add_shapes(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
         ..Default::default()
     });
 
+    // ------------- Sun ------------------
+    commands.spawn_bundle(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            intensity: 0.8,
+            shadows_enabled: true,
+            shadow_extent: 60.0,
+            shadow_depth: 150.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(5.0, 30.0, 55.0).looking_at_xyz(0.0, 0.0, 0.0),
+        ..Default::default()
+    });
+
     // ------------- Floor ------------------
     let floor_height = 10.0;
     commands.spawn_bundle(SimpleMeshBundle {
@@ -81,6 +99,15 @@ fn add_shapes(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
                         color: Color::DARK_GRAY,
                         ..Default::default()
                     });
+                    tower.spawn_bundle(PointLightBundle {
+                        point_light: PointLight {
+                            color: Color::ORANGE,
+                            intensity: 1.5,
+                            range: 25.0,
+                        },
+                        transform: Transform::from_xyz(x, tower_height + 3.0, z),
+                        ..Default::default()
+                    });
                     let amount = 20;
                     let step_angle = 2.0 * PI / amount as f32;
                     for i in 0..amount {
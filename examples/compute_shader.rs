@@ -0,0 +1,64 @@
+use arara::prelude::*;
+use arara::render::{CachedComputePipelineId, ComputePipelineDescriptor, RenderPipelineCache};
+use arara::window::Window;
+use glium::uniforms::UniformBuffer;
+
+const VALUES: usize = 256;
+const WORKGROUP_SIZE: usize = 64;
+
+/// Computes the squares of the first numbers on the GPU.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .init_resource::<SquaresPipeline>()
+        .add_system(compute_squares)
+        .run()
+}
+
+struct SquaresPipeline {
+    id: CachedComputePipelineId,
+}
+
+impl FromWorld for SquaresPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().unwrap();
+        let shader = asset_server.load("shaders/squares.comp");
+        let mut pipeline_cache = world
+            .get_non_send_resource_mut::<RenderPipelineCache>()
+            .unwrap();
+        Self {
+            id: pipeline_cache.queue_compute(ComputePipelineDescriptor {
+                shader,
+                shader_defs: Vec::new(),
+            }),
+        }
+    }
+}
+
+fn compute_squares(
+    window: NonSend<Window>,
+    pipeline_cache: NonSend<RenderPipelineCache>,
+    pipeline: Res<SquaresPipeline>,
+    mut done: Local<bool>,
+) {
+    if *done {
+        return;
+    }
+    // the pipeline is compiled once its shader is loaded
+    let pipeline = match pipeline_cache.get_compute(pipeline.id) {
+        Some(pipeline) => pipeline,
+        None => return,
+    };
+
+    let buffer: UniformBuffer<[f32]> =
+        UniformBuffer::empty_unsized(window.facade(), VALUES * std::mem::size_of::<f32>()).unwrap();
+    pipeline.shader.execute(
+        glium::uniform! { Values: &buffer },
+        (VALUES / WORKGROUP_SIZE) as u32,
+        1,
+        1,
+    );
+    let values = buffer.read().unwrap();
+    info!("squares: {:?}", &values[..16]);
+    *done = true;
+}
@@ -0,0 +1,84 @@
+use arara::prelude::*;
+use arara::render::implement_uniform_block;
+use arara::render::Shader;
+
+/// A striped material drawn with `assets/shaders/custom_material.frag`.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "0f3a3a55-6d2e-4c8b-9a0d-5e7b1f2c4d61"]
+struct StripedMaterial {
+    color: Color,
+    stripe_width: f32,
+}
+
+/// The `material` uniform block of the fragment shader.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StripedMaterialUniform {
+    u_color: [f32; 4],
+    u_stripe_width: f32,
+}
+
+implement_uniform_block!(StripedMaterialUniform, u_color, u_stripe_width);
+
+impl Material for StripedMaterial {
+    type Uniform = StripedMaterialUniform;
+
+    fn fragment_shader(asset_server: &AssetServer) -> Handle<Shader> {
+        asset_server.load("shaders/custom_material.frag")
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        StripedMaterialUniform {
+            u_color: self.color.into(),
+            u_stripe_width: self.stripe_width,
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(MaterialPlugin::<StripedMaterial>::default())
+        .add_startup_system(setup)
+        .run()
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StripedMaterial>>,
+) {
+    commands.spawn_bundle(CustomMaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Sphere::new(32, 16, 1.0))),
+        material: materials.add(StripedMaterial {
+            color: Color::ORANGE,
+            stripe_width: 0.05,
+        }),
+        transform: Transform::from_xyz(0.0, 1.0, 0.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(CustomMaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(10.0, 0.1, 10.0))),
+        material: materials.add(StripedMaterial {
+            color: Color::DARK_GRAY,
+            stripe_width: 0.1,
+        }),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 2.0,
+            range: 50.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0.0, 5.0, 3.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(FlyCameraBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 7.0).looking_at_xyz(0.0, 1.0, 0.0),
+        ..Default::default()
+    });
+}
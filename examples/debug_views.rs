@@ -0,0 +1,90 @@
+use arara::prelude::*;
+
+/// Space toggles the wireframes, 1 to 4 select the regular shading, the normals, the texture
+/// coordinates and the depth.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(select_debug_view)
+        .run()
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Capsule::new(
+            0.5,
+            1.0,
+            0,
+            16,
+            32,
+            CapsuleUvProfile::Aspect,
+        ))),
+        transform: Transform::from_xyz(-2.5, 1.0, 0.0),
+        material: materials.add(StandardMaterial::from(Color::ORANGE)),
+        ..Default::default()
+    });
+
+    // always drawn as a wireframe
+    commands
+        .spawn_bundle(MaterialMeshBundle {
+            mesh: meshes.add(Mesh::from(Icosphere::new(2, 1.0))),
+            transform: Transform::from_xyz(0.0, 1.0, 0.0),
+            material: materials.add(StandardMaterial::from(Color::ANTIQUE_WHITE)),
+            ..Default::default()
+        })
+        .insert(Wireframe);
+
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Thorus::new(32, 16, 1.0, 0.3))),
+        transform: Transform::from_xyz(2.5, 1.0, 0.0),
+        material: materials.add(StandardMaterial::from(Color::CYAN)),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(10.0, 0.1, 10.0))),
+        material: materials.add(StandardMaterial::from(Color::DARK_GRAY)),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 2.0,
+            range: 50.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0.0, 5.0, 3.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(FlyCameraBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 7.0).looking_at_xyz(0.0, 1.0, 0.0),
+        ..Default::default()
+    });
+}
+
+fn select_debug_view(
+    keyboard: Res<Input<KeyCode>>,
+    mut wireframe_config: ResMut<WireframeConfig>,
+    mut debug_view: ResMut<DebugView>,
+) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        wireframe_config.global = !wireframe_config.global;
+    }
+    let views = [
+        (KeyCode::Key1, DebugView::None),
+        (KeyCode::Key2, DebugView::Normals),
+        (KeyCode::Key3, DebugView::Uvs),
+        (KeyCode::Key4, DebugView::Depth),
+    ];
+    for (key, view) in views {
+        if keyboard.just_pressed(key) {
+            *debug_view = view;
+        }
+    }
+}
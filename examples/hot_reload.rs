@@ -0,0 +1,33 @@
+use arara::{asset::AssetServerSettings, prelude::*};
+
+// Run with `cargo run --example hot_reload --features filesystem_watcher`, then edit
+// `assets/textures/madeira.jpeg` or `assets/shaders/fragment_shader_src.frag` while it runs.
+fn main() {
+    App::new()
+        // read by the AssetPlugin, it has to be inserted before the plugins are added
+        .insert_resource(AssetServerSettings {
+            watch_for_changes: true,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run()
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(SimpleMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(2.0, 2.0, 2.0))),
+        image: asset_server.load("textures/madeira.jpeg"),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(3.0, 5.0, 4.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(FlyCameraBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 6.0).looking_at_xyz(0.0, 0.0, 0.0),
+        ..Default::default()
+    });
+}
@@ -7,16 +7,13 @@ fn main() {
         .add_plugin(ParticleSystemPlugin)
         .add_plugin(CoordinateSystemPlugin)
         .add_plugin(FrameTimeDiagnosticPlugin)
-        .add_plugin(EntityCountDiagnosticPlugin::default())
+        .add_plugin(EntityCountDiagnosticPlugin)
         .add_plugin(AssetCountDiagnosticsPlugin::<Image>::default())
         .add_plugin(LogDiagnosticPlugin {
             wait_duration: Duration::from_secs(10),
         })
         .add_startup_system(add_color_only_shader.system())
         .add_startup_system(add_shapes.system())
-        .insert_resource(BPLight {
-            position: vec3(10.0, 10.0, 0.0),
-        })
         // .insert_resource(Camera::new(vec3(0.0, 5.0, 5.0), -FRAC_PI_2, -FRAC_PI_6))
         .run()
 }
@@ -65,7 +62,6 @@ fn add_shapes(
             particle_color: ColorOrGradient::Gradient(fire_gradient.clone()),
             particle_mesh: meshes.add(Mesh::from(Square::new(0.5, 0.5))),
             timer: Timer::from_seconds(0.3, true),
-            ..Default::default()
         });
 
     commands
@@ -84,7 +80,6 @@ fn add_shapes(
             particle_color: ColorOrGradient::Gradient(fire_gradient.clone()),
             particle_mesh: meshes.add(Mesh::from(Square::new(0.5, 0.5))),
             timer: Timer::from_seconds(0.3, true),
-            ..Default::default()
         });
 
     commands
@@ -103,7 +98,6 @@ fn add_shapes(
             particle_color: ColorOrGradient::Gradient(fire_gradient),
             particle_mesh: meshes.add(Mesh::from(Square::new(0.5, 0.5))),
             timer: Timer::from_seconds(0.3, true),
-            ..Default::default()
         });
 
     // ------------------------ smoke -----------------------
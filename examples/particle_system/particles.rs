@@ -7,16 +7,13 @@ fn main() {
         .add_plugin(ParticleSystemPlugin)
         .add_plugin(CoordinateSystemPlugin)
         .add_plugin(FrameTimeDiagnosticPlugin)
-        .add_plugin(EntityCountDiagnosticPlugin::default())
+        .add_plugin(EntityCountDiagnosticPlugin)
         .add_plugin(LogDiagnosticPlugin {
             wait_duration: Duration::from_secs(3),
         })
         .add_startup_system(add_color_only_shader)
         .add_startup_system(add_shapes)
         .add_startup_system(add_camera)
-        .insert_resource(BPLight {
-            position: vec3(10.0, 10.0, 0.0),
-        })
         .run()
 }
 
@@ -46,7 +43,6 @@ fn add_shapes(
             particle_mesh: meshes.add(Mesh::from(Square::new(0.2, 0.5))),
             timer: Timer::from_seconds(0.5, true),
             billboard: Some(Billboard::ViewPlane),
-            ..Default::default()
         });
 }
 
@@ -0,0 +1,60 @@
+use arara::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run()
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // emissive colors above 1 are kept by the HDR target and picked up by the bloom
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Sphere::new(32, 16, 1.0))),
+        transform: Transform::from_xyz(-1.5, 1.0, 0.0),
+        material: materials.add(StandardMaterial {
+            base_color: Color::BLACK,
+            emissive: Color::rgb(4.0, 1.5, 0.5),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(1.5, 1.5, 1.5))),
+        transform: Transform::from_xyz(1.5, 1.0, 0.0),
+        material: materials.add(StandardMaterial::from(Color::ANTIQUE_WHITE)),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(10.0, 0.1, 10.0))),
+        material: materials.add(StandardMaterial::from(Color::DARK_GRAY)),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 2.0,
+            range: 50.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0.0, 5.0, 3.0),
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(FlyCameraBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 7.0).looking_at_xyz(0.0, 1.0, 0.0),
+            ..Default::default()
+        })
+        .insert(PostProcessing::new(vec![
+            PostProcessPass::Bloom(Bloom::default()),
+            PostProcessPass::Tonemapping(Tonemapping::default()),
+            PostProcessPass::Fxaa,
+        ]));
+}
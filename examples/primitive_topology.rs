@@ -0,0 +1,130 @@
+use arara::prelude::*;
+
+const SEGMENTS: usize = 32;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(add_camera)
+        .add_startup_system(add_light)
+        .add_startup_system(add_shapes)
+        .add_system(rotate_shapes)
+        .run();
+}
+
+#[derive(Component)]
+struct Shape;
+
+fn rotate_shapes(time: Res<Time>, mut query: Query<&mut Transform, With<Shape>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(FRAC_PI_4 * time.delta_seconds()));
+    }
+}
+
+/// The same ring of vertices drawn with every [`PrimitiveTopology`], from left to right.
+fn add_shapes(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    let shapes = [
+        (ring_points(), Color::WHITE),
+        (spokes(), Color::YELLOW),
+        (spiral(), Color::CYAN),
+        (disk(), Color::ORANGE),
+        (ribbon(), Color::PURPLE),
+    ];
+    let spacing = 2.5;
+    let first = -spacing * (shapes.len() - 1) as f32 / 2.0;
+    for (i, (mesh, color)) in shapes.into_iter().enumerate() {
+        commands
+            .spawn_bundle(SimpleMeshBundle {
+                mesh: meshes.add(mesh),
+                transform: Transform::from_xyz(first + spacing * i as f32, 0.0, 0.0),
+                color,
+                ..Default::default()
+            })
+            .insert(Shape);
+    }
+}
+
+fn ring(radius: f32) -> impl Iterator<Item = [f32; 3]> {
+    (0..SEGMENTS).map(move |i| {
+        let angle = i as f32 / SEGMENTS as f32 * TAU;
+        [radius * angle.cos(), radius * angle.sin(), 0.0]
+    })
+}
+
+fn mesh(topology: PrimitiveTopology, positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Mesh {
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    let mut mesh = Mesh::new(topology)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.indices = indices;
+    mesh
+}
+
+/// A vertex every few degrees of two rings.
+fn ring_points() -> Mesh {
+    let positions: Vec<_> = ring(1.0).chain(ring(0.5)).collect();
+    let indices = (0..positions.len() as u32).collect();
+    mesh(PrimitiveTopology::PointList, positions, indices)
+}
+
+/// A line from the center to every vertex of the ring.
+fn spokes() -> Mesh {
+    let positions: Vec<_> = std::iter::once([0.0; 3]).chain(ring(1.0)).collect();
+    let indices = (1..positions.len() as u32).flat_map(|i| [0, i]).collect();
+    mesh(PrimitiveTopology::LineList, positions, indices)
+}
+
+/// A single line going around the ring while getting closer to its center.
+fn spiral() -> Mesh {
+    let positions: Vec<_> = (0..SEGMENTS * 3)
+        .map(|i| {
+            let angle = i as f32 / SEGMENTS as f32 * TAU;
+            let radius = 1.0 - i as f32 / (SEGMENTS * 3) as f32;
+            [radius * angle.cos(), radius * angle.sin(), 0.0]
+        })
+        .collect();
+    let indices = (0..positions.len() as u32).collect();
+    mesh(PrimitiveTopology::LineStrip, positions, indices)
+}
+
+/// A triangle from the center to every edge of the ring.
+fn disk() -> Mesh {
+    let positions: Vec<_> = std::iter::once([0.0; 3]).chain(ring(1.0)).collect();
+    let indices = (0..SEGMENTS as u32)
+        .flat_map(|i| [0, i + 1, (i + 1) % SEGMENTS as u32 + 1])
+        .collect();
+    mesh(PrimitiveTopology::TriangleList, positions, indices)
+}
+
+/// A band between two rings, alternating between their vertices from the inner one so the
+/// triangles face the camera.
+fn ribbon() -> Mesh {
+    let outer: Vec<_> = ring(1.0).collect();
+    let inner: Vec<_> = ring(0.6).collect();
+    let positions: Vec<_> = outer.into_iter().chain(inner).collect();
+    let indices = (0..=SEGMENTS as u32)
+        .flat_map(|i| {
+            let i = i % SEGMENTS as u32;
+            [i + SEGMENTS as u32, i]
+        })
+        .collect();
+    mesh(PrimitiveTopology::TriangleStrip, positions, indices)
+}
+
+fn add_camera(mut commands: Commands) {
+    commands.spawn_bundle(FlyCameraBundle {
+        transform: Transform::from_xyz(0.0, 1.5, 12.0).looking_at_xyz(0.0, 0.0, 0.0),
+        ..Default::default()
+    });
+}
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0.0, 3.0, 6.0),
+        ..Default::default()
+    });
+}
@@ -15,7 +15,7 @@ fn main() {
         })
         .add_startup_system(add_shapes)
         .add_startup_system(add_camera)
-        .insert_resource(BPLight::new(-5.0, 10.0, 0.0))
+        .add_startup_system(add_light)
         .run()
 }
 
@@ -23,6 +23,7 @@ fn add_shapes(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let img0: Handle<Image> = asset_server.load("textures/joaozinho.png");
     let monkey_mesh = asset_server.load("models/Monkey.gltf#Mesh0/Primitive0");
@@ -41,10 +42,15 @@ fn add_shapes(
         ..Default::default()
     });
 
-    commands.spawn_bundle(SimpleMeshBundle {
+    commands.spawn_bundle(MaterialMeshBundle {
         mesh: meshes.add(Mesh::from(Sphere::new(32, 16, 1.0))),
         transform: Transform::from_xyz(-2.0, 1.5, -1.0),
-        color: Color::ANTIQUE_WHITE,
+        material: materials.add(StandardMaterial {
+            base_color: Color::ANTIQUE_WHITE,
+            specular_strength: 1.0,
+            shininess: 128.0,
+            ..Default::default()
+        }),
         ..Default::default()
     });
 
@@ -63,13 +69,18 @@ fn add_shapes(
         ..Default::default()
     });
 
-    commands.spawn_bundle(SimpleMeshBundle {
+    commands.spawn_bundle(MaterialMeshBundle {
         mesh: meshes.add(Mesh::from(Thorus::new(32, 24, 1.0, 0.5))),
         transform: Transform {
             translation: vec3(-3.0, 3.0, 3.0),
             ..Default::default()
         },
-        color: Color::MIDNIGHT_BLUE,
+        material: materials.add(StandardMaterial {
+            base_color: Color::MIDNIGHT_BLUE,
+            emissive: Color::rgb(0.0, 0.1, 0.4),
+            double_sided: true,
+            ..Default::default()
+        }),
         ..Default::default()
     });
 
@@ -98,3 +109,14 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(-5.0, 10.0, 0.0),
+        ..Default::default()
+    });
+}
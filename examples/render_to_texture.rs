@@ -0,0 +1,74 @@
+use arara::prelude::*;
+
+/// Spins the cube seen by the camera rendering into the image.
+#[derive(Component)]
+struct Spinning;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(spin)
+        .run()
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // the camera draws into this image, its size is the size of the render target
+    let size = 512;
+    let image = images.add(Image::new(
+        vec![0; (size * size * 4) as usize],
+        (size, size),
+        false,
+    ));
+
+    // only the camera rendering into the image sees layer 1
+    commands
+        .spawn_bundle(SimpleMeshBundle {
+            mesh: meshes.add(Mesh::from(Cuboid::new(1.0, 1.0, 1.0))),
+            transform: Transform::from_xyz(0.0, 0.0, -100.0),
+            color: Color::ORANGE,
+            ..Default::default()
+        })
+        .insert(Spinning)
+        .insert(RenderLayers::layer(1));
+
+    commands
+        .spawn_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_xyz(0.0, 1.5, -97.0).looking_at_xyz(0.0, 0.0, -100.0),
+            ..Default::default()
+        })
+        .insert(RenderTarget::Image(image.clone()))
+        .insert(RenderLayers::layer(1));
+
+    // a monitor showing the image on the main camera layer
+    commands.spawn_bundle(SimpleMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(4.0, 4.0, 0.1))),
+        transform: Transform::from_xyz(0.0, 2.0, 0.0),
+        image,
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(FlyCameraBundle {
+        transform: Transform::from_xyz(3.0, 4.0, 6.0).looking_at_xyz(0.0, 2.0, 0.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 200.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(2.0, 5.0, -50.0),
+        ..Default::default()
+    });
+}
+
+fn spin(time: Res<Time>, mut query: Query<&mut Transform, With<Spinning>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(time.delta_seconds()));
+    }
+}
@@ -9,14 +9,21 @@ fn main() {
         .add_plugin(LogDiagnosticPlugin {
             wait_duration: Duration::from_secs(1),
         })
-        .add_startup_system(add_shapes)
-        .add_startup_system(add_camera)
-        .insert_resource(BPLight {
-            position: vec3(-2.0, 5.0, 3.0),
-        })
+        .add_plugin(RobotPlugin)
         .run()
 }
 
+/// The robot scene, shared with the golden image tests.
+pub struct RobotPlugin;
+
+impl Plugin for RobotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(add_shapes)
+            .add_startup_system(add_camera)
+            .add_startup_system(add_lights);
+    }
+}
+
 #[derive(Component)]
 struct Robot;
 #[derive(Component)]
@@ -437,3 +444,27 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_lights(mut commands: Commands) {
+    // ------------ Lights -----------------
+    commands.spawn_bundle(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            intensity: 0.6,
+            shadows_enabled: true,
+            shadow_extent: 10.0,
+            shadow_depth: 30.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(4.0, 10.0, 4.0).looking_at_xyz(0.0, 0.0, 0.0),
+        ..Default::default()
+    });
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            color: Color::ORANGE,
+            range: 15.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(-2.0, 5.0, 3.0),
+        ..Default::default()
+    });
+}
@@ -0,0 +1,109 @@
+use arara::prelude::*;
+use arara::render::implement_uniform_block;
+use arara::render::Shader;
+
+/// A material reflecting the skybox, drawn with `assets/shaders/reflective_material.frag`.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "8b7e2c1d-4f3a-4a9e-b6d2-1c5e7f9a3b20"]
+struct ReflectiveMaterial {
+    color: Color,
+    reflectivity: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ReflectiveMaterialUniform {
+    u_color: [f32; 4],
+    u_reflectivity: f32,
+}
+
+implement_uniform_block!(ReflectiveMaterialUniform, u_color, u_reflectivity);
+
+impl Material for ReflectiveMaterial {
+    type Uniform = ReflectiveMaterialUniform;
+
+    fn fragment_shader(asset_server: &AssetServer) -> Handle<Shader> {
+        asset_server.load("shaders/reflective_material.frag")
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        ReflectiveMaterialUniform {
+            u_color: self.color.into(),
+            u_reflectivity: self.reflectivity,
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(MaterialPlugin::<ReflectiveMaterial>::default())
+        .add_startup_system(setup)
+        .run()
+}
+
+/// An equirectangular panorama of a blue sky over a brown ground, with a sun towards `+Z`,
+/// behind the camera but visible in the reflections.
+fn sky_panorama() -> Image {
+    let (width, height) = (512, 256);
+    let mut data = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            // latitude from 1 at the zenith to -1 at the nadir
+            let latitude = 1.0 - 2.0 * (y as f32 + 0.5) / height as f32;
+            let longitude = (x as f32 + 0.5) / width as f32 - 0.5;
+            let sun = (longitude * 40.0).powi(2) + ((latitude - 0.2) * 20.0).powi(2) < 1.0;
+            let color = if sun {
+                [1.0, 0.95, 0.7]
+            } else if latitude >= 0.0 {
+                let t = latitude.powf(0.5);
+                [0.8 - 0.6 * t, 0.9 - 0.5 * t, 1.0 - 0.1 * t]
+            } else {
+                [0.35, 0.25, 0.15]
+            };
+            data.extend(color.iter().map(|channel| (channel * 255.0) as u8));
+            data.push(255);
+        }
+    }
+    Image::new(data, (width as u32, height as u32), false)
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut cubemaps: ResMut<Assets<Cubemap>>,
+    mut materials: ResMut<Assets<ReflectiveMaterial>>,
+) {
+    let sky = cubemaps.add(Cubemap::Equirectangular(images.add(sky_panorama())));
+
+    for (i, reflectivity) in [0.0, 0.5, 1.0].into_iter().enumerate() {
+        commands.spawn_bundle(CustomMaterialMeshBundle {
+            mesh: meshes.add(Mesh::from(Sphere::new(32, 16, 1.0))),
+            material: materials.add(ReflectiveMaterial {
+                color: Color::ORANGE,
+                reflectivity,
+            }),
+            transform: Transform::from_xyz(i as f32 * 2.5 - 2.5, 1.0, 0.0),
+            ..Default::default()
+        });
+    }
+
+    commands.spawn_bundle(SimpleMeshBundle {
+        mesh: meshes.add(Mesh::from(Cuboid::new(10.0, 0.1, 10.0))),
+        color: Color::DARK_GRAY,
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_xyz(0.0, 5.0, 10.0).looking_at_xyz(0.0, 0.0, 0.0),
+        ..Default::default()
+    });
+
+    commands
+        .spawn_bundle(FlyCameraBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 7.0).looking_at_xyz(0.0, 1.0, 0.0),
+            ..Default::default()
+        })
+        .insert(Skybox { cubemap: sky });
+}
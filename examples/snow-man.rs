@@ -7,15 +7,24 @@ fn main() {
         .add_plugin(FrameTimeDiagnosticPlugin)
         .add_plugin(EntityCountDiagnosticPlugin)
         .add_plugin(LogDiagnosticPlugin::default())
-        .add_plugin(ParticleSystemPlugin)
-        .add_startup_system(add_shapes)
-        .add_startup_system(add_camera)
-        .init_resource::<Timer>()
-        .insert_resource(BPLight::new(0.0, 10.0, 6.0))
-        .add_system(move_snowman)
+        .add_plugin(SnowManPlugin)
         .run()
 }
 
+/// The snow man scene, shared with the golden image tests.
+pub struct SnowManPlugin;
+
+impl Plugin for SnowManPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(ParticleSystemPlugin)
+            .add_startup_system(add_shapes)
+            .add_startup_system(add_camera)
+            .init_resource::<Timer>()
+            .add_startup_system(add_light)
+            .add_system(move_snowman);
+    }
+}
+
 #[derive(Component)]
 struct SnowMan;
 #[derive(Component)]
@@ -290,3 +299,14 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0.0, 10.0, 6.0),
+        ..Default::default()
+    });
+}
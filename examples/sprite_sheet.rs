@@ -2,6 +2,8 @@ use arara::prelude::*;
 
 fn main() {
     App::new()
+        // keeps the pixel art sharp
+        .insert_resource(DefaultImageSampler(SamplerDescriptor::nearest()))
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup)
         .add_system(animate_sprite_system)
@@ -3,9 +3,7 @@ use arara::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .insert_resource(BPLight {
-            position: vec3(3.0, 2.0, 5.0),
-        })
+        .add_startup_system(add_light)
         .add_startup_system(add_camera)
         .add_startup_system(add_cubes)
         .add_system(rotate_squares)
@@ -83,3 +81,14 @@ fn add_camera(mut commands: Commands) {
         ..Default::default()
     });
 }
+
+fn add_light(mut commands: Commands) {
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            range: 100.0,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(3.0, 2.0, 5.0),
+        ..Default::default()
+    });
+}
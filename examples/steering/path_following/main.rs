@@ -21,7 +21,7 @@ fn main() {
         .insert_resource(ClearColor(Color::WHITE))
         .add_startup_system(add_camera)
         .add_startup_system(setup)
-        .add_startup_system_to_stage(StartupStage::PostStartup, draw_paths)
+        .add_system(draw_paths)
         .add_system(steer)
         .add_system(move_speedy)
         .add_system(spawn_vehicle_on_mouse_click)
@@ -115,7 +115,7 @@ fn main() {
             let (width, height) = frame.get_dimensions();
             let aspect_ratio = height as f32 / width as f32;
 
-            let fov: f32 = 3.141592 / 3.0;
+            let fov = std::f32::consts::FRAC_PI_3;
             let zfar = 1024.0;
             let znear = 0.1;
 
@@ -173,15 +173,12 @@ fn main() {
             std::time::Instant::now() + std::time::Duration::from_nanos(16_666_667);
 
         *control_flow = glutin::event_loop::ControlFlow::WaitUntil(next_frame_time);
-        match ev {
-            glutin::event::Event::WindowEvent { event, .. } => match event {
-                glutin::event::WindowEvent::CloseRequested => {
-                    *control_flow = glutin::event_loop::ControlFlow::Exit;
-                    return;
-                }
-                _ => return,
-            },
-            _ => (),
+        if let glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } = ev
+        {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
         }
     });
 }
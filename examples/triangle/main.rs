@@ -104,7 +104,7 @@ fn main() {
         frame
             .draw(
                 &vertex_buffer,
-                &indices,
+                indices,
                 &program,
                 &uniforms,
                 &Default::default(),
@@ -116,15 +116,12 @@ fn main() {
             std::time::Instant::now() + std::time::Duration::from_nanos(16_666_667);
 
         *control_flow = glutin::event_loop::ControlFlow::WaitUntil(next_frame_time);
-        match ev {
-            glutin::event::Event::WindowEvent { event, .. } => match event {
-                glutin::event::WindowEvent::CloseRequested => {
-                    *control_flow = glutin::event_loop::ControlFlow::Exit;
-                    return;
-                }
-                _ => return,
-            },
-            _ => (),
+        if let glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } = ev
+        {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
         }
     });
 }
@@ -13,9 +13,22 @@ impl PluginGroup for DefaultPlugins {
         group.add(arara_render::RenderPlugin);
         group.add(arara_core_pipeline::CorePipelinePlugin);
         group.add(arara_sprite::SpritePlugin);
+        group.add(arara_render::DebugLinesPlugin);
         group.add(arara_transform::TransformPlugin);
         group.add(arara_camera::CameraPlugin);
         group.add(arara_camera::FlyCameraPlugin);
         group.add(arara_gltf::GltfPlugin);
     }
 }
+
+/// [`DefaultPlugins`] rendering into an offscreen framebuffer instead of a window.
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        DefaultPlugins.build(group);
+        group
+            .add_after::<arara_window::WindowPlugin, _>(arara_window::HeadlessWindowPlugin)
+            .disable::<arara_window::WindowPlugin>();
+    }
+}
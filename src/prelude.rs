@@ -14,5 +14,5 @@ pub use crate::{
     time::prelude::*,
     transform::prelude::*,
     window::prelude::*,
-    DefaultPlugins,
+    DefaultPlugins, HeadlessPlugins,
 };
@@ -0,0 +1,60 @@
+//! Golden image tests of the examples, they need an OpenGL context without a display server, e.g.
+//! Mesa's surfaceless EGL platform.
+//!
+//! Update the images in `tests/golden` with `ARARA_UPDATE_GOLDEN=1 cargo test --test golden`.
+
+#[allow(dead_code, clippy::upper_case_acronyms, clippy::type_complexity)]
+#[path = "../examples/breakout/main.rs"]
+mod breakout;
+#[allow(dead_code)]
+#[path = "../examples/castle.rs"]
+mod castle;
+#[allow(dead_code)]
+#[path = "../examples/robot.rs"]
+mod robot;
+#[allow(dead_code)]
+#[path = "../examples/snow-man.rs"]
+mod snow_man;
+
+use arara::logger::LoggerPlugin;
+use arara::prelude::*;
+use arara_particle_system::ParticleRng;
+use arara_test::GoldenTest;
+
+fn headless_plugins(app: &mut App) -> &mut App {
+    // Every test builds its own app in the same process, the global logger can only be set once.
+    app.add_plugins_with(HeadlessPlugins, |group| group.disable::<LoggerPlugin>())
+}
+
+#[test]
+fn breakout() {
+    GoldenTest::new("breakout").size(800, 600).assert(|app| {
+        headless_plugins(app)
+            .insert_resource(breakout::GameRng::seeded(0))
+            .add_plugin(breakout::BreakoutPlugin);
+    });
+}
+
+#[test]
+fn castle() {
+    GoldenTest::new("castle").assert(|app| {
+        headless_plugins(app).add_plugin(castle::CastlePlugin);
+    });
+}
+
+#[test]
+fn robot() {
+    GoldenTest::new("robot").assert(|app| {
+        headless_plugins(app).add_plugin(robot::RobotPlugin);
+    });
+}
+
+#[test]
+fn snow_man() {
+    GoldenTest::new("snow_man").assert(|app| {
+        // The snow particles spawn at the same positions on every run
+        headless_plugins(app)
+            .insert_resource(ParticleRng::seeded(0))
+            .add_plugin(snow_man::SnowManPlugin);
+    });
+}